@@ -13,7 +13,7 @@ use tracing::Instrument;
 use crate::{
     cli::Request,
     client::{Client, PlayerRequest},
-    config::get_cache_folder_path,
+    config::{self, get_cache_folder_path, SmartPlaylist},
     state::{
         AlbumId, ArtistId, Context, ContextId, Id, PlayableId, Playback, PlaybackMetadata,
         PlaylistId, SharedState, TrackId,
@@ -23,7 +23,7 @@ use rspotify::prelude::{BaseClient, OAuthClient};
 
 use super::{
     Command, Deserialize, GetRequest, IdOrName, ItemId, ItemType, Key, PlaylistCommand, Response,
-    Serialize, MAX_REQUEST_SIZE,
+    Serialize, SmartPlaylistCommand, MAX_REQUEST_SIZE,
 };
 
 pub async fn start_socket(client: Client, socket: UdpSocket, state: Option<SharedState>) {
@@ -169,10 +169,21 @@ async fn handle_socket_request(
             let resp = handle_playlist_request(client, command).await?;
             Ok(resp.into_bytes())
         }
+        Request::SmartPlaylist(command) => {
+            let resp = handle_smart_playlist_request(client, command).await?;
+            Ok(resp.into_bytes())
+        }
         Request::Search { query } => {
             let resp = handle_search_request(client, query).await?;
             Ok(resp)
         }
+        Request::Undo => {
+            let resp = match client.undo(state).await? {
+                Some(desc) => format!("Undid {desc}"),
+                None => "Nothing to undo".to_string(),
+            };
+            Ok(resp.into_bytes())
+        }
     }
 }
 
@@ -206,6 +217,10 @@ async fn handle_get_key_request(
             let albums = client.current_user_saved_albums().await?;
             serde_json::to_vec(&albums)?
         }
+        Key::UserSavedEpisodes => {
+            let episodes = client.current_user_saved_episodes().await?;
+            serde_json::to_vec(&episodes)?
+        }
         Key::UserFollowedArtists => {
             let artists = client.current_user_followed_artists().await?;
             serde_json::to_vec(&artists)?
@@ -747,3 +762,162 @@ async fn playlist_import(
 
     Ok(result)
 }
+
+async fn handle_smart_playlist_request(
+    client: &Client,
+    command: SmartPlaylistCommand,
+) -> Result<String> {
+    match command {
+        SmartPlaylistCommand::Sync { name, replace } => {
+            let smart_playlist_config = &config::get_config().smart_playlist_config;
+            let smart_playlists = match name {
+                Some(name) => vec![smart_playlist_config
+                    .find_smart_playlist(&name)
+                    .with_context(|| format!("Cannot find smart playlist with name='{name}'"))?],
+                None => smart_playlist_config.smart_playlists.iter().collect(),
+            };
+            if smart_playlists.is_empty() {
+                return Ok("No smart playlist is defined".to_string());
+            }
+
+            let mut results = Vec::new();
+            for playlist in smart_playlists {
+                results.push(smart_playlist_sync(client, playlist, replace).await?);
+            }
+            Ok(results.join("\n"))
+        }
+    }
+}
+
+/// The description of playlists created by `smart sync`
+const SMART_PLAYLIST_DESCRIPTION: &str = "Smart playlist synced by spotify_player";
+
+/// Gets the Spotify playlist a smart playlist is synced into.
+///
+/// If the smart playlist doesn't specify a playlist ID, the playlist with the same name created by
+/// `smart sync` is used, or a new playlist is created if no such playlist is found.
+/// Returns the playlist's ID and whether it was newly created.
+async fn smart_playlist_target(
+    client: &Client,
+    playlist: &SmartPlaylist,
+) -> Result<(PlaylistId<'static>, bool)> {
+    if let Some(id) = &playlist.playlist_id {
+        return Ok((PlaylistId::from_id_or_uri(id)?.into_static(), false));
+    }
+
+    // only playlists created by `smart sync` are synced into, so that the user's other playlists
+    // with the same name are never modified
+    let uid = client.current_user().await?.id;
+    for p in client
+        .current_user_playlists()
+        .await?
+        .into_iter()
+        .filter(|p| p.name == playlist.name && p.owner.1 == uid)
+    {
+        let description = client
+            .playlist(p.id.as_ref(), None, None)
+            .await?
+            .description;
+        if description.as_deref() == Some(SMART_PLAYLIST_DESCRIPTION) {
+            return Ok((p.id, false));
+        }
+    }
+
+    let p = client
+        .user_playlist_create(
+            uid,
+            &playlist.name,
+            Some(false),
+            None,
+            Some(SMART_PLAYLIST_DESCRIPTION),
+        )
+        .await?;
+    Ok((p.id, true))
+}
+
+/// Syncs a smart playlist into its Spotify playlist.
+///
+/// By default, only the differences are applied: tracks that are no longer selected by the smart playlist
+/// are deleted and newly selected tracks are added. If `replace` is specified, the playlist's tracks are
+/// replaced with the smart playlist's tracks in their order.
+async fn smart_playlist_sync(
+    client: &Client,
+    playlist: &SmartPlaylist,
+    replace: bool,
+) -> Result<String> {
+    let tracks = client.smart_playlist_tracks(playlist).await?;
+    let (id, created) = smart_playlist_target(client, playlist).await?;
+
+    let mut lines = Vec::new();
+    if created {
+        lines.push(format!("Created playlist {}:{}", id.id(), playlist.name));
+    }
+
+    if replace {
+        let mut chunks = tracks.chunks(TRACK_BUFFER_CAP);
+        let first_chunk = chunks.next().unwrap_or_default();
+        client
+            .playlist_replace_items(
+                id.as_ref(),
+                first_chunk.iter().map(|t| PlayableId::Track(t.id.as_ref())),
+            )
+            .await?;
+        for chunk in chunks {
+            client
+                .playlist_add_items(
+                    id.as_ref(),
+                    chunk.iter().map(|t| PlayableId::Track(t.id.as_ref())),
+                    None,
+                )
+                .await?;
+        }
+        lines.push(format!(
+            "Replaced tracks of {}:{} with {} tracks",
+            id.id(),
+            playlist.name,
+            tracks.len()
+        ));
+    } else {
+        let Context::Playlist {
+            tracks: current_tracks,
+            ..
+        } = client.playlist_context(id.clone()).await?
+        else {
+            anyhow::bail!("expect a playlist context");
+        };
+        let current_ids = current_tracks.iter().map(|t| &t.id).collect::<HashSet<_>>();
+        let new_ids = tracks.iter().map(|t| &t.id).collect::<HashSet<_>>();
+
+        let deleted_ids = current_ids
+            .iter()
+            .filter(|id| !new_ids.contains(*id))
+            .map(|id| PlayableId::Track(id.as_ref()))
+            .collect::<Vec<_>>();
+        for chunk in deleted_ids.chunks(TRACK_BUFFER_CAP) {
+            client
+                .playlist_remove_all_occurrences_of_items(id.as_ref(), chunk.to_vec(), None)
+                .await?;
+        }
+
+        let added_ids = tracks
+            .iter()
+            .filter(|t| !current_ids.contains(&t.id))
+            .map(|t| PlayableId::Track(t.id.as_ref()))
+            .collect::<Vec<_>>();
+        for chunk in added_ids.chunks(TRACK_BUFFER_CAP) {
+            client
+                .playlist_add_items(id.as_ref(), chunk.to_vec(), None)
+                .await?;
+        }
+
+        lines.push(format!(
+            "Synced {}:{}: {} tracks added, {} tracks deleted",
+            id.id(),
+            playlist.name,
+            added_ids.len(),
+            deleted_ids.len()
+        ));
+    }
+
+    Ok(lines.join("\n"))
+}
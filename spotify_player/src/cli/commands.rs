@@ -144,10 +144,50 @@ pub fn init_like_command() -> Command {
         )
 }
 
+pub fn init_undo_command() -> Command {
+    Command::new("undo").about("Undo the most recent deletion from a playlist or the library")
+}
+
 pub fn init_authenticate_command() -> Command {
     Command::new("authenticate").about("Authenticate the application")
 }
 
+pub fn init_config_subcommand() -> Command {
+    Command::new("config")
+        .about("Inspect the application's configurations")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("check")
+                .about("Check the configuration files for unknown keys and invalid values"),
+        )
+        .subcommand(
+            Command::new("dump")
+                .about("Print the effective application configurations, including default values"),
+        )
+}
+
+pub fn init_smart_subcommand() -> Command {
+    Command::new("smart")
+        .about("Smart playlists defined in the smart playlist config file")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("sync")
+                .about("Sync smart playlists into Spotify playlists")
+                .arg(
+                    Arg::new("name")
+                        .required(false)
+                        .help("Name of the smart playlist to sync. All smart playlists are synced if not specified"),
+                )
+                .arg(
+                    Arg::new("replace")
+                        .short('r')
+                        .long("replace")
+                        .action(ArgAction::SetTrue)
+                        .help("Replace the playlists' tracks in the smart playlists' order instead of only adding new tracks and removing stale ones"),
+                ),
+        )
+}
+
 pub fn init_generate_command() -> Command {
     Command::new("generate")
         .about("Generate shell completion for the application CLI")
@@ -2,7 +2,7 @@ use crate::{auth::AuthConfig, client};
 
 use super::{
     config, init_cli, start_socket, Command, ContextType, GetRequest, IdOrName, ItemType, Key,
-    PlaylistCommand, PlaylistId, Request, Response, MAX_REQUEST_SIZE,
+    PlaylistCommand, PlaylistId, Request, Response, SmartPlaylistCommand, MAX_REQUEST_SIZE,
 };
 use anyhow::{Context, Result};
 use clap::{ArgMatches, Id};
@@ -204,6 +204,7 @@ pub fn handle_cli_subcommand(cmd: &str, args: &ArgMatches) -> Result<()> {
         "get" => handle_get_subcommand(args),
         "playback" => handle_playback_subcommand(args)?,
         "playlist" => handle_playlist_subcommand(args)?,
+        "smart" => handle_smart_subcommand(args),
         "connect" => Request::Connect(get_id_or_name(args)),
         "like" => Request::Like {
             unlike: args.get_flag("unlike"),
@@ -214,6 +215,7 @@ pub fn handle_cli_subcommand(cmd: &str, args: &ArgMatches) -> Result<()> {
                 .expect("query is required")
                 .to_owned(),
         },
+        "undo" => Request::Undo,
         _ => unreachable!(),
     };
 
@@ -235,6 +237,54 @@ pub fn handle_cli_subcommand(cmd: &str, args: &ArgMatches) -> Result<()> {
     }
 }
 
+/// Handles the `config` subcommand, which is handled before the application
+/// configurations are initialized so that invalid configuration files can be reported.
+pub fn handle_config_subcommand(
+    args: &ArgMatches,
+    config_folder: &std::path::Path,
+    overrides: &[String],
+) -> Result<()> {
+    let (cmd, _) = args.subcommand().expect("config subcommand is required");
+    match cmd {
+        "check" => {
+            let issues = config::check_config_files(config_folder)?;
+            if issues.is_empty() {
+                println!("No issues found in the configuration files");
+                std::process::exit(0);
+            }
+            for issue in issues {
+                eprintln!("{issue}");
+            }
+            std::process::exit(1);
+        }
+        "dump" => {
+            // dumping the configurations doesn't create any config file
+            let app_config = config::AppConfig::load(config_folder, overrides)?;
+            print!("{}", toml::to_string_pretty(&app_config)?);
+            std::process::exit(0);
+        }
+        _ => unreachable!(),
+    }
+}
+
+fn handle_smart_subcommand(args: &ArgMatches) -> Request {
+    let (cmd, args) = args.subcommand().expect("smart subcommand is required");
+    let command = match cmd {
+        "sync" => {
+            let name = args.get_one::<String>("name").cloned();
+            let replace = args.get_flag("replace");
+            match &name {
+                Some(name) => println!("Syncing smart playlist '{name}'...\n"),
+                None => println!("Syncing all smart playlists...\n"),
+            }
+            SmartPlaylistCommand::Sync { name, replace }
+        }
+        _ => unreachable!(),
+    };
+
+    Request::SmartPlaylist(command)
+}
+
 fn handle_playlist_subcommand(args: &ArgMatches) -> Result<Request> {
     let (cmd, args) = args.subcommand().expect("playlist subcommand is required");
     let command = match cmd {
@@ -9,7 +9,7 @@ use serde::{Deserialize, Serialize};
 const MAX_REQUEST_SIZE: usize = 4096;
 
 pub use client::start_socket;
-pub use handlers::handle_cli_subcommand;
+pub use handlers::{handle_cli_subcommand, handle_config_subcommand};
 
 #[derive(Debug, Serialize, Deserialize, clap::ValueEnum, Clone)]
 pub enum Key {
@@ -18,6 +18,7 @@ pub enum Key {
     UserPlaylists,
     UserLikedTracks,
     UserSavedAlbums,
+    UserSavedEpisodes,
     UserFollowedArtists,
     UserTopTracks,
     Queue,
@@ -84,6 +85,11 @@ pub enum PlaylistCommand {
     },
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub enum SmartPlaylistCommand {
+    Sync { name: Option<String>, replace: bool },
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub enum Command {
     StartContext {
@@ -118,7 +124,9 @@ pub enum Request {
     Connect(IdOrName),
     Like { unlike: bool },
     Playlist(PlaylistCommand),
+    SmartPlaylist(SmartPlaylistCommand),
     Search { query: String },
+    Undo,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -162,8 +170,11 @@ pub fn init_cli() -> anyhow::Result<clap::Command> {
         .subcommand(commands::init_like_command())
         .subcommand(commands::init_authenticate_command())
         .subcommand(commands::init_playlist_subcommand())
+        .subcommand(commands::init_smart_subcommand())
         .subcommand(commands::init_generate_command())
+        .subcommand(commands::init_config_subcommand())
         .subcommand(commands::init_search_command())
+        .subcommand(commands::init_undo_command())
         .arg(
             clap::Arg::new("theme")
                 .short('t')
@@ -171,6 +182,22 @@ pub fn init_cli() -> anyhow::Result<clap::Command> {
                 .value_name("THEME")
                 .help("Application theme"),
         )
+        .arg(
+            clap::Arg::new("mini")
+                .long("mini")
+                .action(clap::ArgAction::SetTrue)
+                .help("Start the application in the single-line mini mode"),
+        )
+        .arg(
+            clap::Arg::new("set")
+                .long("set")
+                .value_name("KEY=VALUE")
+                .action(clap::ArgAction::Append)
+                .global(true)
+                .help(
+                    "Override an application configuration option, e.g. `--set device.volume=50`",
+                ),
+        )
         .arg(
             clap::Arg::new("config-folder")
                 .short('c')
@@ -9,11 +9,15 @@ use crate::{
 
 use crate::utils::map_join;
 
-use super::ClientRequest;
+use super::{ClientRequest, PlayerRequest};
 
 struct PlayerEventHandlerState {
     add_track_to_queue_req_timer: std::time::Instant,
     get_context_timer: std::time::Instant,
+    /// the time of the latest lyrics request (if any)
+    lyrics_requested_at: Option<std::time::Instant>,
+    /// URIs of saved shows whose episode summaries have been requested
+    requested_show_summaries: std::collections::HashSet<String>,
 }
 
 /// starts the client's request handler
@@ -86,6 +90,28 @@ fn handle_playback_change_event(
         client_pub.send(ClientRequest::GetCurrentUserQueue)?;
     }
 
+    // fetch the playing track's lyrics if they are shown in a lyrics pane
+    if let PlayableId::Track(track_id) = &id {
+        let layout = &config::get_config().app_config.layout;
+        if (layout.horizontal.has_pane(config::PaneType::Lyrics)
+            || layout.vertical.has_pane(config::PaneType::Lyrics))
+            && !state
+                .data
+                .read()
+                .caches
+                .lyrics
+                .contains_key(&track_id.uri())
+            && handler_state
+                .lyrics_requested_at
+                .is_none_or(|t| t.elapsed() > std::time::Duration::from_secs(5))
+        {
+            client_pub.send(ClientRequest::GetLyrics {
+                track_id: track_id.clone_static(),
+            })?;
+            handler_state.lyrics_requested_at = Some(std::time::Instant::now());
+        }
+    }
+
     // handle fake track repeat mode
     if playback.fake_track_repeat_state {
         if let Some(progress) = player.playback_progress() {
@@ -141,7 +167,11 @@ fn handle_page_change_event(
                             ContextId::Artist(_) => ContextPageUIState::new_artist(),
                             ContextId::Playlist(_) => ContextPageUIState::new_playlist(),
                             ContextId::Tracks(_) => ContextPageUIState::new_tracks(),
-                            ContextId::Show(_) => ContextPageUIState::new_show(),
+                            // the saved episodes page is rendered as an episode table
+                            ContextId::Show(_) | ContextId::SavedEpisodes => {
+                                ContextPageUIState::new_show()
+                            }
+                            ContextId::Audiobook(_) => ContextPageUIState::new_audiobook(),
                         });
                     }
                     None => {
@@ -155,7 +185,7 @@ fn handle_page_change_event(
             // To avoid making too many requests, only request if context id is changed
             // or it's been a while since the last request.
             if let Some(id) = id {
-                if !matches!(id, ContextId::Tracks(_))
+                if !matches!(id, ContextId::Tracks(_) | ContextId::SavedEpisodes)
                     && !state.data.read().caches.context.contains_key(&id.uri())
                     && (new_id
                         || handler_state.get_context_timer.elapsed()
@@ -188,12 +218,44 @@ fn handle_page_change_event(
                 }
             }
         }
+        // summarize the episodes of saved shows once per session, so that the library's show window
+        // can display each show's latest episode and its number of unplayed episodes
+        PageState::Library { .. } => {
+            let show_ids = state
+                .data
+                .read()
+                .user_data
+                .saved_shows
+                .iter()
+                .filter(|s| handler_state.requested_show_summaries.insert(s.id.uri()))
+                .map(|s| s.id.clone())
+                .collect::<Vec<_>>();
+            if !show_ids.is_empty() {
+                client_pub.send(ClientRequest::GetSavedShowSummaries(show_ids))?;
+            }
+        }
         _ => {}
     }
 
     Ok(())
 }
 
+fn handle_sleep_timer_event(
+    state: &SharedState,
+    client_pub: &flume::Sender<ClientRequest>,
+) -> anyhow::Result<()> {
+    let mut player = state.player.write();
+    if player
+        .sleep_timer
+        .is_some_and(|t| t <= std::time::Instant::now())
+    {
+        tracing::info!("sleep timer is up, pause the playback");
+        player.sleep_timer = None;
+        client_pub.send(ClientRequest::Player(PlayerRequest::Pause))?;
+    }
+    Ok(())
+}
+
 fn handle_player_event(
     state: &SharedState,
     client_pub: &flume::Sender<ClientRequest>,
@@ -203,6 +265,7 @@ fn handle_player_event(
         .context("handle page change event")?;
     handle_playback_change_event(state, client_pub, handler_state)
         .context("handle playback change event")?;
+    handle_sleep_timer_event(state, client_pub).context("handle sleep timer event")?;
 
     Ok(())
 }
@@ -239,6 +302,8 @@ pub async fn start_player_event_watchers(
     let mut handler_state = PlayerEventHandlerState {
         add_track_to_queue_req_timer: std::time::Instant::now(),
         get_context_timer: std::time::Instant::now(),
+        lyrics_requested_at: None,
+        requested_show_summaries: std::collections::HashSet::new(),
     };
 
     loop {
@@ -6,11 +6,13 @@ use crate::{auth, config};
 use crate::{
     auth::AuthConfig,
     state::{
-        store_data_into_file_cache, Album, AlbumId, Artist, ArtistId, Category, Context, ContextId,
-        Device, FileCacheKey, Item, ItemId, MemoryCaches, Playback, PlaybackMetadata, Playlist,
-        PlaylistFolderItem, PlaylistId, SearchResults, SharedState, Show, ShowId, Track, TrackId,
-        UserId, TTL_CACHE_DURATION, USER_LIKED_TRACKS_ID, USER_RECENTLY_PLAYED_TRACKS_ID,
-        USER_TOP_TRACKS_ID,
+        load_data_from_file_cache, store_data_into_file_cache, Album, AlbumId, Artist, ArtistId,
+        Audiobook, AudiobookId, Category, Context, ContextId, Device, Episode, EpisodeId,
+        FileCacheKey, FullAudiobookObject, Item, ItemId, MemoryCaches, Playback, PlaybackMetadata,
+        Playlist, PlaylistFolderItem, PlaylistId, SearchResults, SharedState, Show, ShowId,
+        ShowSummary, SimplifiedAudiobookObject, Track, TrackId, UndoEntry, UserId,
+        TTL_CACHE_DURATION, UNDO_JOURNAL_SIZE, USER_LIKED_TRACKS_ID,
+        USER_RECENTLY_PLAYED_TRACKS_ID, USER_TOP_TRACKS_ID,
     },
 };
 
@@ -34,6 +36,13 @@ pub use request::*;
 use serde::Deserialize;
 
 const SPOTIFY_API_ENDPOINT: &str = "https://api.spotify.com/v1";
+/// the maximum number of items in a request modifying a playlist's items
+const PLAYLIST_ITEMS_BATCH_SIZE: usize = 100;
+/// the maximum number of items in a request modifying user's liked tracks
+const LIKED_TRACKS_BATCH_SIZE: usize = 50;
+/// the maximum number of a played discography's tracks added to the queue,
+/// as each track is added to the queue by a separate request
+const MAX_QUEUED_DISCOGRAPHY_TRACKS: usize = 50;
 const PLAYBACK_TYPES: [&rspotify::model::AdditionalType; 2] = [
     &rspotify::model::AdditionalType::Track,
     &rspotify::model::AdditionalType::Episode,
@@ -218,7 +227,7 @@ impl Client {
                     playback.shuffle_state = shuffle;
                 }
                 let device_id = playback.as_ref().and_then(|p| p.device_id.as_deref());
-                self.start_playback(p, device_id).await?;
+                self.start_playback(p, device_id, None).await?;
                 // For some reasons, when starting a new playback, the integrated `spotify_player`
                 // client doesn't respect the initial shuffle state, so we need to manually update the state
                 if let Some(ref playback) = playback {
@@ -226,6 +235,11 @@ impl Client {
                 }
                 return Ok(None);
             }
+            PlayerRequest::StartPlaybackAt(p, position) => {
+                let device_id = playback.as_ref().and_then(|p| p.device_id.as_deref());
+                self.start_playback(p, device_id, Some(position)).await?;
+                return Ok(None);
+            }
             _ => {}
         }
 
@@ -295,7 +309,7 @@ impl Client {
 
                 playback.mute_state = new_mute_state;
             }
-            PlayerRequest::StartPlayback(..) => {
+            PlayerRequest::StartPlayback(..) | PlayerRequest::StartPlaybackAt(..) => {
                 anyhow::bail!("`StartPlayback` should be handled earlier")
             }
             PlayerRequest::TransferPlayback(..) => {
@@ -366,15 +380,7 @@ impl Client {
             }
             ClientRequest::GetUserPlaylists => {
                 let playlists = self.current_user_playlists().await?;
-                let node = state.data.read().user_data.playlist_folder_node.clone();
-                let playlists = if let Some(node) = node.filter(|n| !n.children.is_empty()) {
-                    crate::playlist_folders::structurize(playlists, &node.children)
-                } else {
-                    playlists
-                        .into_iter()
-                        .map(PlaylistFolderItem::Playlist)
-                        .collect()
-                };
+                let playlists = state.data.read().user_data.structurize_playlists(playlists);
                 store_data_into_file_cache(
                     FileCacheKey::Playlists,
                     &config::get_config().cache_folder,
@@ -413,17 +419,71 @@ impl Client {
                 .context("store user's saved shows into the cache folder")?;
                 state.data.write().user_data.saved_shows = shows;
             }
+            ClientRequest::GetSavedShowSummaries(show_ids) => {
+                // shows are summarized one at a time from their latest episodes,
+                // skipping shows that fail to load
+                for show_id in show_ids {
+                    let uri = show_id.uri();
+                    let latest_episodes = match self.show_latest_episodes(show_id).await {
+                        Ok(episodes) => episodes,
+                        Err(err) => {
+                            tracing::warn!("Failed to summarize show {uri}: {err:#}");
+                            continue;
+                        }
+                    };
+                    let Some(latest_release_date) =
+                        latest_episodes.iter().map(|e| e.release_date.clone()).max()
+                    else {
+                        continue;
+                    };
+                    state.data.write().user_data.saved_show_summaries.insert(
+                        uri,
+                        ShowSummary {
+                            latest_release_date,
+                            latest_episodes,
+                        },
+                    );
+                }
+            }
+            ClientRequest::GetUserSavedEpisodes => {
+                let episodes = self.current_user_saved_episodes().await?;
+                store_data_into_file_cache(
+                    FileCacheKey::SavedEpisodes,
+                    &config::get_config().cache_folder,
+                    &episodes,
+                )
+                .context("store user's saved episodes into the cache folder")?;
+
+                let mut data = state.data.write();
+                data.user_data.saved_episodes.clone_from(&episodes);
+                data.cache_context(
+                    ContextId::SavedEpisodes.uri(),
+                    Context::Episodes {
+                        episodes,
+                        desc: "User's saved episodes".to_string(),
+                    },
+                );
+            }
+            ClientRequest::GetUserSavedAudiobooks => {
+                let audiobooks = self.current_user_saved_audiobooks().await?;
+                store_data_into_file_cache(
+                    FileCacheKey::SavedAudiobooks,
+                    &config::get_config().cache_folder,
+                    &audiobooks,
+                )
+                .context("store user's saved audiobooks into the cache folder")?;
+                state.data.write().user_data.saved_audiobooks = audiobooks;
+            }
             ClientRequest::GetUserTopTracks => {
                 let uri = &USER_TOP_TRACKS_ID.uri;
                 if !state.data.read().caches.context.contains_key(uri) {
                     let tracks = self.current_user_top_tracks().await?;
-                    state.data.write().caches.context.insert(
+                    state.data.write().cache_context(
                         uri.to_owned(),
                         Context::Tracks {
                             tracks,
                             desc: "User's top tracks".to_string(),
                         },
-                        *TTL_CACHE_DURATION,
                     );
                 }
             }
@@ -442,26 +502,63 @@ impl Client {
 
                 let mut data = state.data.write();
                 data.user_data.saved_tracks = tracks_hm;
-                data.caches.context.insert(
+                data.cache_context(
                     USER_LIKED_TRACKS_ID.uri.clone(),
                     Context::Tracks {
                         tracks,
                         desc: "User's liked tracks".to_string(),
                     },
-                    *TTL_CACHE_DURATION,
+                );
+            }
+            ClientRequest::GetSmartPlaylist(name) => {
+                let playlist = config::get_config()
+                    .smart_playlist_config
+                    .find_smart_playlist(&name)
+                    .with_context(|| format!("smart playlist {name} not found"))?;
+                // the playlist's rules are evaluated over the in-memory user data
+                let tracks = match playlist.source {
+                    config::SmartPlaylistSource::LikedTracks => {
+                        let mut tracks = state
+                            .data
+                            .read()
+                            .user_data
+                            .saved_tracks
+                            .values()
+                            .cloned()
+                            .collect::<Vec<_>>();
+                        // liked tracks are ordered from the most recently added one
+                        tracks.sort_by_key(|t| std::cmp::Reverse(t.added_at));
+                        tracks
+                    }
+                    config::SmartPlaylistSource::SavedAlbums => {
+                        self.cached_saved_album_tracks(state).await?
+                    }
+                };
+                let mut data = state.data.write();
+                let tracks = crate::smart_playlists::select_tracks(
+                    playlist,
+                    tracks,
+                    &data.user_data.followed_artists,
+                    &data.user_data.play_counts,
+                );
+                data.cache_context(
+                    crate::smart_playlists::tracks_id(&name).uri,
+                    Context::Tracks {
+                        tracks,
+                        desc: "Smart playlist".to_string(),
+                    },
                 );
             }
             ClientRequest::GetUserRecentlyPlayedTracks => {
                 let uri = &USER_RECENTLY_PLAYED_TRACKS_ID.uri;
                 if !state.data.read().caches.context.contains_key(uri) {
                     let tracks = self.current_user_recently_played_tracks().await?;
-                    state.data.write().caches.context.insert(
+                    state.data.write().cache_context(
                         uri.to_owned(),
                         Context::Tracks {
                             tracks,
                             desc: "User's recently played tracks".to_string(),
                         },
-                        *TTL_CACHE_DURATION,
                     );
                 }
             }
@@ -479,15 +576,18 @@ impl Client {
                                 "`GetContext` request for `tracks` context is not supported!"
                             );
                         }
+                        ContextId::SavedEpisodes => {
+                            anyhow::bail!(
+                                "`GetContext` request for saved episodes context is not supported!"
+                            );
+                        }
                         ContextId::Show(show_id) => self.show_context(show_id).await?,
+                        ContextId::Audiobook(audiobook_id) => {
+                            self.audiobook_context(&audiobook_id).await?
+                        }
                     };
 
-                    state
-                        .data
-                        .write()
-                        .caches
-                        .context
-                        .insert(uri, context, *TTL_CACHE_DURATION);
+                    state.data.write().cache_context(uri, context);
                 }
             }
             ClientRequest::Search(query) => {
@@ -510,23 +610,73 @@ impl Client {
                 if !state.data.read().caches.context.contains_key(&radio_uri) {
                     let tracks = self.radio_tracks(uri).await?;
 
-                    state.data.write().caches.context.insert(
+                    state.data.write().cache_context(
                         radio_uri,
                         Context::Tracks {
                             tracks,
                             desc: format!("{name} Radio"),
                         },
-                        *TTL_CACHE_DURATION,
                     );
                 }
             }
             ClientRequest::AddPlayableToQueue(playable_id) => {
                 self.add_item_to_queue(playable_id, None).await?;
             }
-            ClientRequest::AddPlayableToPlaylist(playlist_id, playable_id) => {
-                self.add_item_to_playlist(state, playlist_id, playable_id)
+            ClientRequest::AddPlayablesToQueue(playable_ids) => {
+                for playable_id in playable_ids {
+                    self.add_item_to_queue(playable_id, None).await?;
+                }
+            }
+            ClientRequest::AddPlayablesToPlaylist(playlist_id, playable_ids) => {
+                self.add_items_to_playlist(state, playlist_id, &playable_ids)
                     .await?;
             }
+            ClientRequest::PlayArtistDiscography(artist_id) => {
+                let tracks = self.artist_discography_tracks(artist_id).await?;
+                if tracks.is_empty() {
+                    return Ok(());
+                }
+                // the number of tracks in a `URIs` playback is limited, so the discography's
+                // first tracks are played and some of the remaining tracks are added to the queue
+                let limit = config::get_config().app_config.tracks_playback_limit;
+                let (first_tracks, remaining_tracks) = tracks.split_at(tracks.len().min(limit));
+                let playback = Playback::URIs(
+                    first_tracks.iter().map(|t| t.id.clone().into()).collect(),
+                    None,
+                );
+                let buffered_playback = state.player.read().buffered_playback.clone();
+                let buffered_playback = self
+                    .handle_player_request(
+                        PlayerRequest::StartPlayback(playback, None),
+                        buffered_playback,
+                    )
+                    .await?;
+                state.player.write().buffered_playback = buffered_playback;
+                self.update_playback(state);
+
+                for track in remaining_tracks.iter().take(MAX_QUEUED_DISCOGRAPHY_TRACKS) {
+                    // the playback has already started, so failing to queue a track
+                    // only stops queueing the remaining ones
+                    if let Err(err) = self
+                        .add_item_to_queue(PlayableId::Track(track.id.clone()), None)
+                        .await
+                    {
+                        tracing::warn!("Failed to add the discography's tracks to queue: {err:#}");
+                        break;
+                    }
+                }
+            }
+            ClientRequest::AddArtistDiscographyToPlaylist {
+                artist_id,
+                playlist_id,
+            } => {
+                let tracks = self.artist_discography_tracks(artist_id).await?;
+                let ids = tracks
+                    .into_iter()
+                    .map(|t| t.id.into())
+                    .collect::<Vec<PlayableId>>();
+                self.add_items_to_playlist(state, playlist_id, &ids).await?;
+            }
             ClientRequest::AddAlbumToQueue(album_id) => {
                 let album_context = self.album_context(album_id).await?;
 
@@ -537,8 +687,8 @@ impl Client {
                     }
                 }
             }
-            ClientRequest::DeleteTrackFromPlaylist(playlist_id, track_id) => {
-                self.delete_track_from_playlist(state, playlist_id, track_id)
+            ClientRequest::DeleteTracksFromPlaylist(playlist_id, track_ids) => {
+                self.delete_tracks_from_playlist(state, playlist_id, &track_ids)
                     .await?;
             }
             ClientRequest::AddToLibrary(item) => {
@@ -547,6 +697,29 @@ impl Client {
             ClientRequest::DeleteFromLibrary(id) => {
                 self.delete_from_library(state, id).await?;
             }
+            ClientRequest::AddTracksToLiked(tracks) => {
+                self.add_tracks_to_liked(state, tracks).await?;
+            }
+            ClientRequest::DeleteTracksFromLiked(track_ids) => {
+                self.delete_tracks_from_liked(state, &track_ids).await?;
+            }
+            ClientRequest::SetEpisodePlayed { episode_id, played } => {
+                // Spotify's Web API doesn't support persisting an episode's played state,
+                // so the state is stored locally
+                let mut data = state.data.write();
+                data.user_data
+                    .played_episodes
+                    .insert(episode_id.uri(), played);
+                store_data_into_file_cache(
+                    FileCacheKey::PlayedEpisodes,
+                    &config::get_config().cache_folder,
+                    &data.user_data.played_episodes,
+                )
+                .context("store episodes' played states into the cache folder")?;
+            }
+            ClientRequest::Undo => {
+                self.undo(Some(state)).await?;
+            }
             ClientRequest::GetCurrentUserQueue => {
                 let queue = self.current_user_queue().await?;
                 state.player.write().queue = Some(queue);
@@ -737,7 +910,7 @@ impl Client {
         let tracks = self.all_paging_items(first_page, &market_query()).await?;
         Ok(tracks
             .into_iter()
-            .filter_map(|t| Track::try_from_full_track(t.track))
+            .filter_map(Track::try_from_saved_track)
             .collect())
     }
 
@@ -834,6 +1007,77 @@ impl Client {
         Ok(albums.into_iter().map(|a| a.album.into()).collect())
     }
 
+    /// Get all tracks of the current user's saved albums, which are added when their albums are saved
+    pub async fn current_user_saved_album_tracks(&self) -> Result<Vec<Track>> {
+        let first_page = self
+            .current_user_saved_albums_manual(
+                Some(rspotify::model::Market::FromToken),
+                Some(50),
+                None,
+            )
+            .await?;
+
+        let albums = self.all_paging_items(first_page, &Query::new()).await?;
+
+        let mut tracks = vec![];
+        for saved_album in albums {
+            let added_at = saved_album.added_at.timestamp() as u64;
+            let (_, album_tracks) = self.full_album_tracks(saved_album.album).await?;
+            tracks.extend(album_tracks.into_iter().map(|mut t| {
+                t.added_at = added_at;
+                t
+            }));
+        }
+        Ok(tracks)
+    }
+
+    /// Get all tracks of the current user's saved albums from the context cache,
+    /// fetching and caching them if not found
+    async fn cached_saved_album_tracks(&self, state: &SharedState) -> Result<Vec<Track>> {
+        let key = crate::smart_playlists::SAVED_ALBUM_TRACKS_CACHE_KEY;
+        if let Some(Context::Tracks { tracks, .. }) = state.data.read().caches.context.get(key) {
+            return Ok(tracks.clone());
+        }
+
+        let tracks = self.current_user_saved_album_tracks().await?;
+        state.data.write().cache_context(
+            key.to_string(),
+            Context::Tracks {
+                tracks: tracks.clone(),
+                desc: "User's saved album tracks".to_string(),
+            },
+        );
+        Ok(tracks)
+    }
+
+    /// Get the tracks of a smart playlist, selected from the current user's library
+    pub async fn smart_playlist_tracks(
+        &self,
+        playlist: &config::SmartPlaylist,
+    ) -> Result<Vec<Track>> {
+        let tracks = match playlist.source {
+            config::SmartPlaylistSource::LikedTracks => self.current_user_saved_tracks().await?,
+            config::SmartPlaylistSource::SavedAlbums => {
+                self.current_user_saved_album_tracks().await?
+            }
+        };
+        let followed_artists = if playlist.rules.followed_artists_only {
+            self.current_user_followed_artists().await?
+        } else {
+            vec![]
+        };
+        let play_counts =
+            load_data_from_file_cache(FileCacheKey::PlayCounts, &config::get_config().cache_folder)
+                .unwrap_or_default();
+
+        Ok(crate::smart_playlists::select_tracks(
+            playlist,
+            tracks,
+            &followed_artists,
+            &play_counts,
+        ))
+    }
+
     /// Get all saved shows of the current user
     pub async fn current_user_saved_shows(&self) -> Result<Vec<Show>> {
         let first_page = self.get_saved_show_manual(Some(50), None).await?;
@@ -841,35 +1085,59 @@ impl Client {
         Ok(shows.into_iter().map(|s| s.show.into()).collect())
     }
 
-    /// Get all albums of an artist
-    pub async fn artist_albums(&self, artist_id: ArtistId<'_>) -> Result<Vec<Album>> {
-        let payload = market_query();
+    /// Get all saved episodes of the current user
+    pub async fn current_user_saved_episodes(&self) -> Result<Vec<Episode>> {
+        // `rspotify` doesn't support the saved episodes API yet
+        #[derive(Debug, Deserialize)]
+        struct SavedEpisode {
+            episode: rspotify::model::FullEpisode,
+        }
 
-        let mut singles = {
-            let first_page = self
-                .artist_albums_manual(
-                    artist_id.as_ref(),
-                    Some(rspotify::model::AlbumType::Single),
-                    Some(rspotify::model::Market::FromToken),
-                    Some(50),
-                    None,
-                )
-                .await?;
-            self.all_paging_items(first_page, &payload).await
-        }?;
-        let mut albums = {
-            let first_page = self
-                .artist_albums_manual(
-                    artist_id.as_ref(),
-                    Some(rspotify::model::AlbumType::Album),
-                    Some(rspotify::model::Market::FromToken),
-                    Some(50),
-                    None,
-                )
-                .await?;
-            self.all_paging_items(first_page, &payload).await
-        }?;
-        albums.append(&mut singles);
+        let first_page = self
+            .http_get::<rspotify::model::Page<SavedEpisode>>(
+                &format!("{SPOTIFY_API_ENDPOINT}/me/episodes"),
+                &Query::from([("limit", "50"), ("market", "from_token")]),
+                false,
+            )
+            .await?;
+        let episodes = self.all_paging_items(first_page, &market_query()).await?;
+        Ok(episodes.into_iter().map(|e| e.episode.into()).collect())
+    }
+
+    /// Get all saved audiobooks of the current user
+    pub async fn current_user_saved_audiobooks(&self) -> Result<Vec<Audiobook>> {
+        // `rspotify` doesn't support the audiobooks API yet
+        let first_page = self
+            .http_get::<rspotify::model::Page<SimplifiedAudiobookObject>>(
+                &format!("{SPOTIFY_API_ENDPOINT}/me/audiobooks"),
+                &Query::from([("limit", "50")]),
+                false,
+            )
+            .await?;
+        let audiobooks = self.all_paging_items(first_page, &Query::new()).await?;
+        Ok(audiobooks
+            .into_iter()
+            .map(std::convert::Into::into)
+            .collect())
+    }
+
+    /// Get all albums of an artist, including singles, compilations and albums the artist appears on
+    pub async fn artist_albums(&self, artist_id: ArtistId<'_>) -> Result<Vec<Album>> {
+        let first_page = self
+            .artist_albums_manual(
+                artist_id.as_ref(),
+                [
+                    rspotify::model::AlbumType::Album,
+                    rspotify::model::AlbumType::Single,
+                    rspotify::model::AlbumType::Compilation,
+                    rspotify::model::AlbumType::AppearsOn,
+                ],
+                Some(rspotify::model::Market::FromToken),
+                Some(50),
+                None,
+            )
+            .await?;
+        let albums = self.all_paging_items(first_page, &market_query()).await?;
 
         // converts `rspotify::model::SimplifiedAlbum` into `state::Album`
         let albums = albums
@@ -879,32 +1147,89 @@ impl Client {
         Ok(Client::process_artist_albums(albums))
     }
 
+    /// Get all tracks of an artist's discography (albums, singles and compilations),
+    /// ordered chronologically by their albums' release dates
+    pub async fn artist_discography_tracks(&self, artist_id: ArtistId<'_>) -> Result<Vec<Track>> {
+        let mut albums = self.artist_albums(artist_id).await?;
+        albums.retain(|a| a.typ != Some(rspotify::model::AlbumType::AppearsOn));
+        albums.sort_by(|x, y| x.release_date.cmp(&y.release_date));
+
+        let mut tracks = vec![];
+        // the API allows to get at most 20 albums at once
+        for albums in albums.chunks(20) {
+            let albums = self
+                .albums(
+                    albums.iter().map(|a| a.id.as_ref()),
+                    Some(rspotify::model::Market::FromToken),
+                )
+                .await?;
+            for album in albums {
+                tracks.extend(self.full_album_tracks(album).await?.1);
+            }
+        }
+        Ok(tracks)
+    }
+
     /// Start a playback
-    async fn start_playback(&self, playback: Playback, device_id: Option<&str>) -> Result<()> {
+    async fn start_playback(
+        &self,
+        playback: Playback,
+        device_id: Option<&str>,
+        position: Option<chrono::Duration>,
+    ) -> Result<()> {
         match playback {
             Playback::Context(id, offset) => match id {
                 ContextId::Album(id) => {
-                    self.start_context_playback(PlayContextId::from(id), device_id, offset, None)
-                        .await?;
+                    self.start_context_playback(
+                        PlayContextId::from(id),
+                        device_id,
+                        offset,
+                        position,
+                    )
+                    .await?;
                 }
                 ContextId::Artist(id) => {
-                    self.start_context_playback(PlayContextId::from(id), device_id, offset, None)
-                        .await?;
+                    self.start_context_playback(
+                        PlayContextId::from(id),
+                        device_id,
+                        offset,
+                        position,
+                    )
+                    .await?;
                 }
                 ContextId::Playlist(id) => {
-                    self.start_context_playback(PlayContextId::from(id), device_id, offset, None)
-                        .await?;
+                    self.start_context_playback(
+                        PlayContextId::from(id),
+                        device_id,
+                        offset,
+                        position,
+                    )
+                    .await?;
                 }
                 ContextId::Show(id) => {
-                    self.start_context_playback(PlayContextId::from(id), device_id, offset, None)
+                    self.start_context_playback(
+                        PlayContextId::from(id),
+                        device_id,
+                        offset,
+                        position,
+                    )
+                    .await?;
+                }
+                ContextId::Audiobook(id) => {
+                    self.start_audiobook_playback(&id, device_id, offset, position)
                         .await?;
                 }
                 ContextId::Tracks(_) => {
                     anyhow::bail!("`StartPlayback` request for `tracks` context is not supported")
                 }
+                ContextId::SavedEpisodes => {
+                    anyhow::bail!(
+                        "`StartPlayback` request for saved episodes context is not supported"
+                    )
+                }
             },
             Playback::URIs(ids, offset) => {
-                self.start_uris_playback(ids, device_id, offset, None)
+                self.start_uris_playback(ids, device_id, offset, position)
                     .await?;
             }
         }
@@ -982,13 +1307,22 @@ impl Client {
             playlist_result,
             show_result,
             episode_result,
+            audiobooks,
         ) = tokio::try_join!(
             self.search_specific_type(query, rspotify::model::SearchType::Track),
             self.search_specific_type(query, rspotify::model::SearchType::Artist),
             self.search_specific_type(query, rspotify::model::SearchType::Album),
             self.search_specific_type(query, rspotify::model::SearchType::Playlist),
             self.search_specific_type(query, rspotify::model::SearchType::Show),
-            self.search_specific_type(query, rspotify::model::SearchType::Episode)
+            self.search_specific_type(query, rspotify::model::SearchType::Episode),
+            // audiobooks are not available in some markets, so failing to search them
+            // doesn't fail the whole search
+            async {
+                Ok(self.search_audiobooks(query).await.unwrap_or_else(|err| {
+                    tracing::warn!("Failed to search audiobooks: {err:#}");
+                    vec![]
+                }))
+            }
         )?;
 
         let (tracks, artists, albums, playlists, shows, episodes) = (
@@ -1041,9 +1375,39 @@ impl Client {
             playlists,
             shows,
             episodes,
+            audiobooks,
         })
     }
 
+    /// Search for audiobooks matching a given query
+    pub async fn search_audiobooks(&self, query: &str) -> Result<Vec<Audiobook>> {
+        // `rspotify` doesn't support searching audiobooks yet
+        #[derive(Debug, Deserialize)]
+        struct AudiobookSearchResult {
+            audiobooks: rspotify::model::Page<Option<SimplifiedAudiobookObject>>,
+        }
+
+        let result = self
+            .http_get::<AudiobookSearchResult>(
+                &format!("{SPOTIFY_API_ENDPOINT}/search"),
+                &Query::from([
+                    ("q", query),
+                    ("type", "audiobook"),
+                    ("market", "from_token"),
+                ]),
+                false,
+            )
+            .await?;
+        // audiobooks are not available in some markets, resulting in `null` items
+        Ok(result
+            .audiobooks
+            .items
+            .into_iter()
+            .flatten()
+            .map(std::convert::Into::into)
+            .collect())
+    }
+
     /// Search for items of a specific type matching a given query
     pub async fn search_specific_type(
         &self,
@@ -1056,23 +1420,29 @@ impl Client {
             .await?)
     }
 
-    /// Add a playable item to a playlist
-    pub async fn add_item_to_playlist(
+    /// Add playable items to a playlist
+    pub async fn add_items_to_playlist(
         &self,
         state: &SharedState,
         playlist_id: PlaylistId<'_>,
-        playable_id: PlayableId<'_>,
+        playable_ids: &[PlayableId<'_>],
     ) -> Result<()> {
-        // remove all the occurrences of the track to ensure no duplication in the playlist
-        self.playlist_remove_all_occurrences_of_items(
-            playlist_id.as_ref(),
-            [playable_id.as_ref()],
-            None,
-        )
-        .await?;
+        for ids in playable_ids.chunks(PLAYLIST_ITEMS_BATCH_SIZE) {
+            // remove all the occurrences of the items to ensure no duplication in the playlist
+            self.playlist_remove_all_occurrences_of_items(
+                playlist_id.as_ref(),
+                ids.iter().map(PlayableId::as_ref),
+                None,
+            )
+            .await?;
 
-        self.playlist_add_items(playlist_id.as_ref(), [playable_id.as_ref()], None)
+            self.playlist_add_items(
+                playlist_id.as_ref(),
+                ids.iter().map(PlayableId::as_ref),
+                None,
+            )
             .await?;
+        }
 
         // After adding a new track to a playlist, remove the cache of that playlist to force refetching new data
         state.data.write().caches.context.remove(&playlist_id.uri());
@@ -1080,20 +1450,61 @@ impl Client {
         Ok(())
     }
 
-    /// Remove a track from a playlist
-    pub async fn delete_track_from_playlist(
+    /// Remove tracks from a playlist
+    pub async fn delete_tracks_from_playlist(
         &self,
         state: &SharedState,
         playlist_id: PlaylistId<'_>,
-        track_id: TrackId<'_>,
+        track_ids: &[TrackId<'_>],
     ) -> Result<()> {
-        // remove all the occurrences of the track to ensure no duplication in the playlist
-        self.playlist_remove_all_occurrences_of_items(
-            playlist_id.as_ref(),
-            [PlayableId::Track(track_id.as_ref())],
-            None,
-        )
-        .await?;
+        // Find the positions of the deleted tracks to be able to restore them later.
+        // The playlist is fetched instead of using the cached data, whose tracks can be sorted
+        // and don't include episodes.
+        let playlist = self
+            .http_get::<rspotify::model::FullPlaylist>(
+                &format!("{SPOTIFY_API_ENDPOINT}/playlists/{}", playlist_id.id()),
+                &market_query(),
+                false,
+            )
+            .await?;
+        let items = self
+            .all_paging_items(playlist.tracks.clone(), &market_query())
+            .await?
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, item)| match item.track {
+                Some(rspotify::model::PlayableItem::Track(rspotify::model::FullTrack {
+                    id: Some(id),
+                    ..
+                })) if track_ids.contains(&id) => Some((i, id)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut snapshot_id = playlist.snapshot_id;
+        for ids in track_ids.chunks(PLAYLIST_ITEMS_BATCH_SIZE) {
+            // remove all the occurrences of the tracks to ensure no duplication in the playlist
+            snapshot_id = self
+                .playlist_remove_all_occurrences_of_items(
+                    playlist_id.as_ref(),
+                    ids.iter().map(|id| PlayableId::Track(id.as_ref())),
+                    Some(&snapshot_id),
+                )
+                .await?
+                .snapshot_id;
+        }
+
+        if !items.is_empty() {
+            Self::record_undo_entry(
+                state,
+                UndoEntry::PlaylistItems {
+                    playlist_id: playlist_id.clone_static(),
+                    playlist_name: playlist.name,
+                    items,
+                    snapshot_id,
+                },
+            )?;
+        }
 
         // After making a delete request, update the playlist in-memory data stored inside the app caches.
         if let Some(Context::Playlist { tracks, .. }) = state
@@ -1103,7 +1514,7 @@ impl Client {
             .context
             .get_mut(&playlist_id.uri())
         {
-            tracks.retain(|t| t.id != track_id);
+            tracks.retain(|t| !track_ids.contains(&t.id));
         }
 
         Ok(())
@@ -1154,20 +1565,7 @@ impl Client {
         // Before adding new item, checks if that item already exists in the library to avoid adding a duplicated item.
         match item {
             Item::Track(track) => {
-                let contains = self
-                    .current_user_saved_tracks_contains([track.id.as_ref()])
-                    .await?;
-                if !contains[0] {
-                    self.current_user_saved_tracks_add([track.id.as_ref()])
-                        .await?;
-                    // update the in-memory `user_data`
-                    state
-                        .data
-                        .write()
-                        .user_data
-                        .saved_tracks
-                        .insert(track.id.uri(), track);
-                }
+                self.add_tracks_to_liked(state, vec![track]).await?;
             }
             Item::Album(album) => {
                 let contains = self
@@ -1226,19 +1624,55 @@ impl Client {
                     state.data.write().user_data.saved_shows.insert(0, show);
                 }
             }
+            Item::Episode(episode) => {
+                if !state.data.read().user_data.is_saved_episode(&episode) {
+                    self.save_episodes(&[episode.id.as_ref()]).await?;
+                    // update the in-memory `user_data` and the saved episodes page
+                    let mut data = state.data.write();
+                    data.user_data.saved_episodes.insert(0, episode);
+                    data.update_saved_episodes_context();
+                }
+            }
+            Item::Audiobook(audiobook) => {
+                let saved = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_audiobooks
+                    .iter()
+                    .any(|a| a.id == audiobook.id);
+                if !saved {
+                    self.save_audiobooks(&[&audiobook.id]).await?;
+                    // update the in-memory `user_data`
+                    state
+                        .data
+                        .write()
+                        .user_data
+                        .saved_audiobooks
+                        .insert(0, audiobook);
+                }
+            }
         }
         Ok(())
     }
 
     // Delete a Spotify item from user's library
     async fn delete_from_library(&self, state: &SharedState, id: ItemId) -> Result<()> {
-        match id {
+        // the deleted item (if found in the in-memory `user_data`) is recorded in the undo journal
+        let entry = match id {
             ItemId::Track(id) => {
-                let uri = id.uri();
-                self.current_user_saved_tracks_delete([id]).await?;
-                state.data.write().user_data.saved_tracks.remove(&uri);
+                self.delete_tracks_from_liked(state, &[id]).await?;
+                None
             }
             ItemId::Album(id) => {
+                let album = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_albums
+                    .iter()
+                    .find(|a| a.id == id)
+                    .cloned();
                 state
                     .data
                     .write()
@@ -1246,8 +1680,17 @@ impl Client {
                     .saved_albums
                     .retain(|a| a.id != id);
                 self.current_user_saved_albums_delete([id]).await?;
+                album.map(UndoEntry::SavedAlbum)
             }
             ItemId::Artist(id) => {
+                let artist = state
+                    .data
+                    .read()
+                    .user_data
+                    .followed_artists
+                    .iter()
+                    .find(|a| a.id == id)
+                    .cloned();
                 state
                     .data
                     .write()
@@ -1255,8 +1698,19 @@ impl Client {
                     .followed_artists
                     .retain(|a| a.id != id);
                 self.user_unfollow_artists([id]).await?;
+                artist.map(UndoEntry::FollowedArtist)
             }
             ItemId::Playlist(id) => {
+                let playlist = state
+                    .data
+                    .read()
+                    .user_data
+                    .playlists
+                    .iter()
+                    .find_map(|item| match item {
+                        PlaylistFolderItem::Playlist(p) if p.id == id => Some(p.clone()),
+                        _ => None,
+                    });
                 state
                     .data
                     .write()
@@ -1267,8 +1721,17 @@ impl Client {
                         PlaylistFolderItem::Folder(_) => true,
                     });
                 self.playlist_unfollow(id).await?;
+                playlist.map(UndoEntry::FollowedPlaylist)
             }
             ItemId::Show(id) => {
+                let show = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_shows
+                    .iter()
+                    .find(|s| s.id == id)
+                    .cloned();
                 state
                     .data
                     .write()
@@ -1277,7 +1740,257 @@ impl Client {
                     .retain(|s| s.id != id);
                 self.remove_users_saved_shows([id], Some(rspotify::model::Market::FromToken))
                     .await?;
+                show.map(UndoEntry::SavedShow)
+            }
+            ItemId::Episode(id) => {
+                let episode = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_episodes
+                    .iter()
+                    .find(|e| e.id == id)
+                    .cloned();
+                {
+                    let mut data = state.data.write();
+                    data.user_data.saved_episodes.retain(|e| e.id != id);
+                    data.update_saved_episodes_context();
+                }
+                self.remove_saved_episodes(&[id]).await?;
+                episode.map(UndoEntry::SavedEpisode)
+            }
+            ItemId::Audiobook(id) => {
+                let audiobook = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_audiobooks
+                    .iter()
+                    .find(|a| a.id == id)
+                    .cloned();
+                state
+                    .data
+                    .write()
+                    .user_data
+                    .saved_audiobooks
+                    .retain(|a| a.id != id);
+                self.remove_saved_audiobooks(&[&id]).await?;
+                audiobook.map(UndoEntry::SavedAudiobook)
+            }
+        };
+        if let Some(entry) = entry {
+            Self::record_undo_entry(state, entry)?;
+        }
+        Ok(())
+    }
+
+    /// Add tracks to user's liked tracks, skipping tracks that are already liked
+    async fn add_tracks_to_liked(&self, state: &SharedState, tracks: Vec<Track>) -> Result<()> {
+        for tracks in tracks.chunks(LIKED_TRACKS_BATCH_SIZE) {
+            let contains = self
+                .current_user_saved_tracks_contains(tracks.iter().map(|t| t.id.as_ref()))
+                .await?;
+            let new_tracks = tracks
+                .iter()
+                .zip(contains)
+                .filter(|(_, contains)| !contains)
+                .map(|(t, _)| t)
+                .collect::<Vec<_>>();
+            if new_tracks.is_empty() {
+                continue;
+            }
+
+            self.current_user_saved_tracks_add(new_tracks.iter().map(|t| t.id.as_ref()))
+                .await?;
+            // update the in-memory `user_data`
+            let saved_tracks = &mut state.data.write().user_data.saved_tracks;
+            for track in new_tracks {
+                saved_tracks.insert(track.id.uri(), track.clone());
+            }
+        }
+        Ok(())
+    }
+
+    /// Delete tracks from user's liked tracks
+    async fn delete_tracks_from_liked(
+        &self,
+        state: &SharedState,
+        track_ids: &[TrackId<'_>],
+    ) -> Result<()> {
+        let mut deleted_tracks = vec![];
+        for ids in track_ids.chunks(LIKED_TRACKS_BATCH_SIZE) {
+            self.current_user_saved_tracks_delete(ids.iter().map(TrackId::as_ref))
+                .await?;
+            // update the in-memory `user_data`
+            let saved_tracks = &mut state.data.write().user_data.saved_tracks;
+            deleted_tracks.extend(ids.iter().filter_map(|id| saved_tracks.remove(&id.uri())));
+        }
+
+        if !deleted_tracks.is_empty() {
+            Self::record_undo_entry(state, UndoEntry::LikedTracks(deleted_tracks))?;
+        }
+        Ok(())
+    }
+
+    /// Record a destructive edit in the undo journal
+    fn record_undo_entry(state: &SharedState, entry: UndoEntry) -> Result<()> {
+        let mut data = state.data.write();
+        let journal = &mut data.user_data.undo_journal;
+        journal.push(entry);
+        if journal.len() > UNDO_JOURNAL_SIZE {
+            journal.remove(0);
+        }
+        store_data_into_file_cache(
+            FileCacheKey::UndoJournal,
+            &config::get_config().cache_folder,
+            journal,
+        )
+        .context("store undo journal into the cache folder")
+    }
+
+    /// Revert the most recent edit recorded in the undo journal.
+    ///
+    /// If the application's state is not provided, the journal is read from the cache folder.
+    /// Returns the description of the reverted edit (if any).
+    pub async fn undo(&self, state: Option<&SharedState>) -> Result<Option<String>> {
+        let cache_folder = &config::get_config().cache_folder;
+        let Some(state) = state else {
+            let mut journal = load_data_from_file_cache::<Vec<UndoEntry>>(
+                FileCacheKey::UndoJournal,
+                cache_folder,
+            )
+            .unwrap_or_default();
+            let Some(entry) = journal.pop() else {
+                return Ok(None);
+            };
+            let desc = entry.to_string();
+            self.restore_undo_entry(None, entry).await?;
+            store_data_into_file_cache(FileCacheKey::UndoJournal, cache_folder, &journal)
+                .context("store undo journal into the cache folder")?;
+            return Ok(Some(desc));
+        };
+
+        // the entry is popped before being restored, so that edits recorded
+        // by other requests in the meantime are kept in the journal
+        let entry = {
+            let mut data = state.data.write();
+            let Some(entry) = data.user_data.undo_journal.pop() else {
+                return Ok(None);
+            };
+            store_data_into_file_cache(
+                FileCacheKey::UndoJournal,
+                cache_folder,
+                &data.user_data.undo_journal,
+            )
+            .context("store undo journal into the cache folder")?;
+            entry
+        };
+        let desc = entry.to_string();
+
+        if let Err(err) = self.restore_undo_entry(Some(state), entry.clone()).await {
+            // keep the entry in the journal, so that the edit can be reverted later
+            Self::record_undo_entry(state, entry)?;
+            return Err(err);
+        }
+        Ok(Some(desc))
+    }
+
+    /// Restore the data removed by an edit recorded in the undo journal
+    async fn restore_undo_entry(
+        &self,
+        state: Option<&SharedState>,
+        entry: UndoEntry,
+    ) -> Result<()> {
+        match entry {
+            UndoEntry::PlaylistItems {
+                playlist_id,
+                mut items,
+                snapshot_id,
+                ..
+            } => {
+                let playlist = self
+                    .http_get::<rspotify::model::FullPlaylist>(
+                        &format!("{SPOTIFY_API_ENDPOINT}/playlists/{}", playlist_id.id()),
+                        &market_query(),
+                        false,
+                    )
+                    .await?;
+                if snapshot_id != playlist.snapshot_id {
+                    tracing::warn!(
+                        "Playlist {} was modified after the deletion, restored items may not be at their former positions",
+                        playlist_id.uri()
+                    );
+                }
+
+                // Insert the items by increasing positions, so each item is placed after
+                // the (restored) items preceding it. Items with consecutive positions are
+                // inserted in a single request.
+                items.sort_by_key(|(pos, _)| *pos);
+                let mut n_items = playlist.tracks.total as usize;
+                let mut i = 0;
+                while i < items.len() {
+                    let start = items[i].0;
+                    let mut j = i + 1;
+                    while j < items.len()
+                        && j - i < PLAYLIST_ITEMS_BATCH_SIZE
+                        && items[j].0 == start + (j - i)
+                    {
+                        j += 1;
+                    }
+                    let position = std::cmp::min(start, n_items);
+                    self.playlist_add_items(
+                        playlist_id.as_ref(),
+                        items[i..j]
+                            .iter()
+                            .map(|(_, id)| PlayableId::Track(id.as_ref())),
+                        Some(position as u32),
+                    )
+                    .await?;
+                    n_items += j - i;
+                    i = j;
+                }
+
+                // remove the cache of the playlist to force refetching new data
+                if let Some(state) = state {
+                    state.data.write().caches.context.remove(&playlist_id.uri());
+                }
             }
+            UndoEntry::LikedTracks(tracks) => match state {
+                Some(state) => self.add_tracks_to_liked(state, tracks).await?,
+                None => {
+                    for tracks in tracks.chunks(LIKED_TRACKS_BATCH_SIZE) {
+                        self.current_user_saved_tracks_add(tracks.iter().map(|t| t.id.as_ref()))
+                            .await?;
+                    }
+                }
+            },
+            UndoEntry::SavedAlbum(album) => match state {
+                Some(state) => self.add_to_library(state, Item::Album(album)).await?,
+                None => self.current_user_saved_albums_add([album.id]).await?,
+            },
+            UndoEntry::FollowedArtist(artist) => match state {
+                Some(state) => self.add_to_library(state, Item::Artist(artist)).await?,
+                None => self.user_follow_artists([artist.id]).await?,
+            },
+            UndoEntry::FollowedPlaylist(playlist) => match state {
+                Some(state) => self.add_to_library(state, Item::Playlist(playlist)).await?,
+                None => self.playlist_follow(playlist.id, None).await?,
+            },
+            UndoEntry::SavedShow(show) => match state {
+                Some(state) => self.add_to_library(state, Item::Show(show)).await?,
+                None => self.save_shows([show.id]).await?,
+            },
+            UndoEntry::SavedEpisode(episode) => match state {
+                Some(state) => self.add_to_library(state, Item::Episode(episode)).await?,
+                None => self.save_episodes(&[episode.id]).await?,
+            },
+            UndoEntry::SavedAudiobook(audiobook) => match state {
+                Some(state) => {
+                    self.add_to_library(state, Item::Audiobook(audiobook))
+                        .await?;
+                }
+                None => self.save_audiobooks(&[&audiobook.id]).await?,
+            },
         }
         Ok(())
     }
@@ -1333,6 +2046,16 @@ impl Client {
         let album = self
             .album(album_id, Some(rspotify::model::Market::FromToken))
             .await?;
+        let (album, tracks) = self.full_album_tracks(album).await?;
+
+        Ok(Context::Album { album, tracks })
+    }
+
+    /// Get all tracks of an album
+    async fn full_album_tracks(
+        &self,
+        album: rspotify::model::FullAlbum,
+    ) -> Result<(Album, Vec<Track>)> {
         let first_page = album.tracks.clone();
 
         // converts `rspotify::model::FullAlbum` into `state::Album`
@@ -1354,7 +2077,7 @@ impl Client {
             })
             .collect::<Vec<_>>();
 
-        Ok(Context::Album { album, tracks })
+        Ok((album, tracks))
     }
 
     /// Get an artist context data
@@ -1436,6 +2159,112 @@ impl Client {
         Ok(Context::Show { show, episodes })
     }
 
+    /// Get the latest episodes of a show (a single page of episodes)
+    async fn show_latest_episodes(&self, show_id: ShowId<'_>) -> Result<Vec<Episode>> {
+        // episodes are parsed as `Option` for the same reason as in `show_context`
+        let page = self
+            .http_get::<rspotify::model::Page<Option<rspotify::model::SimplifiedEpisode>>>(
+                &format!("{SPOTIFY_API_ENDPOINT}/shows/{}/episodes", show_id.id()),
+                &Query::from([("limit", "50"), ("market", "from_token")]),
+                false,
+            )
+            .await?;
+        Ok(page
+            .items
+            .into_iter()
+            .flatten()
+            .map(std::convert::Into::into)
+            .collect())
+    }
+
+    /// Get an audiobook context data
+    pub async fn audiobook_context(&self, audiobook_id: &AudiobookId) -> Result<Context> {
+        tracing::info!("Get audiobook context: {}", audiobook_id.uri());
+
+        let audiobook = self
+            .http_get::<FullAudiobookObject>(
+                &format!("{SPOTIFY_API_ENDPOINT}/audiobooks/{}", audiobook_id.id()),
+                &market_query(),
+                false,
+            )
+            .await?;
+        let chapters = self
+            .all_paging_items(audiobook.chapters, &market_query())
+            .await?
+            .into_iter()
+            .map(std::convert::Into::into)
+            .collect();
+
+        Ok(Context::Audiobook {
+            audiobook: audiobook.audiobook.into(),
+            chapters,
+        })
+    }
+
+    /// Save episodes into the current user's library
+    async fn save_episodes(&self, ids: &[EpisodeId<'_>]) -> Result<()> {
+        // `rspotify` doesn't support the saved episodes API yet
+        let ids = ids.iter().map(Id::id).collect::<Vec<_>>().join(",");
+        self.api_put(&format!("me/episodes?ids={ids}"), &serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove episodes from the current user's library
+    async fn remove_saved_episodes(&self, ids: &[EpisodeId<'_>]) -> Result<()> {
+        let ids = ids.iter().map(Id::id).collect::<Vec<_>>().join(",");
+        self.api_delete(&format!("me/episodes?ids={ids}"), &serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Save audiobooks into the current user's library
+    async fn save_audiobooks(&self, ids: &[&AudiobookId]) -> Result<()> {
+        let ids = ids.iter().map(|id| id.id()).collect::<Vec<_>>().join(",");
+        self.api_put(&format!("me/audiobooks?ids={ids}"), &serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Remove audiobooks from the current user's library
+    async fn remove_saved_audiobooks(&self, ids: &[&AudiobookId]) -> Result<()> {
+        let ids = ids.iter().map(|id| id.id()).collect::<Vec<_>>().join(",");
+        self.api_delete(&format!("me/audiobooks?ids={ids}"), &serde_json::json!({}))
+            .await?;
+        Ok(())
+    }
+
+    /// Start a playback of an audiobook.
+    ///
+    /// `rspotify` doesn't support audiobook contexts, so the request is made manually.
+    async fn start_audiobook_playback(
+        &self,
+        id: &AudiobookId,
+        device_id: Option<&str>,
+        offset: Option<rspotify::model::Offset>,
+        position: Option<chrono::Duration>,
+    ) -> Result<()> {
+        let mut payload = serde_json::json!({ "context_uri": id.uri() });
+        match offset {
+            Some(rspotify::model::Offset::Uri(uri)) => {
+                payload["offset"] = serde_json::json!({ "uri": uri });
+            }
+            Some(rspotify::model::Offset::Position(position)) => {
+                payload["offset"] = serde_json::json!({ "position": position.num_milliseconds() });
+            }
+            None => {}
+        }
+        if let Some(position) = position {
+            payload["position_ms"] = serde_json::json!(position.num_milliseconds());
+        }
+        let url = match device_id {
+            Some(device_id) => format!("me/player/play?device_id={device_id}"),
+            None => "me/player/play".to_string(),
+        };
+        self.api_put(&url, &payload).await?;
+        Ok(())
+    }
+
     /// Make a GET HTTP request to the Spotify server
     async fn http_get<T>(
         &self,
@@ -1618,6 +2447,22 @@ impl Client {
             track_or_episode.clone()
         };
 
+        // count the new track's play in the local play history
+        if let rspotify::model::PlayableItem::Track(ref track) = curr_item {
+            if let Some(id) = &track.id {
+                let mut data = state.data.write();
+                *data.user_data.play_counts.entry(id.uri()).or_default() += 1;
+                // failing to store the play counts doesn't skip the new track's updates below
+                if let Err(err) = store_data_into_file_cache(
+                    FileCacheKey::PlayCounts,
+                    &configs.cache_folder,
+                    &data.user_data.play_counts,
+                ) {
+                    tracing::error!("Failed to store play counts into the cache folder: {err:#}");
+                }
+            }
+        }
+
         let url = match curr_item {
             rspotify::model::PlayableItem::Track(ref track) => {
                 crate::utils::get_track_album_image_url(track)
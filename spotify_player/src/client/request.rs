@@ -1,5 +1,6 @@
 use crate::state::{
-    AlbumId, Category, ContextId, Item, ItemId, PlayableId, Playback, PlaylistId, TrackId,
+    AlbumId, ArtistId, Category, ContextId, EpisodeId, Item, ItemId, PlayableId, Playback,
+    PlaylistId, ShowId, Track, TrackId,
 };
 
 #[derive(Clone, Debug)]
@@ -17,6 +18,8 @@ pub enum PlayerRequest {
     ToggleMute,
     TransferPlayback(String, bool),
     StartPlayback(Playback, Option<bool>),
+    /// Start a playback from a position of its first played item
+    StartPlaybackAt(Playback, chrono::Duration),
 }
 
 #[derive(Clone, Debug)]
@@ -29,10 +32,16 @@ pub enum ClientRequest {
     GetUserPlaylists,
     GetUserSavedAlbums,
     GetUserSavedShows,
+    /// Get the episode summaries of saved shows
+    GetSavedShowSummaries(Vec<ShowId<'static>>),
+    GetUserSavedEpisodes,
+    GetUserSavedAudiobooks,
     GetUserFollowedArtists,
     GetUserSavedTracks,
     GetUserTopTracks,
     GetUserRecentlyPlayedTracks,
+    /// Get the tracks of a smart playlist (by name)
+    GetSmartPlaylist(String),
     GetContext(ContextId),
     GetCurrentPlayback,
     GetRadioTracks {
@@ -41,9 +50,15 @@ pub enum ClientRequest {
     },
     Search(String),
     AddPlayableToQueue(PlayableId<'static>),
+    AddPlayablesToQueue(Vec<PlayableId<'static>>),
     AddAlbumToQueue(AlbumId<'static>),
-    AddPlayableToPlaylist(PlaylistId<'static>, PlayableId<'static>),
-    DeleteTrackFromPlaylist(PlaylistId<'static>, TrackId<'static>),
+    PlayArtistDiscography(ArtistId<'static>),
+    AddArtistDiscographyToPlaylist {
+        artist_id: ArtistId<'static>,
+        playlist_id: PlaylistId<'static>,
+    },
+    AddPlayablesToPlaylist(PlaylistId<'static>, Vec<PlayableId<'static>>),
+    DeleteTracksFromPlaylist(PlaylistId<'static>, Vec<TrackId<'static>>),
     ReorderPlaylistItems {
         playlist_id: PlaylistId<'static>,
         insert_index: usize,
@@ -53,6 +68,13 @@ pub enum ClientRequest {
     },
     AddToLibrary(Item),
     DeleteFromLibrary(ItemId),
+    AddTracksToLiked(Vec<Track>),
+    DeleteTracksFromLiked(Vec<TrackId<'static>>),
+    SetEpisodePlayed {
+        episode_id: EpisodeId<'static>,
+        played: bool,
+    },
+    Undo,
     Player(PlayerRequest),
     GetCurrentUserQueue,
     GetLyrics {
@@ -1,6 +1,9 @@
-use crate::state::{
-    Album, Artist, DataReadGuard, Episode, Playlist, PlaylistFolder, PlaylistFolderItem, Show,
-    Track,
+use crate::{
+    config,
+    state::{
+        Album, Artist, Audiobook, DataReadGuard, Device, Episode, Playlist, PlaylistFolder,
+        PlaylistFolderItem, Show, Track,
+    },
 };
 use serde::Deserialize;
 
@@ -19,12 +22,21 @@ pub enum Command {
     VolumeChange {
         offset: i32,
     },
+    SetVolume {
+        volume: u8,
+    },
     Mute,
     SeekForward,
     SeekBackward,
+    SeekTo {
+        position_secs: u32,
+    },
 
     Quit,
     OpenCommandHelp,
+    OpenCommandPalette,
+    OpenCommandLine,
+    ToggleMiniMode,
     ClosePopup,
 
     SelectNextOrScrollDown,
@@ -36,6 +48,9 @@ pub enum Command {
 
     JumpToCurrentTrackInContext,
     ChooseSelected,
+    PlayDisc {
+        disc: i32,
+    },
 
     RefreshPlayback,
 
@@ -44,6 +59,8 @@ pub enum Command {
 
     FocusNextWindow,
     FocusPreviousWindow,
+    NextArtistAlbumSection,
+    PreviousArtistAlbumSection,
 
     SwitchTheme,
     SwitchDevice,
@@ -57,16 +74,25 @@ pub enum Command {
     BrowseUserPlaylists,
     BrowseUserFollowedArtists,
     BrowseUserSavedAlbums,
+    BrowseUserSavedShows,
+    BrowseSmartPlaylists,
+    BrowseUserSavedAudiobooks,
 
     CurrentlyPlayingContextPage,
     TopTrackPage,
     RecentlyPlayedTrackPage,
     LikedTrackPage,
+    SavedEpisodePage,
     LyricsPage,
     LibraryPage,
     SearchPage,
     BrowsePage,
     PreviousPage,
+    NextPage,
+    OpenInNewTab,
+    NextTab,
+    PreviousTab,
+    CloseTab,
     OpenSpotifyLinkFromClipboard,
 
     SortTrackByTitle,
@@ -74,15 +100,33 @@ pub enum Command {
     SortTrackByAlbum,
     SortTrackByDuration,
     SortTrackByAddedDate,
+    SortTrackByColumn {
+        column: config::TrackTableColumnType,
+    },
+    AddTrackSortKey {
+        column: config::TrackTableColumnType,
+    },
     ReverseTrackOrder,
+    ClearTrackSort,
 
     MovePlaylistItemUp,
     MovePlaylistItemDown,
 
+    ToggleMark,
+    MarkRange,
+    MarkAll,
+    ClearMarks,
+
     CreatePlaylist,
+    Undo,
+
+    NewPlaylistFolder,
+    RenamePlaylistFolder,
+    DeletePlaylistFolder,
+    MoveToPlaylistFolder,
 }
 
-#[derive(Clone, Copy, Debug, Deserialize)]
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
 pub enum Action {
     GoToArtist,
     GoToAlbum,
@@ -102,11 +146,27 @@ pub enum Action {
     CopyLink,
     Follow,
     Unfollow,
+    PlayDiscography,
+    MarkAsPlayed,
+    MarkAsUnplayed,
+}
+
+#[derive(Debug)]
+/// An item in the command palette popup
+pub enum CommandPaletteItem {
+    /// a command with its mapped key sequences
+    Command(Command, Vec<String>),
+    /// an action on the selected item with its mapped key sequences
+    Action(Action, Vec<String>),
+    Playlist(Playlist),
+    Device(Device),
 }
 
 #[derive(Debug)]
 pub enum ActionContext {
     Track(Track),
+    /// marked tracks in a track table
+    Tracks(Vec<Track>),
     Album(Album),
     Artist(Artist),
     Playlist(Playlist),
@@ -115,6 +175,7 @@ pub enum ActionContext {
     // TODO: support actions for playlist folders
     PlaylistFolder(PlaylistFolder),
     Show(Show),
+    Audiobook(Audiobook),
 }
 
 #[derive(Debug, PartialEq, Clone, Deserialize, Default, Copy)]
@@ -124,11 +185,28 @@ pub enum ActionTarget {
     SelectedItem,
 }
 
+#[derive(Debug, Clone, Copy)]
 pub enum CommandOrAction {
     Command(Command),
     Action(Action, ActionTarget),
 }
 
+impl std::fmt::Display for CommandPaletteItem {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (name, desc, keys) = match self {
+            Self::Command(command, keys) => (format!("{command:?}"), command.desc(), keys),
+            Self::Action(action, keys) => (format!("{action:?}"), action.desc().to_string(), keys),
+            Self::Playlist(playlist) => return write!(f, "Playlist: {playlist}"),
+            Self::Device(device) => return write!(f, "Device: {}", device.name),
+        };
+        write!(f, "{name}: {desc}")?;
+        if !keys.is_empty() {
+            write!(f, " [{}]", keys.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
 impl From<Track> for ActionContext {
     fn from(v: Track) -> Self {
         Self::Track(v)
@@ -165,6 +243,12 @@ impl From<Episode> for ActionContext {
     }
 }
 
+impl From<Audiobook> for ActionContext {
+    fn from(v: Audiobook) -> Self {
+        Self::Audiobook(v)
+    }
+}
+
 impl From<PlaylistFolderItem> for ActionContext {
     fn from(value: PlaylistFolderItem) -> Self {
         match value {
@@ -178,6 +262,7 @@ impl ActionContext {
     pub fn get_available_actions(&self, data: &DataReadGuard) -> Vec<Action> {
         match self {
             Self::Track(track) => construct_track_actions(track, data),
+            Self::Tracks(_) => construct_marked_tracks_actions(false),
             Self::Album(album) => construct_album_actions(album, data),
             Self::Artist(artist) => construct_artist_actions(artist, data),
             Self::Playlist(playlist) => construct_playlist_actions(playlist, data),
@@ -185,6 +270,7 @@ impl ActionContext {
             // TODO: support actions for playlist folders
             Self::PlaylistFolder(_) => vec![],
             Self::Show(show) => construct_show_actions(show, data),
+            Self::Audiobook(audiobook) => construct_audiobook_actions(audiobook, data),
         }
     }
 }
@@ -211,6 +297,20 @@ pub fn construct_track_actions(track: &Track, data: &DataReadGuard) -> Vec<Actio
     actions
 }
 
+/// constructs a list of bulk actions on marked tracks
+pub fn construct_marked_tracks_actions(is_modifiable_playlist: bool) -> Vec<Action> {
+    let mut actions = vec![
+        Action::AddToPlaylist,
+        Action::AddToQueue,
+        Action::AddToLiked,
+        Action::DeleteFromLiked,
+    ];
+    if is_modifiable_playlist {
+        actions.push(Action::DeleteFromPlaylist);
+    }
+    actions
+}
+
 /// constructs a list of actions on an album
 pub fn construct_album_actions(album: &Album, data: &DataReadGuard) -> Vec<Action> {
     let mut actions = vec![
@@ -230,7 +330,12 @@ pub fn construct_album_actions(album: &Album, data: &DataReadGuard) -> Vec<Actio
 
 /// constructs a list of actions on an artist
 pub fn construct_artist_actions(artist: &Artist, data: &DataReadGuard) -> Vec<Action> {
-    let mut actions = vec![Action::GoToRadio, Action::CopyLink];
+    let mut actions = vec![
+        Action::GoToRadio,
+        Action::PlayDiscography,
+        Action::AddToPlaylist,
+        Action::CopyLink,
+    ];
 
     if data
         .user_data
@@ -273,9 +378,35 @@ pub fn construct_show_actions(show: &Show, data: &DataReadGuard) -> Vec<Action>
     actions
 }
 
+/// constructs a list of actions on an audiobook
+pub fn construct_audiobook_actions(audiobook: &Audiobook, data: &DataReadGuard) -> Vec<Action> {
+    let mut actions = vec![Action::CopyLink];
+    if data
+        .user_data
+        .saved_audiobooks
+        .iter()
+        .any(|a| a.id == audiobook.id)
+    {
+        actions.push(Action::DeleteFromLibrary);
+    } else {
+        actions.push(Action::AddToLibrary);
+    }
+    actions
+}
+
 /// constructs a list of actions on an episode
-pub fn construct_episode_actions(episode: &Episode, _data: &DataReadGuard) -> Vec<Action> {
+pub fn construct_episode_actions(episode: &Episode, data: &DataReadGuard) -> Vec<Action> {
     let mut actions = vec![Action::CopyLink, Action::AddToPlaylist, Action::AddToQueue];
+    if data.user_data.is_played_episode(episode) {
+        actions.push(Action::MarkAsUnplayed);
+    } else {
+        actions.push(Action::MarkAsPlayed);
+    }
+    if data.user_data.is_saved_episode(episode) {
+        actions.push(Action::DeleteFromLibrary);
+    } else {
+        actions.push(Action::AddToLibrary);
+    }
     if episode.show.is_some() {
         actions.push(Action::ShowActionsOnShow);
         actions.push(Action::GoToShow);
@@ -283,10 +414,250 @@ pub fn construct_episode_actions(episode: &Episode, _data: &DataReadGuard) -> Ve
     actions
 }
 
+/// constructs a list of items in the command palette popup, which consists of
+/// all commands (with their key sequences), all mapped commands with parameters, all actions, user's playlists and available devices
+pub fn construct_command_palette_items(
+    data: &DataReadGuard,
+    devices: &[Device],
+) -> Vec<CommandPaletteItem> {
+    let keymap_config = &config::get_config().keymap_config;
+
+    // unmapped commands are listed too, as they can only be run from the command palette
+    let mut commands = Command::ALL
+        .iter()
+        .map(|&command| (command, vec![]))
+        .collect::<std::collections::BTreeMap<Command, Vec<String>>>();
+    for keymap in keymap_config
+        .keymaps
+        .iter()
+        .filter(|k| k.include_in_help_screen())
+    {
+        commands
+            .entry(keymap.command)
+            .or_default()
+            .push(keymap.key_sequence.to_string());
+    }
+
+    let mut items = commands
+        .into_iter()
+        .map(|(command, keys)| CommandPaletteItem::Command(command, keys))
+        .collect::<Vec<_>>();
+
+    items.extend(Action::ALL.into_iter().map(|action| {
+        let keys = keymap_config
+            .actions
+            .iter()
+            .filter(|a| a.action == action)
+            .map(|a| a.key_sequence.to_string())
+            .collect();
+        CommandPaletteItem::Action(action, keys)
+    }));
+
+    items.extend(
+        data.user_data
+            .playlists
+            .iter()
+            .filter_map(|item| match item {
+                PlaylistFolderItem::Playlist(p) => Some(CommandPaletteItem::Playlist(p.clone())),
+                PlaylistFolderItem::Folder(_) => None,
+            }),
+    );
+
+    items.extend(devices.iter().cloned().map(CommandPaletteItem::Device));
+
+    items
+}
+
+impl Action {
+    /// all available actions
+    pub const ALL: [Action; 21] = [
+        Action::GoToArtist,
+        Action::GoToAlbum,
+        Action::GoToRadio,
+        Action::GoToShow,
+        Action::AddToLibrary,
+        Action::AddToPlaylist,
+        Action::AddToQueue,
+        Action::AddToLiked,
+        Action::DeleteFromLiked,
+        Action::DeleteFromLibrary,
+        Action::DeleteFromPlaylist,
+        Action::ShowActionsOnAlbum,
+        Action::ShowActionsOnArtist,
+        Action::ShowActionsOnShow,
+        Action::ToggleLiked,
+        Action::CopyLink,
+        Action::Follow,
+        Action::Unfollow,
+        Action::PlayDiscography,
+        Action::MarkAsPlayed,
+        Action::MarkAsUnplayed,
+    ];
+
+    pub fn desc(self) -> &'static str {
+        match self {
+            Self::GoToArtist => "go to the artist page of the selected item",
+            Self::GoToAlbum => "go to the album page of the selected item",
+            Self::GoToRadio => "go to the radio page of the selected item",
+            Self::GoToShow => "go to the show page of the selected episode",
+            Self::AddToLibrary => "add the selected item to user's library",
+            Self::AddToPlaylist => "add the selected item to a playlist",
+            Self::AddToQueue => "add the selected item to queue",
+            Self::AddToLiked => "add the selected track to user's liked tracks",
+            Self::DeleteFromLiked => "delete the selected track from user's liked tracks",
+            Self::DeleteFromLibrary => "delete the selected item from user's library",
+            Self::DeleteFromPlaylist => "delete the selected item from the current playlist",
+            Self::ShowActionsOnAlbum => "show actions on the album of the selected item",
+            Self::ShowActionsOnArtist => "show actions on the artist of the selected item",
+            Self::ShowActionsOnShow => "show actions on the show of the selected episode",
+            Self::ToggleLiked => "toggle the liked status of the selected track",
+            Self::CopyLink => "copy the link of the selected item",
+            Self::Follow => "follow the selected artist",
+            Self::Unfollow => "unfollow the selected artist",
+            Self::PlayDiscography => {
+                "play the selected artist's entire discography chronologically"
+            }
+            Self::MarkAsPlayed => "mark the selected episode as played",
+            Self::MarkAsUnplayed => "mark the selected episode as unplayed",
+        }
+    }
+}
+
 impl Command {
+    /// all available commands without parameters. Commands with parameters
+    /// (e.g. `VolumeChange`) are only available with their mapped parameters.
+    pub const ALL: &[Command] = &[
+        Command::NextTrack,
+        Command::PreviousTrack,
+        Command::ResumePause,
+        Command::PlayRandom,
+        Command::Repeat,
+        Command::ToggleFakeTrackRepeatMode,
+        Command::Shuffle,
+        Command::Mute,
+        Command::SeekForward,
+        Command::SeekBackward,
+        Command::Quit,
+        Command::OpenCommandHelp,
+        Command::OpenCommandPalette,
+        Command::OpenCommandLine,
+        Command::ToggleMiniMode,
+        Command::ClosePopup,
+        Command::SelectNextOrScrollDown,
+        Command::SelectPreviousOrScrollUp,
+        Command::PageSelectNextOrScrollDown,
+        Command::PageSelectPreviousOrScrollUp,
+        Command::SelectFirstOrScrollToTop,
+        Command::SelectLastOrScrollToBottom,
+        Command::JumpToCurrentTrackInContext,
+        Command::ChooseSelected,
+        Command::RefreshPlayback,
+        #[cfg(feature = "streaming")]
+        Command::RestartIntegratedClient,
+        Command::FocusNextWindow,
+        Command::FocusPreviousWindow,
+        Command::NextArtistAlbumSection,
+        Command::PreviousArtistAlbumSection,
+        Command::SwitchTheme,
+        Command::SwitchDevice,
+        Command::Search,
+        Command::Queue,
+        Command::ShowActionsOnSelectedItem,
+        Command::ShowActionsOnCurrentTrack,
+        Command::AddSelectedItemToQueue,
+        Command::BrowseUserPlaylists,
+        Command::BrowseUserFollowedArtists,
+        Command::BrowseUserSavedAlbums,
+        Command::BrowseUserSavedShows,
+        Command::BrowseSmartPlaylists,
+        Command::BrowseUserSavedAudiobooks,
+        Command::CurrentlyPlayingContextPage,
+        Command::TopTrackPage,
+        Command::RecentlyPlayedTrackPage,
+        Command::LikedTrackPage,
+        Command::SavedEpisodePage,
+        Command::LyricsPage,
+        Command::LibraryPage,
+        Command::SearchPage,
+        Command::BrowsePage,
+        Command::PreviousPage,
+        Command::NextPage,
+        Command::OpenInNewTab,
+        Command::NextTab,
+        Command::PreviousTab,
+        Command::CloseTab,
+        Command::OpenSpotifyLinkFromClipboard,
+        Command::SortTrackByTitle,
+        Command::SortTrackByArtists,
+        Command::SortTrackByAlbum,
+        Command::SortTrackByDuration,
+        Command::SortTrackByAddedDate,
+        Command::ReverseTrackOrder,
+        Command::ClearTrackSort,
+        Command::MovePlaylistItemUp,
+        Command::MovePlaylistItemDown,
+        Command::ToggleMark,
+        Command::MarkRange,
+        Command::MarkAll,
+        Command::ClearMarks,
+        Command::CreatePlaylist,
+        Command::Undo,
+        Command::NewPlaylistFolder,
+        Command::RenamePlaylistFolder,
+        Command::DeletePlaylistFolder,
+        Command::MoveToPlaylistFolder,
+    ];
+
+    /// checks if the command is repeated when prefixed by a count (e.g. `5j`)
+    pub fn is_repeatable(self) -> bool {
+        matches!(
+            self,
+            Self::NextTrack
+                | Self::PreviousTrack
+                | Self::SelectNextOrScrollDown
+                | Self::SelectPreviousOrScrollUp
+                | Self::PageSelectNextOrScrollDown
+                | Self::PageSelectPreviousOrScrollUp
+        )
+    }
+
+    /// checks if the command supports a count prefix,
+    /// either by being repeated or by using the count as a multiplier of its parameter
+    pub fn accepts_count(self) -> bool {
+        self.is_repeatable()
+            || matches!(
+                self,
+                Self::VolumeChange { .. }
+                    | Self::SeekForward
+                    | Self::SeekBackward
+                    | Self::MovePlaylistItemUp
+                    | Self::MovePlaylistItemDown
+            )
+    }
+
     pub fn desc(self) -> String {
-        if let Self::VolumeChange { offset } = self {
-            return format!("change playback volume by {offset}");
+        match self {
+            Self::VolumeChange { offset } => {
+                return format!("change playback volume by {offset}");
+            }
+            Self::SetVolume { volume } => return format!("set playback volume to {volume}%"),
+            Self::SeekTo { position_secs } => return format!("seek to {position_secs}s"),
+            Self::PlayDisc { disc } => {
+                return format!("play the album (if in an album page) from disc {disc}");
+            }
+            Self::SortTrackByColumn { column } => {
+                return format!(
+                    "sort the track table (if any) by the `{}` column",
+                    column.name()
+                );
+            }
+            Self::AddTrackSortKey { column } => {
+                return format!(
+                    "sort the track table (if any) additionally by the `{}` column, keeping the current order for ties",
+                    column.name()
+                );
+            }
+            _ => {}
         }
 
         match self {
@@ -329,23 +700,45 @@ impl Command {
             Self::AddSelectedItemToQueue => "add the selected item to queue",
             Self::FocusNextWindow => "focus the next focusable window (if any)",
             Self::FocusPreviousWindow => "focus the previous focusable window (if any)",
+            Self::NextArtistAlbumSection => {
+                "switch to the next section of the albums window in an artist page"
+            }
+            Self::PreviousArtistAlbumSection => {
+                "switch to the previous section of the albums window in an artist page"
+            }
             Self::SwitchTheme => "open a popup for switching theme",
             Self::SwitchDevice => "open a popup for switching device",
             Self::Search => "open a popup for searching in the current page",
             Self::BrowseUserPlaylists => "open a popup for browsing user's playlists",
             Self::BrowseUserFollowedArtists => "open a popup for browsing user's followed artists",
             Self::BrowseUserSavedAlbums => "open a popup for browsing user's saved albums",
+            Self::BrowseUserSavedShows => "open a popup for browsing user's saved shows",
+            Self::BrowseSmartPlaylists => "open a popup for browsing smart playlists",
+            Self::BrowseUserSavedAudiobooks => "open a popup for browsing user's saved audiobooks",
             Self::CurrentlyPlayingContextPage => "go to the currently playing context page",
             Self::TopTrackPage => "go to the user top track page",
             Self::RecentlyPlayedTrackPage => "go to the user recently played track page",
             Self::LikedTrackPage => "go to the user liked track page",
+            Self::SavedEpisodePage => "go to the user saved episode page",
             Self::LyricsPage => "go to the lyrics page of the current track",
             Self::LibraryPage => "go to the user library page",
             Self::SearchPage => "go to the search page",
             Self::BrowsePage => "go to the browse page",
             Self::Queue => "go to the queue page",
             Self::OpenCommandHelp => "go to the command help page",
+            Self::OpenCommandPalette => {
+                "open a popup for searching and running commands, actions, playlists and devices"
+            }
+            Self::OpenCommandLine => "open a command line for typing commands such as `volume 35`",
+            Self::ToggleMiniMode => {
+                "toggle the single-line mini mode showing only the playback status"
+            }
             Self::PreviousPage => "go to the previous page",
+            Self::NextPage => "go to the next page after going to the previous page",
+            Self::OpenInNewTab => "open the selected item in a new tab",
+            Self::NextTab => "switch to the next tab",
+            Self::PreviousTab => "switch to the previous tab",
+            Self::CloseTab => "close the current tab",
             Self::OpenSpotifyLinkFromClipboard => "open a Spotify link from clipboard",
             Self::SortTrackByTitle => "sort the track table (if any) by track's title",
             Self::SortTrackByArtists => "sort the track table (if any) by track's artists",
@@ -353,10 +746,33 @@ impl Command {
             Self::SortTrackByDuration => "sort the track table (if any) by track's duration",
             Self::SortTrackByAddedDate => "sort the track table (if any) by track's added date",
             Self::ReverseTrackOrder => "reverse the order of the track table (if any)",
+            Self::ClearTrackSort => {
+                "restore the original order of the track table (if any) and forget its chosen order"
+            }
             Self::MovePlaylistItemUp => "move playlist item up one position",
             Self::MovePlaylistItemDown => "move playlist item down one position",
+            Self::ToggleMark => "mark or unmark the selected track in the track table for bulk actions",
+            Self::MarkRange => {
+                "start marking a range in the track table, or mark tracks from the range's start to the selected track"
+            }
+            Self::MarkAll => "mark all (filtered) tracks in the track table",
+            Self::ClearMarks => "unmark all tracks in the track table",
             Self::CreatePlaylist => "create a new playlist",
-            Self::VolumeChange { offset: _ } => unreachable!(),
+            Self::Undo => "undo the most recent deletion from a playlist or the library",
+            Self::NewPlaylistFolder => "create a local playlist folder in the library's current folder",
+            Self::RenamePlaylistFolder => "rename the selected local playlist folder in the library",
+            Self::DeletePlaylistFolder => "delete the selected local playlist folder in the library",
+            Self::MoveToPlaylistFolder => {
+                "move the selected playlist or local folder in the library into another folder"
+            }
+            Self::VolumeChange { .. }
+            | Self::SetVolume { .. }
+            | Self::SeekTo { .. }
+            | Self::PlayDisc { .. }
+            | Self::SortTrackByColumn { .. }
+            | Self::AddTrackSortKey { .. } => {
+                unreachable!()
+            }
         }
         .to_string()
     }
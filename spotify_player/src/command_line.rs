@@ -0,0 +1,279 @@
+//! Parsing and completion for the ex-style (`:`) command line
+
+use crate::{
+    command::Command,
+    config::{self, TrackTableColumnType},
+    state::TrackOrder,
+    utils::map_join,
+};
+use anyhow::{bail, Context, Result};
+
+/// Names of commands supported by the command line
+pub const COMMAND_NAMES: [&str; 9] = [
+    "device", "disc", "folder", "play", "seek", "sleep", "sort", "theme", "volume",
+];
+
+/// Names of subcommands of the `folder` command
+const FOLDER_SUBCOMMANDS: [&str; 4] = ["delete", "move", "new", "rename"];
+
+/// Get fields that a track table can be sorted by, together with the corresponding column
+fn sort_fields() -> Vec<(&'static str, TrackTableColumnType)> {
+    let mut fields = vec![("added", TrackTableColumnType::AddedAt)];
+    fields.extend(
+        config::TRACK_TABLE_COLUMNS
+            .iter()
+            .filter(|(_, column)| TrackOrder::from_column(*column).is_some())
+            .copied(),
+    );
+    fields
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A command typed in the command line
+pub enum LineCommand {
+    /// Set the volume, or change it by an offset if `relative` is true
+    Volume { value: i32, relative: bool },
+    /// Seek to a position, or move by an offset if `relative` is true
+    Seek { secs: i64, relative: bool },
+    /// Start a playback from a Spotify URI
+    Play(String),
+    /// Switch to a theme
+    Theme(String),
+    /// Transfer the playback to a device whose name matches the given name
+    Device(String),
+    /// Pause the playback after a duration, or cancel the sleep timer if `None`
+    Sleep(Option<std::time::Duration>),
+    /// Sort the track table by stacked sort keys, given as sort commands
+    Sort(Vec<Command>),
+    /// Play the album of the current page from a disc
+    Disc(i32),
+    /// Edit the local playlist folders of the library page
+    Folder(FolderCommand),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+/// A command editing the local playlist folders of the library page
+pub enum FolderCommand {
+    /// Create a folder in the library's current folder
+    New(String),
+    /// Rename the selected folder
+    Rename(String),
+    /// Delete the selected folder, moving its items into the folder's parent
+    Delete,
+    /// Move the selected playlist or folder into the folder at a path, e.g. `/Rock/90s`
+    Move(String),
+}
+
+impl std::str::FromStr for LineCommand {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let s = s.trim();
+        let (name, arg) = match s.split_once(char::is_whitespace) {
+            Some((name, arg)) => (name, arg.trim()),
+            None => (s, ""),
+        };
+        if arg.is_empty() {
+            bail!("missing argument for command `{name}`");
+        }
+
+        let command = match name {
+            "vol" | "volume" => {
+                let (value, relative) = parse_signed(arg);
+                let value = value
+                    .parse::<i32>()
+                    .with_context(|| format!("invalid volume: {arg}"))?;
+                Self::Volume { value, relative }
+            }
+            "seek" => {
+                let (value, relative) = parse_signed(arg);
+                let secs = parse_position(value)?;
+                Self::Seek { secs, relative }
+            }
+            "play" => Self::Play(arg.to_string()),
+            "theme" => Self::Theme(arg.to_string()),
+            "device" => Self::Device(arg.to_string()),
+            "disc" => Self::Disc(
+                arg.parse()
+                    .with_context(|| format!("invalid disc number: {arg}"))?,
+            ),
+            "folder" => {
+                let (subcommand, arg) = match arg.split_once(char::is_whitespace) {
+                    Some((subcommand, arg)) => (subcommand, arg.trim()),
+                    None => (arg, ""),
+                };
+                if arg.is_empty() && subcommand != "delete" {
+                    bail!("missing argument for command `folder {subcommand}`");
+                }
+                Self::Folder(match subcommand {
+                    "new" => FolderCommand::New(arg.to_string()),
+                    "rename" => FolderCommand::Rename(arg.to_string()),
+                    "delete" => FolderCommand::Delete,
+                    "move" => FolderCommand::Move(arg.to_string()),
+                    _ => bail!("unknown folder command: {subcommand}"),
+                })
+            }
+            "sleep" => Self::Sleep(if arg == "off" {
+                None
+            } else {
+                Some(parse_duration(arg)?)
+            }),
+            "sort" => {
+                // the first field is the primary sort key, the others are added as secondary keys
+                let commands = arg
+                    .split_whitespace()
+                    .enumerate()
+                    .map(|(i, arg)| {
+                        let column = parse_sort_field(arg)?;
+                        Ok(if i == 0 {
+                            Command::SortTrackByColumn { column }
+                        } else {
+                            Command::AddTrackSortKey { column }
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Self::Sort(commands)
+            }
+            _ => bail!("unknown command: {name}"),
+        };
+        Ok(command)
+    }
+}
+
+/// Split a leading `+`/`-` sign from a value, returning the signed value and whether it is relative
+fn parse_signed(s: &str) -> (&str, bool) {
+    match s.strip_prefix('+') {
+        Some(s) => (s, true),
+        None => (s, s.starts_with('-')),
+    }
+}
+
+/// Find the sort field with a given name or an unambiguous prefix of it
+fn parse_sort_field(s: &str) -> Result<TrackTableColumnType> {
+    let fields = sort_fields();
+    if let Some((_, column)) = fields.iter().find(|(field, _)| *field == s) {
+        return Ok(*column);
+    }
+    let matches = fields
+        .into_iter()
+        .filter(|(field, _)| field.starts_with(s))
+        .collect::<Vec<_>>();
+    match matches.as_slice() {
+        [] => bail!("unknown sort field: {s}"),
+        [(_, column)] => Ok(*column),
+        _ => bail!(
+            "ambiguous sort field `{s}`, candidates: {}",
+            map_join(&matches, |(field, _)| *field, ", ")
+        ),
+    }
+}
+
+/// Parse a playback position in the form of `secs`, `mm:ss` or `hh:mm:ss`
+fn parse_position(s: &str) -> Result<i64> {
+    let (sign, s) = match s.strip_prefix('-') {
+        Some(s) => (-1, s),
+        None => (1, s),
+    };
+    let mut secs: i64 = 0;
+    for part in s.split(':') {
+        // only the whole position can be signed
+        if part.is_empty() || !part.bytes().all(|b| b.is_ascii_digit()) {
+            bail!("invalid position: {s}");
+        }
+        secs = part
+            .parse::<i64>()
+            .ok()
+            .and_then(|part| secs.checked_mul(60)?.checked_add(part))
+            .with_context(|| format!("position is too large: {s}"))?;
+    }
+    Ok(sign * secs)
+}
+
+/// Parse a duration in the form of `1h30m`, `20m`, `90s` or `20` (in minutes)
+fn parse_duration(s: &str) -> Result<std::time::Duration> {
+    let too_large = || format!("duration is too large: {s}");
+    if s.bytes().all(|b| b.is_ascii_digit()) {
+        let secs = s
+            .parse::<u64>()
+            .ok()
+            .and_then(|mins| mins.checked_mul(60))
+            .with_context(too_large)?;
+        return Ok(std::time::Duration::from_secs(secs));
+    }
+
+    let mut secs: u64 = 0;
+    let mut value = String::new();
+    for c in s.chars() {
+        if c.is_ascii_digit() {
+            value.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => bail!("invalid duration unit `{c}` in {s}"),
+        };
+        let value = std::mem::take(&mut value);
+        if value.is_empty() {
+            bail!("invalid duration: {s}");
+        }
+        secs = value
+            .parse::<u64>()
+            .ok()
+            .and_then(|value| secs.checked_add(value.checked_mul(unit)?))
+            .with_context(too_large)?;
+    }
+    if !value.is_empty() {
+        bail!("missing duration unit in {s}");
+    }
+    Ok(std::time::Duration::from_secs(secs))
+}
+
+/// Get completion candidates for a partially typed command line.
+///
+/// Each candidate is the full command line text after completion.
+pub fn complete(
+    text: &str,
+    themes: &[String],
+    devices: &[String],
+    folders: &[String],
+) -> Vec<String> {
+    let Some((mut name, mut arg)) = text.split_once(' ') else {
+        return COMMAND_NAMES
+            .iter()
+            .filter(|c| c.starts_with(text))
+            .map(|c| format!("{c} "))
+            .collect();
+    };
+
+    let values: Vec<&str> = match name {
+        "theme" => themes.iter().map(String::as_str).collect(),
+        "device" => devices.iter().map(String::as_str).collect(),
+        "sort" => {
+            // complete the last sort field
+            if let Some(i) = text.rfind(' ') {
+                (name, arg) = text.split_at(i + 1);
+                name = name.trim_end();
+            }
+            sort_fields().into_iter().map(|(field, _)| field).collect()
+        }
+        "sleep" => vec!["off"],
+        "folder" => {
+            // complete the destination folder of a move
+            if let Some(path) = arg.strip_prefix("move ") {
+                (name, arg) = ("folder move", path);
+                folders.iter().map(String::as_str).collect()
+            } else {
+                FOLDER_SUBCOMMANDS.to_vec()
+            }
+        }
+        _ => vec![],
+    };
+    let arg = arg.to_lowercase();
+    values
+        .into_iter()
+        .filter(|v| v.to_lowercase().starts_with(&arg))
+        .map(|v| format!("{name} {v}"))
+        .collect()
+}
@@ -1,6 +1,7 @@
 use crate::{
     command::{Action, ActionTarget, Command, CommandOrAction},
     key::{Key, KeySequence},
+    state::{PageType, PopupType, WindowType},
 };
 use anyhow::Result;
 use serde::Deserialize;
@@ -12,6 +13,8 @@ pub struct KeymapConfig {
     pub keymaps: Vec<Keymap>,
     #[serde(default)]
     pub actions: Vec<ActionMap>,
+    #[serde(default)]
+    pub macros: Vec<Macro>,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -19,6 +22,8 @@ pub struct KeymapConfig {
 pub struct Keymap {
     pub key_sequence: KeySequence,
     pub command: Command,
+    #[serde(default, flatten)]
+    pub scope: KeymapScope,
 }
 
 #[derive(Clone, Debug, Deserialize)]
@@ -28,294 +33,148 @@ pub struct ActionMap {
     #[serde(default)]
     pub target: ActionTarget,
     pub action: Action,
+    #[serde(default, flatten)]
+    pub scope: KeymapScope,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+/// A keymap that runs a list of steps in order when a key sequence is pressed
+pub struct Macro {
+    pub key_sequence: KeySequence,
+    pub steps: Vec<MacroStep>,
+    #[serde(default, flatten)]
+    pub scope: KeymapScope,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(untagged)]
+/// A step of a macro
+pub enum MacroStep {
+    /// runs a command as if its key sequence was pressed
+    Command { command: Command },
+    /// runs an action as if its key sequence was pressed
+    Action {
+        action: Action,
+        #[serde(default)]
+        target: ActionTarget,
+    },
+    /// starts a playback of a Spotify item given its URI
+    Play { play: String },
+    /// switches to a theme given its name
+    Theme { theme: String },
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+/// A scope in which a keymap is active.
+///
+/// An unspecified field matches any value, so the default scope is the global scope.
+pub struct KeymapScope {
+    pub page: Option<PageType>,
+    pub window: Option<WindowType>,
+    pub popup: Option<PopupType>,
 }
 
 impl Default for KeymapConfig {
     fn default() -> Self {
         KeymapConfig {
             actions: vec![],
+            macros: vec![],
             keymaps: vec![
-                Keymap {
-                    key_sequence: "n".into(),
-                    command: Command::NextTrack,
-                },
-                Keymap {
-                    key_sequence: "p".into(),
-                    command: Command::PreviousTrack,
-                },
-                Keymap {
-                    key_sequence: ".".into(),
-                    command: Command::PlayRandom,
-                },
-                Keymap {
-                    key_sequence: "space".into(),
-                    command: Command::ResumePause,
-                },
-                Keymap {
-                    key_sequence: "C-r".into(),
-                    command: Command::Repeat,
-                },
-                Keymap {
-                    key_sequence: "M-r".into(),
-                    command: Command::ToggleFakeTrackRepeatMode,
-                },
-                Keymap {
-                    key_sequence: "C-s".into(),
-                    command: Command::Shuffle,
-                },
-                Keymap {
-                    key_sequence: "+".into(),
-                    command: Command::VolumeChange { offset: 5 },
-                },
-                Keymap {
-                    key_sequence: "-".into(),
-                    command: Command::VolumeChange { offset: -5 },
-                },
-                Keymap {
-                    key_sequence: "_".into(),
-                    command: Command::Mute,
-                },
-                Keymap {
-                    key_sequence: ">".into(),
-                    command: Command::SeekForward,
-                },
-                Keymap {
-                    key_sequence: "<".into(),
-                    command: Command::SeekBackward,
-                },
-                Keymap {
-                    key_sequence: "enter".into(),
-                    command: Command::ChooseSelected,
-                },
-                Keymap {
-                    key_sequence: "r".into(),
-                    command: Command::RefreshPlayback,
-                },
-                Keymap {
-                    key_sequence: "/".into(),
-                    command: Command::Search,
-                },
-                Keymap {
-                    key_sequence: "z".into(),
-                    command: Command::Queue,
-                },
-                Keymap {
-                    key_sequence: "C-z".into(),
-                    command: Command::AddSelectedItemToQueue,
-                },
-                Keymap {
-                    key_sequence: "Z".into(),
-                    command: Command::AddSelectedItemToQueue,
-                },
-                Keymap {
-                    key_sequence: "C-space".into(),
-                    command: Command::ShowActionsOnSelectedItem,
-                },
-                Keymap {
-                    key_sequence: "g a".into(),
-                    command: Command::ShowActionsOnSelectedItem,
-                },
-                Keymap {
-                    key_sequence: "a".into(),
-                    command: Command::ShowActionsOnCurrentTrack,
-                },
+                Keymap::global("n", Command::NextTrack),
+                Keymap::global("p", Command::PreviousTrack),
+                Keymap::global(".", Command::PlayRandom),
+                Keymap::global("space", Command::ResumePause),
+                Keymap::global("C-r", Command::Repeat),
+                Keymap::global("M-r", Command::ToggleFakeTrackRepeatMode),
+                Keymap::global("C-s", Command::Shuffle),
+                Keymap::global("+", Command::VolumeChange { offset: 5 }),
+                Keymap::global("-", Command::VolumeChange { offset: -5 }),
+                Keymap::global("_", Command::Mute),
+                Keymap::global(">", Command::SeekForward),
+                Keymap::global("<", Command::SeekBackward),
+                Keymap::global("enter", Command::ChooseSelected),
+                Keymap::global("r", Command::RefreshPlayback),
+                Keymap::global("/", Command::Search),
+                Keymap::global("z", Command::Queue),
+                Keymap::global("C-z", Command::AddSelectedItemToQueue),
+                Keymap::global("Z", Command::AddSelectedItemToQueue),
+                Keymap::global("C-space", Command::ShowActionsOnSelectedItem),
+                Keymap::global("g a", Command::ShowActionsOnSelectedItem),
+                Keymap::global("a", Command::ShowActionsOnCurrentTrack),
                 #[cfg(feature = "streaming")]
-                Keymap {
-                    key_sequence: "R".into(),
-                    command: Command::RestartIntegratedClient,
-                },
-                Keymap {
-                    key_sequence: "tab".into(),
-                    command: Command::FocusNextWindow,
-                },
-                Keymap {
-                    key_sequence: "backtab".into(),
-                    command: Command::FocusPreviousWindow,
-                },
-                Keymap {
-                    key_sequence: "T".into(),
-                    command: Command::SwitchTheme,
-                },
-                Keymap {
-                    key_sequence: "D".into(),
-                    command: Command::SwitchDevice,
-                },
-                Keymap {
-                    key_sequence: "u p".into(),
-                    command: Command::BrowseUserPlaylists,
-                },
-                Keymap {
-                    key_sequence: "u a".into(),
-                    command: Command::BrowseUserFollowedArtists,
-                },
-                Keymap {
-                    key_sequence: "u A".into(),
-                    command: Command::BrowseUserSavedAlbums,
-                },
-                Keymap {
-                    key_sequence: "g space".into(),
-                    command: Command::CurrentlyPlayingContextPage,
-                },
-                Keymap {
-                    key_sequence: "g t".into(),
-                    command: Command::TopTrackPage,
-                },
-                Keymap {
-                    key_sequence: "g r".into(),
-                    command: Command::RecentlyPlayedTrackPage,
-                },
-                Keymap {
-                    key_sequence: "g y".into(),
-                    command: Command::LikedTrackPage,
-                },
-                Keymap {
-                    key_sequence: "g L".into(),
-                    command: Command::LyricsPage,
-                },
-                Keymap {
-                    key_sequence: "l".into(),
-                    command: Command::LyricsPage,
-                },
-                Keymap {
-                    key_sequence: "g l".into(),
-                    command: Command::LibraryPage,
-                },
-                Keymap {
-                    key_sequence: "g s".into(),
-                    command: Command::SearchPage,
-                },
-                Keymap {
-                    key_sequence: "g b".into(),
-                    command: Command::BrowsePage,
-                },
-                Keymap {
-                    key_sequence: "backspace".into(),
-                    command: Command::PreviousPage,
-                },
-                Keymap {
-                    key_sequence: "C-q".into(),
-                    command: Command::PreviousPage,
-                },
-                Keymap {
-                    key_sequence: "O".into(),
-                    command: Command::OpenSpotifyLinkFromClipboard,
-                },
-                Keymap {
-                    key_sequence: "?".into(),
-                    command: Command::OpenCommandHelp,
-                },
-                Keymap {
-                    key_sequence: "C-h".into(),
-                    command: Command::OpenCommandHelp,
-                },
-                Keymap {
-                    key_sequence: "q".into(),
-                    command: Command::Quit,
-                },
-                Keymap {
-                    key_sequence: "C-c".into(),
-                    command: Command::Quit,
-                },
-                Keymap {
-                    key_sequence: "esc".into(),
-                    command: Command::ClosePopup,
-                },
-                Keymap {
-                    key_sequence: "j".into(),
-                    command: Command::SelectNextOrScrollDown,
-                },
-                Keymap {
-                    key_sequence: "C-n".into(),
-                    command: Command::SelectNextOrScrollDown,
-                },
-                Keymap {
-                    key_sequence: "down".into(),
-                    command: Command::SelectNextOrScrollDown,
-                },
-                Keymap {
-                    key_sequence: "k".into(),
-                    command: Command::SelectPreviousOrScrollUp,
-                },
-                Keymap {
-                    key_sequence: "C-p".into(),
-                    command: Command::SelectPreviousOrScrollUp,
-                },
-                Keymap {
-                    key_sequence: "up".into(),
-                    command: Command::SelectPreviousOrScrollUp,
-                },
-                Keymap {
-                    key_sequence: "page_up".into(),
-                    command: Command::PageSelectPreviousOrScrollUp,
-                },
-                Keymap {
-                    key_sequence: "C-b".into(),
-                    command: Command::PageSelectPreviousOrScrollUp,
-                },
-                Keymap {
-                    key_sequence: "page_down".into(),
-                    command: Command::PageSelectNextOrScrollDown,
-                },
-                Keymap {
-                    key_sequence: "C-f".into(),
-                    command: Command::PageSelectNextOrScrollDown,
-                },
-                Keymap {
-                    key_sequence: "g g".into(),
-                    command: Command::SelectFirstOrScrollToTop,
-                },
-                Keymap {
-                    key_sequence: "home".into(),
-                    command: Command::SelectFirstOrScrollToTop,
-                },
-                Keymap {
-                    key_sequence: "G".into(),
-                    command: Command::SelectLastOrScrollToBottom,
-                },
-                Keymap {
-                    key_sequence: "end".into(),
-                    command: Command::SelectLastOrScrollToBottom,
-                },
-                Keymap {
-                    key_sequence: "s t".into(),
-                    command: Command::SortTrackByTitle,
-                },
-                Keymap {
-                    key_sequence: "s a".into(),
-                    command: Command::SortTrackByArtists,
-                },
-                Keymap {
-                    key_sequence: "s A".into(),
-                    command: Command::SortTrackByAlbum,
-                },
-                Keymap {
-                    key_sequence: "s d".into(),
-                    command: Command::SortTrackByDuration,
-                },
-                Keymap {
-                    key_sequence: "s D".into(),
-                    command: Command::SortTrackByAddedDate,
-                },
-                Keymap {
-                    key_sequence: "s r".into(),
-                    command: Command::ReverseTrackOrder,
-                },
-                Keymap {
-                    key_sequence: "C-k".into(),
-                    command: Command::MovePlaylistItemUp,
-                },
-                Keymap {
-                    key_sequence: "C-j".into(),
-                    command: Command::MovePlaylistItemDown,
-                },
-                Keymap {
-                    key_sequence: "N".into(),
-                    command: Command::CreatePlaylist,
-                },
-                Keymap {
-                    key_sequence: "g c".into(),
-                    command: Command::JumpToCurrentTrackInContext,
-                },
+                Keymap::global("R", Command::RestartIntegratedClient),
+                Keymap::global("tab", Command::FocusNextWindow),
+                Keymap::global("backtab", Command::FocusPreviousWindow),
+                Keymap::global("}", Command::NextArtistAlbumSection),
+                Keymap::global("{", Command::PreviousArtistAlbumSection),
+                Keymap::global("T", Command::SwitchTheme),
+                Keymap::global("D", Command::SwitchDevice),
+                Keymap::global("u p", Command::BrowseUserPlaylists),
+                Keymap::global("u a", Command::BrowseUserFollowedArtists),
+                Keymap::global("u A", Command::BrowseUserSavedAlbums),
+                Keymap::global("u s", Command::BrowseUserSavedShows),
+                Keymap::global("u S", Command::BrowseSmartPlaylists),
+                Keymap::global("u b", Command::BrowseUserSavedAudiobooks),
+                Keymap::global("g space", Command::CurrentlyPlayingContextPage),
+                Keymap::global("g t", Command::TopTrackPage),
+                Keymap::global("g r", Command::RecentlyPlayedTrackPage),
+                Keymap::global("g y", Command::LikedTrackPage),
+                Keymap::global("g e", Command::SavedEpisodePage),
+                Keymap::global("g L", Command::LyricsPage),
+                Keymap::global("l", Command::LyricsPage),
+                Keymap::global("g l", Command::LibraryPage),
+                Keymap::global("g s", Command::SearchPage),
+                Keymap::global("g b", Command::BrowsePage),
+                Keymap::global("backspace", Command::PreviousPage),
+                Keymap::global("C-q", Command::PreviousPage),
+                Keymap::global("M-right", Command::NextPage),
+                Keymap::global("C-t", Command::OpenInNewTab),
+                Keymap::global("]", Command::NextTab),
+                Keymap::global("[", Command::PreviousTab),
+                Keymap::global("C-w", Command::CloseTab),
+                Keymap::global("O", Command::OpenSpotifyLinkFromClipboard),
+                Keymap::global("?", Command::OpenCommandHelp),
+                Keymap::global("C-h", Command::OpenCommandHelp),
+                Keymap::global("M-x", Command::OpenCommandPalette),
+                Keymap::global(":", Command::OpenCommandLine),
+                Keymap::global("M-m", Command::ToggleMiniMode),
+                Keymap::global("q", Command::Quit),
+                Keymap::global("C-c", Command::Quit),
+                Keymap::global("esc", Command::ClosePopup),
+                Keymap::global("j", Command::SelectNextOrScrollDown),
+                Keymap::global("C-n", Command::SelectNextOrScrollDown),
+                Keymap::global("down", Command::SelectNextOrScrollDown),
+                Keymap::global("k", Command::SelectPreviousOrScrollUp),
+                Keymap::global("C-p", Command::SelectPreviousOrScrollUp),
+                Keymap::global("up", Command::SelectPreviousOrScrollUp),
+                Keymap::global("page_up", Command::PageSelectPreviousOrScrollUp),
+                Keymap::global("C-b", Command::PageSelectPreviousOrScrollUp),
+                Keymap::global("page_down", Command::PageSelectNextOrScrollDown),
+                Keymap::global("C-f", Command::PageSelectNextOrScrollDown),
+                Keymap::global("g g", Command::SelectFirstOrScrollToTop),
+                Keymap::global("home", Command::SelectFirstOrScrollToTop),
+                Keymap::global("G", Command::SelectLastOrScrollToBottom),
+                Keymap::global("end", Command::SelectLastOrScrollToBottom),
+                Keymap::global("s t", Command::SortTrackByTitle),
+                Keymap::global("s a", Command::SortTrackByArtists),
+                Keymap::global("s A", Command::SortTrackByAlbum),
+                Keymap::global("s d", Command::SortTrackByDuration),
+                Keymap::global("s D", Command::SortTrackByAddedDate),
+                Keymap::global("s r", Command::ReverseTrackOrder),
+                Keymap::global("s c", Command::ClearTrackSort),
+                Keymap::global("C-k", Command::MovePlaylistItemUp),
+                Keymap::global("C-j", Command::MovePlaylistItemDown),
+                Keymap::global("m", Command::ToggleMark),
+                Keymap::global("V", Command::MarkRange),
+                Keymap::global("C-a", Command::MarkAll),
+                Keymap::global("M", Command::ClearMarks),
+                Keymap::global("N", Command::CreatePlaylist),
+                Keymap::global("U", Command::Undo),
+                Keymap::in_page("F n", Command::NewPlaylistFolder, PageType::Library),
+                Keymap::in_page("F r", Command::RenamePlaylistFolder, PageType::Library),
+                Keymap::in_page("F d", Command::DeletePlaylistFolder, PageType::Library),
+                Keymap::in_page("F m", Command::MoveToPlaylistFolder, PageType::Library),
+                Keymap::global("g c", Command::JumpToCurrentTrackInContext),
             ],
         }
     }
@@ -342,15 +201,17 @@ impl KeymapConfig {
                 let mut parsed = toml::from_str::<Self>(&content)?;
                 std::mem::swap(&mut self.keymaps, &mut parsed.keymaps);
                 std::mem::swap(&mut self.actions, &mut parsed.actions);
+                // there is no default macro, so user-defined macros can be used directly
+                self.macros = parsed.macros;
 
                 // a dumb approach (with quadratic complexity) to merge two different keymap arrays
                 // while keeping the invariant:
-                // - each `KeySequence` is mapped to only one `Command`.
+                // - each `KeySequence` is mapped to only one `Command` in a scope.
                 parsed.keymaps.into_iter().for_each(|keymap| {
                     if !self
                         .keymaps
                         .iter()
-                        .any(|k| k.key_sequence == keymap.key_sequence)
+                        .any(|k| k.key_sequence == keymap.key_sequence && k.scope == keymap.scope)
                     {
                         self.keymaps.push(keymap);
                     }
@@ -359,7 +220,7 @@ impl KeymapConfig {
                     if !self
                         .actions
                         .iter()
-                        .any(|k| k.key_sequence == action.key_sequence)
+                        .any(|k| k.key_sequence == action.key_sequence && k.scope == action.scope)
                     {
                         self.actions.push(action);
                     }
@@ -369,60 +230,289 @@ impl KeymapConfig {
         Ok(())
     }
 
-    /// finds all keymaps whose mapped key sequence has a given `prefix` key sequence as its prefix
-    pub fn find_matched_prefix_keymaps(&self, prefix: &KeySequence) -> Vec<&Keymap> {
+    /// checks the keymap config file in `path` folder, returning a list of found issues
+    /// such as unknown commands/actions or key sequences mapped more than once
+    pub fn check_config_file(path: &std::path::Path) -> Result<Vec<String>> {
+        let file = super::KEYMAP_CONFIG_FILE;
+        let content = match std::fs::read_to_string(path.join(file)) {
+            Ok(content) => content,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(err) => return Err(err.into()),
+        };
+        let value = match toml::from_str::<toml::Table>(&content) {
+            Ok(value) => value,
+            Err(err) => return Ok(vec![format!("{file}: {err}")]),
+        };
+
+        let mut issues = vec![];
+        // maps each parsed (scoped) key sequence to the entries binding it
+        let mut bindings: Vec<((KeySequence, KeymapScope), Vec<String>)> = vec![];
+        for (key, value) in value {
+            let parse_entry: fn(toml::Value) -> Result<_, toml::de::Error> = match key.as_str() {
+                "keymaps" => |v| v.try_into::<Keymap>().map(|k| (k.key_sequence, k.scope)),
+                "actions" => |v| v.try_into::<ActionMap>().map(|a| (a.key_sequence, a.scope)),
+                "macros" => |v| v.try_into::<Macro>().map(|m| (m.key_sequence, m.scope)),
+                _ => {
+                    issues.push(format!("{file}: unknown key `{key}`"));
+                    continue;
+                }
+            };
+            let toml::Value::Array(entries) = value else {
+                issues.push(format!("{file}: `{key}` must be an array of tables"));
+                continue;
+            };
+            // scope fields are flattened into the entries, in which unknown fields are ignored
+            // when parsing, so they are reported explicitly (e.g. a misspelled scope field)
+            let fields: &[&str] = match key.as_str() {
+                "keymaps" => &["key_sequence", "command", "page", "window", "popup"],
+                "actions" => &[
+                    "key_sequence",
+                    "target",
+                    "action",
+                    "page",
+                    "window",
+                    "popup",
+                ],
+                _ => &["key_sequence", "steps", "page", "window", "popup"],
+            };
+            for (i, entry) in entries.into_iter().enumerate() {
+                let entry_name = format!("{key}[{i}]");
+                if let Some(field) = entry
+                    .as_table()
+                    .and_then(|t| t.keys().find(|k| !fields.contains(&k.as_str())))
+                {
+                    issues.push(format!(
+                        "{file}: `{entry_name}`: unknown field `{field}`, expected one of {}",
+                        fields
+                            .iter()
+                            .map(|f| format!("`{f}`"))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ));
+                    continue;
+                }
+                match parse_entry(entry) {
+                    Ok(binding) => match bindings.iter_mut().find(|(b, _)| *b == binding) {
+                        Some((_, entries)) => entries.push(entry_name),
+                        None => bindings.push((binding, vec![entry_name])),
+                    },
+                    Err(err) => issues.push(format!("{file}: `{entry_name}`: {}", err.message())),
+                }
+            }
+        }
+        for ((key_sequence, scope), entries) in bindings {
+            if entries.len() > 1 {
+                issues.push(format!(
+                    "{file}: key sequence `{key_sequence}` is mapped more than once in `{scope}` scope ({})",
+                    entries.join(", ")
+                ));
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// finds all keymaps active in a `scope` whose mapped key sequence has a given `prefix` key sequence as its prefix
+    pub fn find_matched_prefix_keymaps(
+        &self,
+        prefix: &KeySequence,
+        scope: KeymapScope,
+    ) -> Vec<&Keymap> {
         self.keymaps
             .iter()
-            .filter(|&keymap| prefix.is_prefix(&keymap.key_sequence))
+            .filter(|&keymap| {
+                keymap.scope.contains(scope) && prefix.is_prefix(&keymap.key_sequence)
+            })
             .collect()
     }
 
-    /// finds all actions whose mapped key sequence has a given `prefix` key sequence as its prefix
-    pub fn find_matched_prefix_actions(&self, prefix: &KeySequence) -> Vec<&ActionMap> {
+    /// finds all actions active in a `scope` whose mapped key sequence has a given `prefix` key sequence as its prefix
+    pub fn find_matched_prefix_actions(
+        &self,
+        prefix: &KeySequence,
+        scope: KeymapScope,
+    ) -> Vec<&ActionMap> {
         self.actions
             .iter()
-            .filter(|&action| prefix.is_prefix(&action.key_sequence))
+            .filter(|&action| {
+                action.scope.contains(scope) && prefix.is_prefix(&action.key_sequence)
+            })
             .collect()
     }
 
-    /// checks if there is any command or action that has a given `prefix` key sequence as its prefix
-    pub fn has_matched_prefix(&self, prefix: &KeySequence) -> bool {
-        let keymaps = self.find_matched_prefix_keymaps(prefix);
-        let actions = self.find_matched_prefix_actions(prefix);
-        !keymaps.is_empty() || !actions.is_empty()
+    /// checks if there is any command, action or macro active in a `scope`
+    /// that has a given `prefix` key sequence as its prefix
+    pub fn has_matched_prefix(&self, prefix: &KeySequence, scope: KeymapScope) -> bool {
+        let keymaps = self.find_matched_prefix_keymaps(prefix, scope);
+        let actions = self.find_matched_prefix_actions(prefix, scope);
+        let has_macro = self
+            .macros
+            .iter()
+            .any(|m| m.scope.contains(scope) && prefix.is_prefix(&m.key_sequence));
+        !keymaps.is_empty() || !actions.is_empty() || has_macro
     }
 
-    /// finds a command from a mapped key sequence
-    pub fn find_command_from_key_sequence(&self, key_sequence: &KeySequence) -> Option<Command> {
-        self.keymaps
-            .iter()
-            .find(|&keymap| keymap.key_sequence == *key_sequence && keymap.command != Command::None)
-            .map(|keymap| keymap.command)
+    /// finds the most specific macro active in a `scope` from a mapped key sequence
+    pub fn find_macro_from_key_sequence(
+        &self,
+        key_sequence: &KeySequence,
+        scope: KeymapScope,
+    ) -> Option<&Macro> {
+        most_specific(
+            self.macros
+                .iter()
+                .filter(|&m| m.key_sequence == *key_sequence),
+            |m| m.scope,
+            scope,
+        )
     }
 
-    /// finds an action from a mapped key sequence
-    pub fn find_action_from_key_sequence(
+    /// finds the most specific keymap active in a `scope` from a mapped key sequence
+    fn find_keymap_from_key_sequence(
         &self,
         key_sequence: &KeySequence,
-    ) -> Option<(Action, ActionTarget)> {
-        self.actions
-            .iter()
-            .find(|&action| action.key_sequence == *key_sequence)
-            .map(|action| (action.action, action.target))
+        scope: KeymapScope,
+    ) -> Option<&Keymap> {
+        most_specific(
+            self.keymaps
+                .iter()
+                .filter(|&keymap| keymap.key_sequence == *key_sequence),
+            |keymap| keymap.scope,
+            scope,
+        )
+    }
+
+    /// finds the most specific action active in a `scope` from a mapped key sequence
+    fn find_action_map_from_key_sequence(
+        &self,
+        key_sequence: &KeySequence,
+        scope: KeymapScope,
+    ) -> Option<&ActionMap> {
+        most_specific(
+            self.actions
+                .iter()
+                .filter(|&action| action.key_sequence == *key_sequence),
+            |action| action.scope,
+            scope,
+        )
     }
 
-    /// finds a command or action from a mapped key sequence
+    /// finds a command active in a `scope` from a mapped key sequence
+    pub fn find_command_from_key_sequence(
+        &self,
+        key_sequence: &KeySequence,
+        scope: KeymapScope,
+    ) -> Option<Command> {
+        self.find_keymap_from_key_sequence(key_sequence, scope)
+            .map(|keymap| keymap.command)
+            .filter(|&command| command != Command::None)
+    }
+
+    /// finds a command or action active in a `scope` from a mapped key sequence.
+    ///
+    /// If both a command and an action are found, the one with a more specific scope is returned,
+    /// with the command taking precedence over the action if their scopes are equally specific.
     pub fn find_command_or_action_from_key_sequence(
         &self,
         key_sequence: &KeySequence,
+        scope: KeymapScope,
     ) -> Option<CommandOrAction> {
-        if let Some(command) = self.find_command_from_key_sequence(key_sequence) {
-            return Some(CommandOrAction::Command(command));
+        let keymap = self.find_keymap_from_key_sequence(key_sequence, scope);
+        let action = self.find_action_map_from_key_sequence(key_sequence, scope);
+        match (keymap, action) {
+            (Some(keymap), Some(action))
+                if action.scope.specificity() > keymap.scope.specificity() =>
+            {
+                Some(CommandOrAction::Action(action.action, action.target))
+            }
+            // a `None` command disables the key sequence in the keymap's scope
+            (Some(keymap), _) => (keymap.command != Command::None)
+                .then_some(CommandOrAction::Command(keymap.command)),
+            (None, Some(action)) => Some(CommandOrAction::Action(action.action, action.target)),
+            (None, None) => None,
+        }
+    }
+}
+
+/// finds the item with the most specific scope among `items` whose scope is active in `scope`.
+/// If multiple items are equally specific, the first one is returned.
+fn most_specific<'a, T>(
+    items: impl Iterator<Item = &'a T>,
+    get_scope: impl Fn(&T) -> KeymapScope,
+    scope: KeymapScope,
+) -> Option<&'a T> {
+    items
+        .filter(|item| get_scope(item).contains(scope))
+        .fold(None, |best: Option<&T>, item| match best {
+            Some(best) if get_scope(best).specificity() >= get_scope(item).specificity() => {
+                Some(best)
+            }
+            _ => Some(item),
+        })
+}
+
+impl Keymap {
+    /// creates a keymap active in the global scope
+    fn global(key_sequence: &str, command: Command) -> Self {
+        Self {
+            key_sequence: key_sequence.into(),
+            command,
+            scope: KeymapScope::default(),
+        }
+    }
+
+    /// creates a keymap active in a page
+    fn in_page(key_sequence: &str, command: Command, page: PageType) -> Self {
+        Self {
+            key_sequence: key_sequence.into(),
+            command,
+            scope: KeymapScope {
+                page: Some(page),
+                ..KeymapScope::default()
+            },
         }
-        if let Some((action, target)) = self.find_action_from_key_sequence(key_sequence) {
-            return Some(CommandOrAction::Action(action, target));
+    }
+}
+
+impl KeymapScope {
+    /// checks if the scope contains a given (current) `scope`,
+    /// i.e. every specified field of the scope matches the given scope's field
+    fn contains(self, scope: KeymapScope) -> bool {
+        (self.page.is_none() || self.page == scope.page)
+            && (self.window.is_none() || self.window == scope.window)
+            && (self.popup.is_none() || self.popup == scope.popup)
+    }
+
+    /// the number of specified fields, used to determine the most specific scope
+    fn specificity(self) -> usize {
+        usize::from(self.page.is_some())
+            + usize::from(self.window.is_some())
+            + usize::from(self.popup.is_some())
+    }
+
+    /// checks if the scope is the global scope, i.e. no field is specified
+    pub fn is_global(self) -> bool {
+        self.specificity() == 0
+    }
+}
+
+impl std::fmt::Display for KeymapScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut fields = vec![];
+        if let Some(page) = self.page {
+            fields.push(format!("page={page:?}"));
+        }
+        if let Some(window) = self.window {
+            fields.push(format!("window={window:?}"));
+        }
+        if let Some(popup) = self.popup {
+            fields.push(format!("popup={popup:?}"));
+        }
+        if fields.is_empty() {
+            write!(f, "global")
+        } else {
+            write!(f, "{}", fields.join(", "))
         }
-        None
     }
 }
 
@@ -434,13 +524,11 @@ impl Keymap {
 
 impl std::fmt::Display for Keymap {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "{} -> {:?}: {}",
-            self.key_sequence,
-            self.command,
-            self.command.desc()
-        )
+        write!(f, "{}", self.key_sequence)?;
+        if !self.scope.is_global() {
+            write!(f, " ({})", self.scope)?;
+        }
+        write!(f, " -> {:?}: {}", self.command, self.command.desc())
     }
 }
 
@@ -1,4 +1,5 @@
 mod keymap;
+mod smart_playlist;
 mod theme;
 
 const DEFAULT_CONFIG_FOLDER: &str = ".config/spotify-player";
@@ -6,8 +7,10 @@ const DEFAULT_CACHE_FOLDER: &str = ".cache/spotify-player";
 const APP_CONFIG_FILE: &str = "app.toml";
 const THEME_CONFIG_FILE: &str = "theme.toml";
 const KEYMAP_CONFIG_FILE: &str = "keymap.toml";
+const SMART_PLAYLIST_CONFIG_FILE: &str = "smart_playlists.toml";
+const CONFIG_ENV_VAR_PREFIX: &str = "SPOTIFY_PLAYER_";
 
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, Context, Result};
 use config_parser2::{config_parser_impl, ConfigParse, ConfigParser};
 use librespot_core::config::SessionConfig;
 use reqwest::Url;
@@ -18,8 +21,11 @@ use std::{
 };
 
 use keymap::KeymapConfig;
+use smart_playlist::SmartPlaylistConfig;
 use theme::ThemeConfig;
 
+pub use keymap::{KeymapScope, MacroStep};
+pub use smart_playlist::{SmartPlaylist, SmartPlaylistSource};
 pub use theme::Theme;
 
 use crate::auth::SPOTIFY_CLIENT_ID;
@@ -31,15 +37,21 @@ pub struct Configs {
     pub app_config: AppConfig,
     pub keymap_config: KeymapConfig,
     pub theme_config: ThemeConfig,
+    pub smart_playlist_config: SmartPlaylistConfig,
     pub cache_folder: std::path::PathBuf,
 }
 
 impl Configs {
-    pub fn new(config_folder: &std::path::Path, cache_folder: &std::path::Path) -> Result<Self> {
+    pub fn new(
+        config_folder: &std::path::Path,
+        cache_folder: &std::path::Path,
+        overrides: &[String],
+    ) -> Result<Self> {
         Ok(Self {
-            app_config: AppConfig::new(config_folder)?,
+            app_config: AppConfig::new(config_folder, overrides)?,
             keymap_config: KeymapConfig::new(config_folder)?,
             theme_config: ThemeConfig::new(config_folder)?,
+            smart_playlist_config: SmartPlaylistConfig::new(config_folder)?,
             cache_folder: cache_folder.to_path_buf(),
         })
     }
@@ -47,6 +59,9 @@ impl Configs {
 
 #[derive(Debug, Deserialize, Serialize, ConfigParse)]
 #[allow(clippy::struct_excessive_bools)]
+// `deny_unknown_fields` only affects `config check`, as configurations
+// are normally parsed with `ConfigParser`, which ignores unknown keys
+#[serde(deny_unknown_fields)]
 /// Application configurations
 pub struct AppConfig {
     pub theme: String,
@@ -60,6 +75,7 @@ pub struct AppConfig {
     pub player_event_hook_command: Option<Command>,
 
     pub playback_format: String,
+    pub mini_playback_format: String,
     #[cfg(feature = "notify")]
     pub notify_format: NotifyFormat,
     #[cfg(feature = "notify")]
@@ -88,6 +104,12 @@ pub struct AppConfig {
 
     pub layout: LayoutConfig,
 
+    pub track_table: TrackTableConfig,
+
+    // mini mode configs
+    pub mini_mode: bool,
+    pub mini_mode_height_threshold: u16,
+
     #[cfg(feature = "image")]
     pub cover_img_length: usize,
     #[cfg(feature = "image")]
@@ -115,6 +137,8 @@ pub struct AppConfig {
 
     pub seek_duration_secs: u16,
 
+    /// deprecated and ignored, as albums on artist pages are always grouped by their types
+    #[serde(default, skip_serializing)]
     pub sort_artist_albums_by_type: bool,
 }
 
@@ -170,6 +194,7 @@ impl Command {
 }
 
 #[derive(Debug, Deserialize, Serialize, ConfigParse, Clone)]
+#[serde(deny_unknown_fields)]
 /// Application device configurations
 pub struct DeviceConfig {
     pub name: String,
@@ -183,23 +208,272 @@ pub struct DeviceConfig {
 
 #[derive(Debug, Deserialize, Serialize, ConfigParse, Clone)]
 #[cfg(feature = "notify")]
+#[serde(deny_unknown_fields)]
 pub struct NotifyFormat {
     pub summary: String,
     pub body: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, ConfigParse, Clone)]
+#[serde(deny_unknown_fields)]
 // Application layout configurations
 pub struct LayoutConfig {
     pub library: LibraryLayoutConfig,
     pub playback_window_position: Position,
     pub playback_window_height: usize,
+    /// the main area's panes when the application is in horizontal orientation
+    pub horizontal: PaneLayout,
+    /// the main area's panes when the application is in vertical orientation
+    pub vertical: PaneLayout,
 }
 
 #[derive(Debug, Deserialize, Serialize, ConfigParse, Clone)]
+#[serde(deny_unknown_fields)]
+#[allow(clippy::struct_field_names)]
 pub struct LibraryLayoutConfig {
     pub playlist_percent: u16,
     pub album_percent: u16,
+    /// If not specified, the show window takes half of the space left by the playlist and album windows
+    pub show_percent: Option<u16>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// A pane of the application's main area
+pub enum PaneType {
+    /// the current page
+    Page,
+    /// the playback's queue
+    Queue,
+    /// the lyrics of the currently playing track
+    Lyrics,
+    /// the cover image of the currently playing item
+    Cover,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Pane {
+    pub pane_type: PaneType,
+    /// the pane's size in percentage, the remaining space is shared
+    /// between panes without a specified size if not set
+    pub percent: Option<u16>,
+}
+
+/// A layout of the application's main area, which splits the area into multiple panes
+/// along the orientation's axis.
+///
+/// The layout is represented by a list of panes separated by `|`, each of which
+/// can have an optional size in percentage, e.g. `"page | queue:30"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct PaneLayout(pub Vec<Pane>);
+config_parser_impl!(PaneLayout);
+
+impl TryFrom<String> for PaneLayout {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        let panes = s
+            .split('|')
+            .map(|pane| {
+                let pane = pane.trim();
+                let (name, percent) = match pane.split_once(':') {
+                    Some((name, percent)) => (
+                        name.trim(),
+                        Some(
+                            percent
+                                .trim()
+                                .parse::<u16>()
+                                .with_context(|| format!("invalid pane size in `{pane}`"))?,
+                        ),
+                    ),
+                    None => (pane, None),
+                };
+                let pane_type = match name {
+                    "page" => PaneType::Page,
+                    "queue" => PaneType::Queue,
+                    "lyrics" => PaneType::Lyrics,
+                    "cover" => PaneType::Cover,
+                    _ => anyhow::bail!("unknown pane `{name}`, expected one of `page`, `queue`, `lyrics` or `cover`"),
+                };
+                Ok(Pane { pane_type, percent })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        if panes
+            .iter()
+            .filter(|p| p.pane_type == PaneType::Page)
+            .count()
+            != 1
+        {
+            anyhow::bail!("layout `{s}` must have exactly one `page` pane");
+        }
+        if panes.iter().filter_map(|p| p.percent).sum::<u16>() > 100 {
+            anyhow::bail!("summation of pane sizes in layout `{s}` cannot be greater than 100");
+        }
+        Ok(Self(panes))
+    }
+}
+
+impl From<PaneLayout> for String {
+    fn from(layout: PaneLayout) -> Self {
+        layout
+            .0
+            .iter()
+            .map(|pane| {
+                let name = match pane.pane_type {
+                    PaneType::Page => "page",
+                    PaneType::Queue => "queue",
+                    PaneType::Lyrics => "lyrics",
+                    PaneType::Cover => "cover",
+                };
+                match pane.percent {
+                    Some(percent) => format!("{name}:{percent}"),
+                    None => name.to_string(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+impl PaneLayout {
+    /// checks if the layout has a pane of a given type
+    pub fn has_pane(&self, pane_type: PaneType) -> bool {
+        self.0.iter().any(|p| p.pane_type == pane_type)
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ConfigParse, Clone)]
+#[serde(deny_unknown_fields)]
+/// Columns of track tables, which can be configured for each type of context pages
+pub struct TrackTableConfig {
+    /// the columns of a track table if its page has no specific configuration
+    pub default: TrackTableColumns,
+    pub playlist: Option<TrackTableColumns>,
+    pub album: Option<TrackTableColumns>,
+    pub artist: Option<TrackTableColumns>,
+    /// the columns of track tables in pages like liked tracks, top tracks or recently played tracks
+    pub tracks: Option<TrackTableColumns>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+/// A column of a track table
+pub enum TrackTableColumnType {
+    /// the liked icon of a liked track
+    Liked,
+    /// the track's position in the table, or the playback status if the track is playing
+    Number,
+    Title,
+    Artists,
+    Album,
+    Duration,
+    /// the track's number in its album
+    Track,
+    /// the track's disc number in its album
+    Disc,
+    Popularity,
+    ReleaseDate,
+    AddedAt,
+    /// the user who added the track to a (collaborative) playlist
+    AddedBy,
+    /// an explicit marker of the track
+    Explicit,
+    /// the number of times the track was played by the application
+    PlayCount,
+}
+
+/// Names of track table columns used in the configuration
+pub const TRACK_TABLE_COLUMNS: [(&str, TrackTableColumnType); 14] = [
+    ("liked", TrackTableColumnType::Liked),
+    ("number", TrackTableColumnType::Number),
+    ("title", TrackTableColumnType::Title),
+    ("artists", TrackTableColumnType::Artists),
+    ("album", TrackTableColumnType::Album),
+    ("duration", TrackTableColumnType::Duration),
+    ("track", TrackTableColumnType::Track),
+    ("disc", TrackTableColumnType::Disc),
+    ("popularity", TrackTableColumnType::Popularity),
+    ("release_date", TrackTableColumnType::ReleaseDate),
+    ("added_at", TrackTableColumnType::AddedAt),
+    ("added_by", TrackTableColumnType::AddedBy),
+    ("explicit", TrackTableColumnType::Explicit),
+    ("play_count", TrackTableColumnType::PlayCount),
+];
+
+impl TrackTableColumnType {
+    pub fn name(self) -> &'static str {
+        TRACK_TABLE_COLUMNS
+            .iter()
+            .find(|(_, column)| *column == self)
+            .map(|(name, _)| *name)
+            .expect("all columns are named")
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct TrackTableColumn {
+    pub column_type: TrackTableColumnType,
+    /// the column's fixed width, the column's default width is used if not set
+    pub width: Option<u16>,
+}
+
+/// Columns of a track table, represented by a list of column names separated by `|`,
+/// each of which can have an optional fixed width, e.g. `"liked | number | title | added_by:20"`.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TrackTableColumns(pub Vec<TrackTableColumn>);
+config_parser_impl!(TrackTableColumns);
+
+impl TryFrom<String> for TrackTableColumns {
+    type Error = anyhow::Error;
+
+    fn try_from(s: String) -> Result<Self> {
+        let columns =
+            s.split('|')
+                .map(|column| {
+                    let column = column.trim();
+                    let (name, width) =
+                        match column.split_once(':') {
+                            Some((name, width)) => (
+                                name.trim(),
+                                Some(width.trim().parse::<u16>().with_context(|| {
+                                    format!("invalid column width in `{column}`")
+                                })?),
+                            ),
+                            None => (column, None),
+                        };
+                    let column_type = TRACK_TABLE_COLUMNS
+                        .iter()
+                        .find(|(n, _)| *n == name)
+                        .map(|(_, column)| *column)
+                        .with_context(|| {
+                            format!(
+                                "unknown track table column `{name}`, expected one of {}",
+                                TRACK_TABLE_COLUMNS
+                                    .map(|(n, _)| format!("`{n}`"))
+                                    .join(", ")
+                            )
+                        })?;
+                    Ok(TrackTableColumn { column_type, width })
+                })
+                .collect::<Result<Vec<_>>>()?;
+        Ok(Self(columns))
+    }
+}
+
+impl From<TrackTableColumns> for String {
+    fn from(columns: TrackTableColumns) -> Self {
+        columns
+            .0
+            .iter()
+            .map(|column| match column.width {
+                Some(width) => format!("{}:{width}", column.column_type.name()),
+                None => column.column_type.name().to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -266,6 +540,7 @@ impl Default for AppConfig {
             tracks_playback_limit: 50,
 
             playback_format: String::from("{status} {track} • {artists}\n{album}\n{metadata}"),
+            mini_playback_format: String::from("{status} {track} • {artists}"),
             #[cfg(feature = "notify")]
             notify_format: NotifyFormat {
                 summary: String::from("{track} • {artists}"),
@@ -292,6 +567,11 @@ impl Default for AppConfig {
 
             layout: LayoutConfig::default(),
 
+            track_table: TrackTableConfig::default(),
+
+            mini_mode: false,
+            mini_mode_height_threshold: 5,
+
             #[cfg(feature = "image")]
             cover_img_length: 9,
             #[cfg(feature = "image")]
@@ -352,9 +632,38 @@ impl Default for LayoutConfig {
             library: LibraryLayoutConfig {
                 playlist_percent: 40,
                 album_percent: 40,
+                show_percent: None,
             },
             playback_window_position: Position::Top,
             playback_window_height: 6,
+            horizontal: PaneLayout(vec![Pane {
+                pane_type: PaneType::Page,
+                percent: None,
+            }]),
+            vertical: PaneLayout(vec![Pane {
+                pane_type: PaneType::Page,
+                percent: None,
+            }]),
+        }
+    }
+}
+
+impl Default for TrackTableConfig {
+    fn default() -> Self {
+        Self {
+            default: "liked | number | title | artists | album | duration"
+                .to_string()
+                .try_into()
+                .expect("valid track table columns"),
+            playlist: None,
+            album: Some(
+                "liked | track | title | artists | duration"
+                    .to_string()
+                    .try_into()
+                    .expect("valid track table columns"),
+            ),
+            artist: None,
+            tracks: None,
         }
     }
 }
@@ -364,21 +673,99 @@ impl LayoutConfig {
         if self.library.album_percent + self.library.playlist_percent > 99 {
             anyhow::bail!("Invalid library layout: summation of album_percent and playlist_percent cannot be greater than 99!");
         }
+        if self.library.album_percent
+            + self.library.playlist_percent
+            + self.library.show_percent.unwrap_or_default()
+            > 99
+        {
+            anyhow::bail!("Invalid library layout: summation of album_percent, playlist_percent and show_percent cannot be greater than 99!");
+        }
         Ok(())
     }
 }
 
 impl AppConfig {
-    pub fn new(path: &Path) -> Result<Self> {
-        let mut config = Self::default();
-        if !config.parse_config_file(path)? {
-            config.write_config_file(path)?;
+    /// creates the application configurations like `load`,
+    /// writing the default configurations into the config file if no config file found
+    pub fn new(path: &Path, overrides: &[String]) -> Result<Self> {
+        if !path.join(APP_CONFIG_FILE).exists() {
+            Self::default().write_config_file(path)?;
         }
+        Self::load(path, overrides)
+    }
 
+    /// loads the application configurations from the config file in `path` folder,
+    /// then applies the environment variable overrides and the `key=value` `overrides`
+    pub fn load(path: &Path, overrides: &[String]) -> Result<Self> {
+        let mut config = Self::default();
+        config.parse_config_file(path)?;
+        config.apply_overrides(overrides)?;
         config.layout.check_values()?;
         Ok(config)
     }
 
+    /// applies configuration overrides, which can be specified by either
+    /// - a `SPOTIFY_PLAYER_{KEY}` environment variable, in which `__` separates nested keys,
+    ///   e.g. `SPOTIFY_PLAYER_DEVICE__BITRATE=160`
+    /// - a `{key}={value}` argument, in which `.` separates nested keys, e.g. `device.bitrate=160`
+    ///
+    /// Argument overrides take precedence over environment variable overrides.
+    fn apply_overrides(&mut self, overrides: &[String]) -> Result<()> {
+        let env_overrides = std::env::vars().filter_map(|(name, value)| {
+            let key = name
+                .strip_prefix(CONFIG_ENV_VAR_PREFIX)?
+                .to_lowercase()
+                .replace("__", ".");
+            Some((name, key, value, true))
+        });
+        let arg_overrides = overrides
+            .iter()
+            .map(|arg| match arg.split_once('=') {
+                Some((key, value)) => {
+                    Ok((arg.clone(), key.trim().to_owned(), value.to_owned(), false))
+                }
+                None => Err(anyhow!(
+                    "invalid config override {arg}: expect a `key=value` format"
+                )),
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let defaults = toml::Value::try_from(Self::default())?;
+        let override_table = |path: &[String], value: toml::Value| {
+            let mut table = toml::Value::Table(toml::Table::new());
+            set_nested_value(&mut table, path, value);
+            let mut issues = vec![];
+            find_invalid_keys::<Self>(&defaults, &mut vec![], &table, &mut issues);
+            (table, issues)
+        };
+        for (source, key, value, is_env) in env_overrides.chain(arg_overrides) {
+            let path = key.split('.').map(str::to_owned).collect::<Vec<_>>();
+            let (mut table, mut issues) = override_table(&path, parse_override_value(&value));
+            // a value parsed as a non-string TOML value (e.g. an all-digit client ID)
+            // can still be a valid string value
+            if !issues.is_empty() {
+                let (str_table, str_issues) =
+                    override_table(&path, toml::Value::String(value.clone()));
+                if str_issues.is_empty() {
+                    (table, issues) = (str_table, str_issues);
+                }
+            }
+
+            if let Some(issue) = issues.first() {
+                // unrelated environment variables may share the prefix,
+                // so environment variables with unknown options are skipped.
+                // Logging is not initialized when loading configurations, so the warning is printed.
+                if is_env && issue.contains("unknown field") {
+                    eprintln!("Ignored config override {source}: {issue}");
+                    continue;
+                }
+                anyhow::bail!("invalid config override {source}: {issue}");
+            }
+            self.parse(table)?;
+        }
+        Ok(())
+    }
+
     // parses configurations from an application config file in `path` folder,
     // then updates the current configurations accordingly.
     // returns false if no config file found and true otherwise
@@ -401,6 +788,35 @@ impl AppConfig {
             })
     }
 
+    /// checks the application config file in `path` folder, returning a list of found issues
+    /// such as unknown keys or invalid values
+    fn check_config_file(path: &Path) -> Result<Vec<String>> {
+        let content = match std::fs::read_to_string(path.join(APP_CONFIG_FILE)) {
+            Ok(content) => content,
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => return Ok(vec![]),
+            Err(error) => return Err(error.into()),
+        };
+        let value = match toml::from_str::<toml::Value>(&content) {
+            Ok(value) => value,
+            Err(err) => return Ok(vec![format!("{APP_CONFIG_FILE}: {err}")]),
+        };
+
+        let defaults = toml::Value::try_from(Self::default())?;
+        let mut issues = vec![];
+        find_invalid_keys::<Self>(&defaults, &mut vec![], &value, &mut issues);
+        if issues.is_empty() {
+            let mut config = Self::default();
+            config.parse(value)?;
+            if let Err(err) = config.layout.check_values() {
+                issues.push(err.to_string());
+            }
+        }
+        Ok(issues
+            .into_iter()
+            .map(|issue| format!("{APP_CONFIG_FILE}: {issue}"))
+            .collect())
+    }
+
     pub fn session_config(&self) -> SessionConfig {
         let proxy = self
             .proxy
@@ -430,6 +846,78 @@ impl AppConfig {
     }
 }
 
+/// finds invalid keys in a TOML table `value` located at `path` of a configuration
+/// by deserializing each key on top of the default configurations `defaults`.
+/// Nested tables are checked recursively to locate the invalid keys.
+fn find_invalid_keys<T: serde::de::DeserializeOwned>(
+    defaults: &toml::Value,
+    path: &mut Vec<String>,
+    value: &toml::Value,
+    issues: &mut Vec<String>,
+) {
+    let Some(table) = value.as_table() else {
+        return;
+    };
+    for (key, value) in table {
+        path.push(key.clone());
+
+        let mut config = defaults.clone();
+        set_nested_value(&mut config, path, value.clone());
+        if let Err(err) = config.try_into::<T>() {
+            let is_nested_table = value.is_table()
+                && path
+                    .iter()
+                    .try_fold(defaults, |v, key| v.get(key))
+                    .is_some_and(toml::Value::is_table);
+            if is_nested_table {
+                find_invalid_keys::<T>(defaults, path, value, issues);
+            } else {
+                issues.push(format!("`{}`: {}", path.join("."), err.message()));
+            }
+        }
+
+        path.pop();
+    }
+}
+
+/// sets the value located at a (nested) `path` of a TOML table,
+/// creating intermediate tables if needed
+fn set_nested_value(root: &mut toml::Value, path: &[String], value: toml::Value) {
+    let Some((last, parents)) = path.split_last() else {
+        return;
+    };
+    let mut table = root;
+    for key in parents {
+        let toml::Value::Table(t) = table else {
+            return;
+        };
+        table = t
+            .entry(key.clone())
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+    }
+    if let toml::Value::Table(t) = table {
+        t.insert(last.clone(), value);
+    }
+}
+
+/// parses the value of a configuration override as a TOML value,
+/// falling back to a string if the value is not a valid TOML value
+fn parse_override_value(value: &str) -> toml::Value {
+    toml::from_str::<toml::Table>(&format!("value = {value}"))
+        .ok()
+        .and_then(|mut table| table.remove("value"))
+        .unwrap_or_else(|| toml::Value::String(value.to_owned()))
+}
+
+/// checks the configuration files in `path` folder, returning a list of found issues
+pub fn check_config_files(path: &Path) -> Result<Vec<String>> {
+    let mut issues = AppConfig::check_config_file(path)?;
+    issues.extend(KeymapConfig::check_config_file(path)?);
+    issues.extend(ThemeConfig::check_config_file(path)?);
+    issues.extend(SmartPlaylistConfig::check_config_file(path)?);
+    Ok(issues)
+}
+
 /// gets the application's configuration folder path
 pub fn get_config_folder_path() -> Result<PathBuf> {
     match dirs_next::home_dir() {
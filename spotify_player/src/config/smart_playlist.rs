@@ -0,0 +1,94 @@
+use anyhow::Result;
+use serde::Deserialize;
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Smart playlist configurations
+pub struct SmartPlaylistConfig {
+    #[serde(default)]
+    pub smart_playlists: Vec<SmartPlaylist>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// A playlist whose tracks are selected from the user's library by rules
+pub struct SmartPlaylist {
+    pub name: String,
+    /// where the playlist's tracks are selected from
+    #[serde(default)]
+    pub source: SmartPlaylistSource,
+    /// rules that a track must satisfy to be included in the playlist
+    #[serde(default)]
+    pub rules: SmartPlaylistRules,
+    /// the maximum number of tracks in the playlist
+    pub limit: Option<usize>,
+    /// ID of the Spotify playlist the smart playlist is synced into.
+    /// If not specified, the playlist with the same name created by `smart sync` is used (or created).
+    pub playlist_id: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SmartPlaylistSource {
+    /// the user's liked tracks
+    #[default]
+    LikedTracks,
+    /// tracks of the user's saved albums
+    SavedAlbums,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+/// Rules of a smart playlist. A track must satisfy all the specified rules.
+pub struct SmartPlaylistRules {
+    /// only include tracks by artists the user follows
+    #[serde(default)]
+    pub followed_artists_only: bool,
+    /// only include tracks by artists with one of the names (case-insensitive)
+    #[serde(default)]
+    pub artists: Vec<String>,
+    /// only include tracks added to the library in the last number of days.
+    /// A track of a saved album is added when the album is saved.
+    pub added_within_days: Option<u64>,
+    /// only include tracks released before the year
+    pub released_before: Option<i32>,
+    /// only include tracks released after the year
+    pub released_after: Option<i32>,
+    /// only include tracks played by the application at least the number of times
+    pub min_play_count: Option<u32>,
+    /// only include tracks played by the application at most the number of times
+    pub max_play_count: Option<u32>,
+}
+
+impl SmartPlaylistConfig {
+    /// finds a smart playlist whose name matches a given `name`
+    pub fn find_smart_playlist(&self, name: &str) -> Option<&SmartPlaylist> {
+        self.smart_playlists.iter().find(|p| p.name == name)
+    }
+
+    pub fn new(path: &std::path::Path) -> Result<Self> {
+        let file_path = path.join(super::SMART_PLAYLIST_CONFIG_FILE);
+        match std::fs::read_to_string(&file_path) {
+            Err(err) => {
+                tracing::warn!(
+                    "Failed to open the smart playlist config file (path={file_path:?}): {err:#}. No smart playlist is defined",
+                );
+                Ok(Self::default())
+            }
+            Ok(content) => Ok(toml::from_str::<Self>(&content)?),
+        }
+    }
+
+    /// checks the smart playlist config file in `path` folder, returning a list of found issues
+    pub fn check_config_file(path: &std::path::Path) -> Result<Vec<String>> {
+        let file = super::SMART_PLAYLIST_CONFIG_FILE;
+        match std::fs::read_to_string(path.join(file)) {
+            Ok(content) => Ok(match toml::from_str::<Self>(&content) {
+                Ok(_) => vec![],
+                Err(err) => vec![format!("{file}: {}", err.message())],
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
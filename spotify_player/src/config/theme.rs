@@ -76,6 +76,7 @@ struct ComponentStyle {
     playlist_desc: Option<Style>,
     table_header: Option<Style>,
     selection: Option<Style>,
+    marked: Option<Style>,
     secondary_row: Option<Style>,
     like: Option<Style>,
     lyrics_played: Option<Style>,
@@ -164,6 +165,19 @@ impl ThemeConfig {
         }
         Ok(())
     }
+
+    /// checks the theme config file in `path` folder, returning a list of found issues
+    pub fn check_config_file(path: &std::path::Path) -> Result<Vec<String>> {
+        let file = super::THEME_CONFIG_FILE;
+        match std::fs::read_to_string(path.join(file)) {
+            Ok(content) => Ok(match toml::from_str::<Self>(&content) {
+                Ok(_) => vec![],
+                Err(err) => vec![format!("{file}: {}", err.message())],
+            }),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err.into()),
+        }
+    }
 }
 
 impl Theme {
@@ -324,6 +338,14 @@ impl Theme {
             .style(&self.palette)
     }
 
+    pub fn marked(&self) -> style::Style {
+        self.component_style
+            .marked
+            .as_ref()
+            .unwrap_or(&Style::default().fg(StyleColor::Yellow))
+            .style(&self.palette)
+    }
+
     pub fn secondary_row(&self) -> style::Style {
         self.component_style
             .secondary_row
@@ -4,16 +4,17 @@ use crate::{
         self, construct_artist_actions, Action, ActionContext, ActionTarget, Command,
         CommandOrAction,
     },
-    config,
+    config::{self, MacroStep},
     key::{Key, KeySequence},
     state::{
         ActionListItem, Album, AlbumId, Artist, ArtistFocusState, ArtistId, ArtistPopupAction,
-        BrowsePageUIState, Context, ContextId, ContextPageType, ContextPageUIState, DataReadGuard,
-        Focusable, Id, Item, ItemId, LibraryFocusState, LibraryPageUIState, PageState, PageType,
-        PlayableId, Playback, PlaylistCreateCurrentField, PlaylistFolderItem, PlaylistId,
-        PlaylistPopupAction, PopupState, SearchFocusState, SearchPageUIState, SharedState, ShowId,
-        Track, TrackId, TrackOrder, UIStateGuard, USER_LIKED_TRACKS_ID,
-        USER_RECENTLY_PLAYED_TRACKS_ID, USER_TOP_TRACKS_ID,
+        AudiobookId, BrowsePageUIState, Context, ContextId, ContextPageType, ContextPageUIState,
+        DataReadGuard, EpisodeId, Focusable, Id, Item, ItemId, LibraryFocusState,
+        LibraryPageUIState, MouseTarget, PageState, PageType, PlayableId, Playback,
+        PlaylistCreateCurrentField, PlaylistFolderItem, PlaylistId, PlaylistPopupAction,
+        PopupState, SearchFocusState, SearchPageUIState, SharedState, ShowId, Track, TrackId,
+        TrackOrder, UIStateGuard, USER_LIKED_TRACKS_ID, USER_RECENTLY_PLAYED_TRACKS_ID,
+        USER_TOP_TRACKS_ID,
     },
     ui::{single_line_input::LineInput, Orientation},
     utils::parse_uri,
@@ -30,6 +31,9 @@ mod page;
 mod popup;
 mod window;
 
+/// The maximum value of a count prefix
+const MAX_COUNT_PREFIX: usize = 999;
+
 /// Start a terminal event handler (key pressed, mouse clicked, etc)
 pub fn start_event_handler(state: &SharedState, client_pub: &flume::Sender<ClientRequest>) {
     while let Ok(event) = crossterm::event::read() {
@@ -58,34 +62,138 @@ pub fn start_event_handler(state: &SharedState, client_pub: &flume::Sender<Clien
     }
 }
 
+/// The maximum duration between two clicks of a double click
+const DOUBLE_CLICK_DURATION: std::time::Duration = std::time::Duration::from_millis(500);
+
 // Handle a terminal mouse event
 fn handle_mouse_event(
     event: crossterm::event::MouseEvent,
     client_pub: &flume::Sender<ClientRequest>,
     state: &SharedState,
 ) -> Result<()> {
-    // a left click event
-    if let crossterm::event::MouseEventKind::Down(crossterm::event::MouseButton::Left) = event.kind
-    {
-        tracing::debug!("Handling mouse event: {event:?}");
-        let rect = state.ui.lock().playback_progress_bar_rect;
-        if event.row == rect.y {
-            // calculate the seek position (in ms) based on the mouse click position,
-            // the progress bar's width and the track's duration (in ms)
-            let player = state.player.read();
-            let duration = match player.currently_playing() {
-                Some(rspotify::model::PlayableItem::Track(track)) => Some(track.duration),
-                Some(rspotify::model::PlayableItem::Episode(episode)) => Some(episode.duration),
-                None => None,
+    use crossterm::event::{MouseButton, MouseEventKind};
+
+    let mut ui = state.ui.lock();
+    // clicks on the current page's windows are ignored if there is a focused popup
+    let region = ui.find_mouse_region(event.column, event.row).filter(|r| {
+        !(matches!(
+            r.target,
+            MouseTarget::Window { .. } | MouseTarget::Row { .. }
+        ) && ui.has_focused_popup())
+    });
+
+    match event.kind {
+        // a left click event
+        MouseEventKind::Down(MouseButton::Left) => {
+            tracing::debug!("Handling mouse event: {event:?}");
+            let rect = ui.playback_progress_bar_rect;
+            if rect.contains((event.column, event.row).into()) {
+                // calculate the seek position (in ms) based on the mouse click position,
+                // the progress bar's width and the track's duration (in ms)
+                let player = state.player.read();
+                let duration = match player.currently_playing() {
+                    Some(rspotify::model::PlayableItem::Track(track)) => Some(track.duration),
+                    Some(rspotify::model::PlayableItem::Episode(episode)) => Some(episode.duration),
+                    None => None,
+                };
+                if let Some(duration) = duration {
+                    let position_ms = (duration.num_milliseconds())
+                        * i64::from(event.column - rect.x)
+                        / i64::from(rect.width);
+                    client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(
+                        chrono::Duration::try_milliseconds(position_ms).unwrap(),
+                    )))?;
+                }
+                return Ok(());
+            }
+
+            let Some(region) = region else {
+                return Ok(());
             };
-            if let Some(duration) = duration {
-                let position_ms =
-                    (duration.num_milliseconds()) * i64::from(event.column) / i64::from(rect.width);
-                client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(
-                    chrono::Duration::try_milliseconds(position_ms).unwrap(),
-                )))?;
+            let is_double_click = ui.last_mouse_click.is_some_and(|(time, column, row)| {
+                (column, row) == (event.column, event.row) && time.elapsed() < DOUBLE_CLICK_DURATION
+            });
+            ui.last_mouse_click =
+                (!is_double_click).then(|| (std::time::Instant::now(), event.column, event.row));
+
+            // the clicked row's position in a list/table, starting from the region's top
+            let row = usize::from(event.row - region.rect.y);
+            let selected = match region.target {
+                MouseTarget::Button(found) => {
+                    handle_command_or_action(found, client_pub, state, &mut ui)?;
+                    return Ok(());
+                }
+                MouseTarget::Tab(id) => {
+                    ui.switch_tab(id);
+                    return Ok(());
+                }
+                MouseTarget::ArtistAlbumSection(section) => {
+                    ui.current_page_mut().select_artist_album_section(section);
+                    return Ok(());
+                }
+                MouseTarget::Window { window, len } => {
+                    let page = ui.current_page_mut();
+                    page.focus_window(window);
+                    match page.focus_window_state_mut() {
+                        Some(mut window_state) if window_state.offset() + row < len => {
+                            window_state.select(window_state.offset() + row);
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+                MouseTarget::Row { window, id } => {
+                    let page = ui.current_page_mut();
+                    page.focus_window(window);
+                    match page.focus_window_state_mut() {
+                        Some(mut window_state) => {
+                            window_state.select(id);
+                            true
+                        }
+                        None => false,
+                    }
+                }
+                MouseTarget::PopupList { len } => {
+                    match ui.popup.as_mut().and_then(PopupState::list_state_mut) {
+                        Some(list_state) if list_state.offset() + row < len => {
+                            list_state.select(Some(list_state.offset() + row));
+                            true
+                        }
+                        _ => false,
+                    }
+                }
+            };
+
+            // double clicking an item chooses it
+            if selected && is_double_click {
+                handle_command_or_action(
+                    CommandOrAction::Command(Command::ChooseSelected),
+                    client_pub,
+                    state,
+                    &mut ui,
+                )?;
             }
         }
+        MouseEventKind::ScrollDown | MouseEventKind::ScrollUp => {
+            // scrolling a window focuses it before moving its selection
+            if let Some(MouseTarget::Window { window, .. } | MouseTarget::Row { window, .. }) =
+                region.map(|r| r.target)
+            {
+                ui.current_page_mut().focus_window(window);
+            }
+            let command = if event.kind == MouseEventKind::ScrollDown {
+                Command::SelectNextOrScrollDown
+            } else {
+                Command::SelectPreviousOrScrollUp
+            };
+            handle_command_or_action(
+                CommandOrAction::Command(command),
+                client_pub,
+                state,
+                &mut ui,
+            )?;
+        }
+        _ => {}
     }
     Ok(())
 }
@@ -99,50 +207,199 @@ fn handle_key_event(
     let key: Key = event.into();
     let mut ui = state.ui.lock();
 
+    let keymap_config = &config::get_config().keymap_config;
+    let scope = ui.keymap_scope();
+
+    // handle a digit key typed before a key sequence as a part of the count prefix,
+    // unless the digit key is mapped to any command/action or digits are used for user's inputs
+    if ui.input_key_sequence.keys.is_empty() && ui.accepts_count_prefix() {
+        let digit = match key.plain_code() {
+            Some(crossterm::event::KeyCode::Char(c)) => c.to_digit(10),
+            _ => None,
+        };
+        if let Some(digit) = digit {
+            let is_mapped =
+                keymap_config.has_matched_prefix(&KeySequence { keys: vec![key] }, scope);
+            // similar to vim, `0` can only be a part of a non-empty count prefix
+            if !is_mapped && (digit > 0 || ui.count_prefix.is_some()) {
+                let count = ui.count_prefix.unwrap_or_default() * 10 + digit as usize;
+                ui.count_prefix = Some(count.min(MAX_COUNT_PREFIX));
+                return Ok(());
+            }
+        }
+    }
+
     let mut key_sequence = ui.input_key_sequence.clone();
     key_sequence.keys.push(key);
 
     // check if the current key sequence matches any keymap's prefix
     // if not, reset the key sequence
-    let keymap_config = &config::get_config().keymap_config;
-    if !keymap_config.has_matched_prefix(&key_sequence) {
+    if !keymap_config.has_matched_prefix(&key_sequence, scope) {
         key_sequence = KeySequence { keys: vec![key] };
     }
 
     tracing::debug!("Handling key event: {event:?}, current key sequence: {key_sequence:?}");
-    let handled = {
-        if ui.popup.is_none() {
+
+    // macros take precedence over commands and actions mapped to the same key sequence
+    if let Some(m) = keymap_config.find_macro_from_key_sequence(&key_sequence, scope) {
+        ui.input_key_sequence.keys = vec![];
+        ui.count_prefix = None;
+        return handle_macro_steps(&m.steps, client_pub, state, &mut ui);
+    }
+
+    let found = keymap_config.find_command_or_action_from_key_sequence(&key_sequence, scope);
+
+    // a repeatable command is handled multiple times based on the count prefix,
+    // other commands supporting a count prefix read the count from the UI state when being handled
+    let n_repeats = match found {
+        Some(CommandOrAction::Command(command)) if command.is_repeatable() => count_prefix(&ui),
+        _ => 1,
+    };
+
+    let mut handled = false;
+    for _ in 0..n_repeats {
+        handled = if ui.popup.is_none() {
             page::handle_key_sequence_for_page(&key_sequence, client_pub, state, &mut ui)?
         } else {
             popup::handle_key_sequence_for_popup(&key_sequence, client_pub, state, &mut ui)?
+        };
+
+        // if the key sequence is not handled, let the global handler handle it
+        if !handled {
+            handled = match found {
+                Some(CommandOrAction::Action(action, target)) => {
+                    handle_global_action(action, target, client_pub, state, &mut ui)?
+                }
+                Some(CommandOrAction::Command(command)) => {
+                    handle_global_command(command, client_pub, state, &mut ui)?
+                }
+                None => false,
+            };
         }
-    };
 
-    // if the key sequence is not handled, let the global handler handle it
-    let handled = if handled {
-        true
-    } else {
-        match keymap_config.find_command_or_action_from_key_sequence(&key_sequence) {
-            Some(CommandOrAction::Action(action, target)) => {
-                handle_global_action(action, target, client_pub, state, &mut ui)?
-            }
-            Some(CommandOrAction::Command(command)) => {
-                handle_global_command(command, client_pub, state, &mut ui)?
-            }
-            None => false,
+        if !handled {
+            break;
         }
-    };
+    }
 
-    // if handled, clear the key sequence
+    // if handled, clear the key sequence and the count prefix
     // otherwise, the current key sequence can be a prefix of a command's shortcut
     if handled {
         ui.input_key_sequence.keys = vec![];
+        ui.count_prefix = None;
     } else {
+        if !keymap_config.has_matched_prefix(&key_sequence, scope) {
+            // an unmapped key sequence cancels the count prefix
+            ui.count_prefix = None;
+        }
         ui.input_key_sequence = key_sequence;
     }
     Ok(())
 }
 
+/// Handle the steps of a macro in order, stopping at the first failed step
+fn handle_macro_steps(
+    steps: &[MacroStep],
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<()> {
+    for step in steps {
+        match step {
+            MacroStep::Command { command } => {
+                handle_command_or_action(
+                    CommandOrAction::Command(*command),
+                    client_pub,
+                    state,
+                    ui,
+                )?;
+            }
+            MacroStep::Action { action, target } => {
+                handle_command_or_action(
+                    CommandOrAction::Action(*action, *target),
+                    client_pub,
+                    state,
+                    ui,
+                )?;
+            }
+            MacroStep::Play { play } => {
+                let playback = playback_from_uri(play)?;
+                client_pub.send(ClientRequest::Player(PlayerRequest::StartPlayback(
+                    playback, None,
+                )))?;
+            }
+            MacroStep::Theme { theme } => {
+                ui.theme = config::get_config()
+                    .theme_config
+                    .find_theme(theme)
+                    .with_context(|| format!("theme {theme} not found"))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Handle a command or action as if its mapped key sequence was pressed
+fn handle_command_or_action(
+    found: CommandOrAction,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let handled = match (&ui.popup, found) {
+        (None | Some(PopupState::Search { .. }), _) => {
+            page::handle_command_or_action_for_page(found, client_pub, state, ui)?
+        }
+        (Some(_), CommandOrAction::Command(command)) => {
+            popup::handle_command_for_popup(command, client_pub, state, ui)?
+        }
+        (Some(_), CommandOrAction::Action(..)) => false,
+    };
+
+    // if the command or action is not handled, let the global handler handle it
+    if handled {
+        return Ok(true);
+    }
+    match found {
+        CommandOrAction::Action(action, target) => {
+            handle_global_action(action, target, client_pub, state, ui)
+        }
+        CommandOrAction::Command(command) => handle_global_command(command, client_pub, state, ui),
+    }
+}
+
+/// Construct a playback from a Spotify URI, which can be a context (playlist, album, artist, show, audiobook) URI
+/// or a playable item (track, episode) URI
+fn playback_from_uri(uri: &str) -> Result<Playback> {
+    let uri = parse_uri(uri);
+    let playback = match uri.split(':').nth(1) {
+        Some("playlist") => Playback::Context(
+            ContextId::Playlist(PlaylistId::from_uri(&uri)?.into_static()),
+            None,
+        ),
+        Some("album") => Playback::Context(
+            ContextId::Album(AlbumId::from_uri(&uri)?.into_static()),
+            None,
+        ),
+        Some("artist") => Playback::Context(
+            ContextId::Artist(ArtistId::from_uri(&uri)?.into_static()),
+            None,
+        ),
+        Some("show") => {
+            Playback::Context(ContextId::Show(ShowId::from_uri(&uri)?.into_static()), None)
+        }
+        Some("audiobook") => {
+            Playback::Context(ContextId::Audiobook(AudiobookId::from_uri(&uri)?), None)
+        }
+        Some("track") => Playback::URIs(vec![TrackId::from_uri(&uri)?.into_static().into()], None),
+        Some("episode") => {
+            Playback::URIs(vec![EpisodeId::from_uri(&uri)?.into_static().into()], None)
+        }
+        _ => anyhow::bail!("unsupported Spotify URI: {uri}"),
+    };
+    Ok(playback)
+}
+
 pub fn handle_action_in_context(
     action: Action,
     context: ActionContext,
@@ -184,9 +441,10 @@ pub fn handle_action_in_context(
             Action::AddToPlaylist => {
                 client_pub.send(ClientRequest::GetUserPlaylists)?;
                 ui.popup = Some(PopupState::UserPlaylistList(
-                    PlaylistPopupAction::AddTrack {
+                    PlaylistPopupAction::AddTracks {
                         folder_id: 0,
-                        track_id: track.id,
+                        track_ids: vec![track.id],
+                        marked: false,
                     },
                     ListState::default(),
                 ));
@@ -245,9 +503,9 @@ pub fn handle_action_in_context(
                     ..
                 } = ui.current_page()
                 {
-                    client_pub.send(ClientRequest::DeleteTrackFromPlaylist(
+                    client_pub.send(ClientRequest::DeleteTracksFromPlaylist(
                         playlist_id.clone_static(),
-                        track.id,
+                        vec![track.id],
                     ))?;
                 }
                 ui.popup = None;
@@ -255,6 +513,62 @@ pub fn handle_action_in_context(
             }
             _ => Ok(false),
         },
+        ActionContext::Tracks(tracks) => {
+            let n_tracks = tracks.len();
+            let track_ids = tracks.iter().map(|t| t.id.clone()).collect::<Vec<_>>();
+            // bulk actions (except adding to a playlist, which requires choosing the playlist first)
+            // are sent after a confirmation
+            let (message, request) = match action {
+                Action::AddToPlaylist => {
+                    client_pub.send(ClientRequest::GetUserPlaylists)?;
+                    ui.popup = Some(PopupState::UserPlaylistList(
+                        PlaylistPopupAction::AddTracks {
+                            folder_id: 0,
+                            track_ids,
+                            marked: true,
+                        },
+                        ListState::default(),
+                    ));
+                    return Ok(true);
+                }
+                Action::AddToQueue => (
+                    format!("Add {n_tracks} tracks to queue?"),
+                    ClientRequest::AddPlayablesToQueue(
+                        track_ids.into_iter().map(Into::into).collect(),
+                    ),
+                ),
+                Action::AddToLiked => (
+                    format!("Add {n_tracks} tracks to liked tracks?"),
+                    ClientRequest::AddTracksToLiked(tracks),
+                ),
+                Action::DeleteFromLiked => (
+                    format!("Delete {n_tracks} tracks from liked tracks?"),
+                    ClientRequest::DeleteTracksFromLiked(track_ids),
+                ),
+                Action::DeleteFromPlaylist => {
+                    let PageState::Context {
+                        id: Some(ContextId::Playlist(playlist_id)),
+                        ..
+                    } = ui.current_page()
+                    else {
+                        return Ok(false);
+                    };
+                    (
+                        format!("Delete {n_tracks} tracks from the playlist?"),
+                        ClientRequest::DeleteTracksFromPlaylist(
+                            playlist_id.clone_static(),
+                            track_ids,
+                        ),
+                    )
+                }
+                _ => return Ok(false),
+            };
+            ui.popup = Some(PopupState::Confirm {
+                message,
+                request: Box::new(request),
+            });
+            Ok(true)
+        }
         ActionContext::Album(album) => match action {
             Action::GoToArtist => {
                 handle_go_to_artist(album.artists, ui);
@@ -324,6 +638,22 @@ pub fn handle_action_in_context(
                 })?;
                 Ok(true)
             }
+            Action::PlayDiscography => {
+                client_pub.send(ClientRequest::PlayArtistDiscography(artist.id))?;
+                ui.popup = None;
+                Ok(true)
+            }
+            Action::AddToPlaylist => {
+                client_pub.send(ClientRequest::GetUserPlaylists)?;
+                ui.popup = Some(PopupState::UserPlaylistList(
+                    PlaylistPopupAction::AddArtistDiscography {
+                        folder_id: 0,
+                        artist,
+                    },
+                    ListState::default(),
+                ));
+                Ok(true)
+            }
             _ => Ok(false),
         },
         ActionContext::Playlist(playlist) => match action {
@@ -377,6 +707,28 @@ pub fn handle_action_in_context(
             }
             _ => Ok(false),
         },
+        ActionContext::Audiobook(audiobook) => match action {
+            Action::CopyLink => {
+                let audiobook_url =
+                    format!("https://open.spotify.com/audiobook/{}", audiobook.id.id());
+                execute_copy_command(audiobook_url)?;
+                ui.popup = None;
+                Ok(true)
+            }
+            Action::AddToLibrary => {
+                client_pub.send(ClientRequest::AddToLibrary(Item::Audiobook(audiobook)))?;
+                ui.popup = None;
+                Ok(true)
+            }
+            Action::DeleteFromLibrary => {
+                client_pub.send(ClientRequest::DeleteFromLibrary(ItemId::Audiobook(
+                    audiobook.id,
+                )))?;
+                ui.popup = None;
+                Ok(true)
+            }
+            _ => Ok(false),
+        },
         ActionContext::Episode(episode) => match action {
             Action::GoToShow => {
                 if let Some(show) = episode.show {
@@ -403,6 +755,26 @@ pub fn handle_action_in_context(
                 ui.popup = None;
                 Ok(true)
             }
+            Action::AddToLibrary => {
+                client_pub.send(ClientRequest::AddToLibrary(Item::Episode(episode)))?;
+                ui.popup = None;
+                Ok(true)
+            }
+            Action::DeleteFromLibrary => {
+                client_pub.send(ClientRequest::DeleteFromLibrary(ItemId::Episode(
+                    episode.id,
+                )))?;
+                ui.popup = None;
+                Ok(true)
+            }
+            Action::MarkAsPlayed | Action::MarkAsUnplayed => {
+                client_pub.send(ClientRequest::SetEpisodePlayed {
+                    episode_id: episode.id,
+                    played: action == Action::MarkAsPlayed,
+                })?;
+                ui.popup = None;
+                Ok(true)
+            }
             Action::AddToPlaylist => {
                 client_pub.send(ClientRequest::GetUserPlaylists)?;
                 ui.popup = Some(PopupState::UserPlaylistList(
@@ -514,6 +886,17 @@ fn handle_global_action(
     Ok(false)
 }
 
+/// Get the pending count prefix of the handled command, defaulting to 1
+fn count_prefix(ui: &UIStateGuard) -> usize {
+    ui.count_prefix.unwrap_or(1)
+}
+
+/// Get the duration to seek by, which is the configured seek duration multiplied by the count prefix
+fn seek_duration(ui: &UIStateGuard) -> chrono::Duration {
+    let duration = config::get_config().app_config.seek_duration_secs;
+    chrono::Duration::try_seconds(i64::from(duration) * count_prefix(ui) as i64).unwrap()
+}
+
 /// Handle a global command that is not specific to any page/popup
 fn handle_global_command(
     command: Command,
@@ -549,36 +932,62 @@ fn handle_global_command(
         Command::VolumeChange { offset } => {
             if let Some(ref playback) = state.player.read().buffered_playback {
                 if let Some(volume) = playback.volume {
-                    let volume = std::cmp::min(volume as i32 + offset, 100_i32);
+                    let offset = offset.saturating_mul(count_prefix(ui) as i32);
+                    let volume = (volume as i32).saturating_add(offset).clamp(0, 100);
                     client_pub.send(ClientRequest::Player(PlayerRequest::Volume(volume as u8)))?;
                 }
             }
         }
+        Command::SetVolume { volume } => {
+            client_pub.send(ClientRequest::Player(PlayerRequest::Volume(
+                volume.min(100),
+            )))?;
+        }
         Command::Mute => {
             client_pub.send(ClientRequest::Player(PlayerRequest::ToggleMute))?;
         }
         Command::SeekForward => {
             if let Some(progress) = state.player.read().playback_progress() {
-                let duration = config::get_config().app_config.seek_duration_secs;
+                let duration = seek_duration(ui);
                 client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(
-                    progress + chrono::Duration::try_seconds(i64::from(duration)).unwrap(),
+                    progress + duration,
                 )))?;
             }
         }
         Command::SeekBackward => {
             if let Some(progress) = state.player.read().playback_progress() {
-                let duration = config::get_config().app_config.seek_duration_secs;
+                let duration = seek_duration(ui);
                 client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(
-                    std::cmp::max(
-                        chrono::Duration::zero(),
-                        progress - chrono::Duration::try_seconds(i64::from(duration)).unwrap(),
-                    ),
+                    std::cmp::max(chrono::Duration::zero(), progress - duration),
                 )))?;
             }
         }
+        Command::SeekTo { position_secs } => {
+            client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(
+                chrono::Duration::try_seconds(i64::from(position_secs)).unwrap(),
+            )))?;
+        }
         Command::OpenCommandHelp => {
             ui.new_page(PageState::CommandHelp { scroll_offset: 0 });
         }
+        Command::ToggleMiniMode => {
+            ui.is_mini_mode = !ui.is_mini_mode;
+        }
+        Command::OpenCommandLine => {
+            client_pub.send(ClientRequest::GetDevices)?;
+            ui.popup = Some(PopupState::CommandLine {
+                input: LineInput::default(),
+                history_index: None,
+                completions: None,
+            });
+        }
+        Command::OpenCommandPalette => {
+            client_pub.send(ClientRequest::GetDevices)?;
+            ui.popup = Some(PopupState::CommandPalette(
+                LineInput::default(),
+                ListState::default(),
+            ));
+        }
         Command::RefreshPlayback => {
             client_pub.send(ClientRequest::GetCurrentPlayback)?;
         }
@@ -629,6 +1038,17 @@ fn handle_global_command(
             client_pub.send(ClientRequest::GetUserSavedAlbums)?;
             ui.popup = Some(PopupState::UserSavedAlbumList(ListState::default()));
         }
+        Command::BrowseUserSavedShows => {
+            client_pub.send(ClientRequest::GetUserSavedShows)?;
+            ui.popup = Some(PopupState::UserSavedShowList(ListState::default()));
+        }
+        Command::BrowseSmartPlaylists => {
+            ui.popup = Some(PopupState::SmartPlaylistList(ListState::default()));
+        }
+        Command::BrowseUserSavedAudiobooks => {
+            client_pub.send(ClientRequest::GetUserSavedAudiobooks)?;
+            ui.popup = Some(PopupState::UserSavedAudiobookList(ListState::default()));
+        }
         Command::TopTrackPage => {
             ui.new_page(PageState::Context {
                 id: None,
@@ -659,6 +1079,14 @@ fn handle_global_command(
             });
             client_pub.send(ClientRequest::GetUserSavedTracks)?;
         }
+        Command::SavedEpisodePage => {
+            ui.new_page(PageState::Context {
+                id: None,
+                context_page_type: ContextPageType::Browsing(ContextId::SavedEpisodes),
+                state: None,
+            });
+            client_pub.send(ClientRequest::GetUserSavedEpisodes)?;
+        }
         Command::LibraryPage => {
             ui.new_page(PageState::Library {
                 state: LibraryPageUIState::new(),
@@ -680,11 +1108,31 @@ fn handle_global_command(
             client_pub.send(ClientRequest::GetBrowseCategories)?;
         }
         Command::PreviousPage => {
-            if ui.history.len() > 1 {
-                ui.history.pop();
+            if ui.current_tab_mut().back() {
+                ui.popup = None;
+            }
+        }
+        Command::NextPage => {
+            if ui.current_tab_mut().forward() {
                 ui.popup = None;
             }
         }
+        Command::OpenInNewTab => {
+            // windows of items with a page (album, artist, playlist, show, audiobook)
+            // open the selected item's page in a new tab, so other items have no page to open
+            return Ok(false);
+        }
+        Command::NextTab => {
+            let id = (ui.current_tab + 1) % ui.tabs.len();
+            ui.switch_tab(id);
+        }
+        Command::PreviousTab => {
+            let id = (ui.current_tab + ui.tabs.len() - 1) % ui.tabs.len();
+            ui.switch_tab(id);
+        }
+        Command::CloseTab => {
+            ui.close_current_tab();
+        }
         Command::OpenSpotifyLinkFromClipboard => {
             let content = get_clipboard_content().context("get clipboard's content")?;
             let re = regex::Regex::new(
@@ -791,6 +1239,9 @@ fn handle_global_command(
                 current_field: PlaylistCreateCurrentField::Name,
             });
         }
+        Command::Undo => {
+            client_pub.send(ClientRequest::Undo)?;
+        }
         Command::JumpToCurrentTrackInContext => {
             let track_id = match state.player.read().currently_playing() {
                 Some(rspotify::model::PlayableItem::Track(track)) => {
@@ -2,6 +2,11 @@ use anyhow::Context as _;
 use command::CommandOrAction;
 
 use super::*;
+use crate::{
+    command_line::FolderCommand,
+    playlist_folders,
+    state::{store_data_into_file_cache, FileCacheKey},
+};
 
 pub fn handle_key_sequence_for_page(
     key_sequence: &KeySequence,
@@ -18,9 +23,27 @@ pub fn handle_key_sequence_for_page(
 
     match config::get_config()
         .keymap_config
-        .find_command_or_action_from_key_sequence(key_sequence)
+        .find_command_or_action_from_key_sequence(key_sequence, ui.keymap_scope())
     {
-        Some(CommandOrAction::Command(command)) => match page_type {
+        Some(found) => handle_command_or_action_for_page(found, client_pub, state, ui),
+        None => Ok(false),
+    }
+}
+
+/// Handle a command or action for the current page
+pub fn handle_command_or_action_for_page(
+    found: CommandOrAction,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let page_type = ui.current_page().page_type();
+    if page_type == PageType::Search {
+        return handle_command_or_action_for_search_page(found, client_pub, state, ui);
+    }
+
+    match found {
+        CommandOrAction::Command(command) => match page_type {
             PageType::Search => anyhow::bail!("page search type should already be handled!"),
             PageType::Library => handle_command_for_library_page(command, client_pub, ui, state),
             PageType::Context => handle_command_for_context_page(command, client_pub, ui, state),
@@ -30,7 +53,7 @@ pub fn handle_key_sequence_for_page(
             PageType::Queue => Ok(handle_command_for_queue_page(command, ui)),
             PageType::CommandHelp => Ok(handle_command_for_command_help_page(command, ui)),
         },
-        Some(CommandOrAction::Action(action, ActionTarget::SelectedItem)) => match page_type {
+        CommandOrAction::Action(action, ActionTarget::SelectedItem) => match page_type {
             PageType::Search => anyhow::bail!("page search type should already be handled!"),
             PageType::Library => handle_action_for_library_page(action, client_pub, ui, state),
             PageType::Context => {
@@ -39,7 +62,7 @@ pub fn handle_key_sequence_for_page(
             PageType::Browse => handle_action_for_browse_page(action, client_pub, ui, state),
             _ => Ok(false),
         },
-        _ => Ok(false),
+        CommandOrAction::Action(..) => Ok(false),
     }
 }
 
@@ -79,6 +102,13 @@ fn handle_action_for_library_page(
             ui,
             client_pub,
         ),
+        LibraryFocusState::SavedShows => window::handle_action_for_selected_item(
+            action,
+            &ui.search_filtered_items(&data.user_data.saved_shows),
+            &data,
+            ui,
+            client_pub,
+        ),
     }
 }
 
@@ -88,6 +118,28 @@ fn handle_command_for_library_page(
     ui: &mut UIStateGuard,
     state: &SharedState,
 ) -> Result<bool> {
+    // folder commands with arguments are completed in the command line
+    let folder_command_line = match command {
+        Command::NewPlaylistFolder => Some("folder new "),
+        Command::RenamePlaylistFolder => Some("folder rename "),
+        Command::MoveToPlaylistFolder => Some("folder move "),
+        Command::DeletePlaylistFolder => {
+            handle_folder_command(FolderCommand::Delete, state, ui)?;
+            return Ok(true);
+        }
+        _ => None,
+    };
+    if let Some(text) = folder_command_line {
+        let mut input = LineInput::default();
+        input.set_text(text);
+        ui.popup = Some(PopupState::CommandLine {
+            input,
+            history_index: None,
+            completions: None,
+        });
+        return Ok(true);
+    }
+
     if command == Command::Search {
         ui.new_search_popup();
         Ok(true)
@@ -122,8 +174,103 @@ fn handle_command_for_library_page(
                     ui,
                 ))
             }
+            LibraryFocusState::SavedShows => Ok(window::handle_command_for_show_list_window(
+                command,
+                &ui.search_filtered_items(&data.user_data.saved_shows),
+                &data,
+                ui,
+            )),
+        }
+    }
+}
+
+/// Handle a command editing the local playlist folders of the library page
+pub fn handle_folder_command(
+    command: FolderCommand,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<()> {
+    let PageState::Library { state: page_state } = ui.current_page() else {
+        anyhow::bail!("playlist folders can only be edited in the library page");
+    };
+    let (focus_state, folder_id) = (page_state.focus, page_state.playlist_folder_id);
+
+    let mut data = state.data.write();
+    let current_folder_uri = data
+        .user_data
+        .playlist_folder_uri(folder_id)
+        .map(ToString::to_string);
+    let selected_item = match ui.current_page_mut().selected() {
+        Some(id) if focus_state == LibraryFocusState::Playlists => ui
+            .search_filtered_items(&data.user_data.folder_playlists_items(folder_id))
+            .get(id)
+            .map(|item| (**item).clone()),
+        _ => None,
+    };
+    let selected_folder = || match &selected_item {
+        Some(PlaylistFolderItem::Folder(f)) if !f.is_parent_link() => {
+            if !f.is_local() {
+                anyhow::bail!("imported folder {} cannot be edited", f.name);
+            }
+            Ok(f.uri.clone())
+        }
+        _ => anyhow::bail!("no folder selected"),
+    };
+
+    let nodes = &mut data.user_data.local_playlist_folders;
+    match command {
+        FolderCommand::New(name) => {
+            playlist_folders::create_local_folder(nodes, current_folder_uri.as_deref(), name)?;
+        }
+        FolderCommand::Rename(name) => {
+            playlist_folders::rename_local_folder(nodes, &selected_folder()?, name)?;
+        }
+        FolderCommand::Delete => {
+            playlist_folders::delete_local_folder(nodes, &selected_folder()?)?;
+        }
+        FolderCommand::Move(path) => {
+            let folder_uri = playlist_folders::find_local_folder_by_path(nodes, &path)?;
+            match &selected_item {
+                Some(PlaylistFolderItem::Playlist(p)) => {
+                    playlist_folders::move_playlist(nodes, p, folder_uri.as_deref())?;
+                }
+                Some(PlaylistFolderItem::Folder(_)) => {
+                    playlist_folders::move_local_folder(
+                        nodes,
+                        &selected_folder()?,
+                        folder_uri.as_deref(),
+                    )?;
+                }
+                None => anyhow::bail!("no playlist or folder selected"),
+            }
         }
     }
+
+    let cache_folder = &config::get_config().cache_folder;
+    store_data_into_file_cache(
+        FileCacheKey::LocalPlaylistFolders,
+        cache_folder,
+        &data.user_data.local_playlist_folders,
+    )
+    .context("store local playlist folders into the cache folder")?;
+    data.user_data.restructurize_playlists();
+    store_data_into_file_cache(
+        FileCacheKey::Playlists,
+        cache_folder,
+        &data.user_data.playlists,
+    )
+    .context("store user's playlists into the cache folder")?;
+
+    // folder ids are re-assigned after restructurizing the playlists,
+    // so look up the library's current folder by its URI
+    let folder_id = current_folder_uri
+        .and_then(|uri| data.user_data.playlist_folder_id(&uri))
+        .unwrap_or(0);
+    if let PageState::Library { state: page_state } = ui.current_page_mut() {
+        page_state.playlist_folder_id = folder_id;
+        page_state.playlist_list.select(Some(0));
+    }
+    Ok(())
 }
 
 fn handle_key_sequence_for_search_page(
@@ -132,6 +279,7 @@ fn handle_key_sequence_for_search_page(
     state: &SharedState,
     ui: &mut UIStateGuard,
 ) -> Result<bool> {
+    let scope = ui.keymap_scope();
     let (focus_state, current_query, line_input) = match ui.current_page_mut() {
         PageState::Search {
             state,
@@ -144,15 +292,16 @@ fn handle_key_sequence_for_search_page(
     // handle user's input
     if let SearchFocusState::Input = focus_state {
         if key_sequence.keys.len() == 1 {
-            return match &key_sequence.keys[0] {
-                Key::None(crossterm::event::KeyCode::Enter) => {
+            let key = &key_sequence.keys[0];
+            return match key.plain_code() {
+                Some(crossterm::event::KeyCode::Enter) => {
                     if !line_input.is_empty() {
                         *current_query = line_input.get_text();
                         client_pub.send(ClientRequest::Search(line_input.get_text()))?;
                     }
                     Ok(true)
                 }
-                k => match line_input.input(k) {
+                _ => match line_input.input(key) {
                     None => Ok(false),
                     _ => Ok(true),
                 },
@@ -160,18 +309,36 @@ fn handle_key_sequence_for_search_page(
         }
     }
 
-    let Some(found_keymap) = config::get_config()
+    match config::get_config()
         .keymap_config
-        .find_command_or_action_from_key_sequence(key_sequence)
-    else {
-        return Ok(false);
+        .find_command_or_action_from_key_sequence(key_sequence, scope)
+    {
+        Some(found) => handle_command_or_action_for_search_page(found, client_pub, state, ui),
+        None => Ok(false),
+    }
+}
+
+fn handle_command_or_action_for_search_page(
+    found_keymap: CommandOrAction,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let (focus_state, current_query) = match ui.current_page() {
+        PageState::Search {
+            state,
+            current_query,
+            ..
+        } => (state.focus, current_query.clone()),
+        _ => anyhow::bail!("expect a search page"),
     };
 
     let data = state.data.read();
-    let search_results = data.caches.search.get(current_query);
+    let search_results = data.caches.search.get(&current_query);
 
     match focus_state {
-        SearchFocusState::Input => anyhow::bail!("user's search input should be handled before"),
+        // commands and actions are not applicable to the search input window
+        SearchFocusState::Input => Ok(false),
         SearchFocusState::Tracks => {
             let tracks = search_results
                 .map(|s| s.tracks.iter().collect::<Vec<_>>())
@@ -284,6 +451,32 @@ fn handle_key_sequence_for_search_page(
                 CommandOrAction::Action(..) => Ok(false),
             }
         }
+        SearchFocusState::Audiobooks => {
+            let audiobooks = search_results
+                .map(|s| s.audiobooks.iter().collect::<Vec<_>>())
+                .unwrap_or_default();
+
+            match found_keymap {
+                CommandOrAction::Command(command) => {
+                    Ok(window::handle_command_for_audiobook_list_window(
+                        command,
+                        &audiobooks,
+                        &data,
+                        ui,
+                    ))
+                }
+                CommandOrAction::Action(action, ActionTarget::SelectedItem) => {
+                    window::handle_action_for_selected_item(
+                        action,
+                        &audiobooks,
+                        &data,
+                        ui,
+                        client_pub,
+                    )
+                }
+                CommandOrAction::Action(..) => Ok(false),
+            }
+        }
     }
 }
 
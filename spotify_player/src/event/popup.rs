@@ -1,6 +1,13 @@
 use super::*;
-use crate::command::construct_artist_actions;
+use crate::{
+    command::{construct_artist_actions, construct_command_palette_items, CommandPaletteItem},
+    command_line::{self, LineCommand},
+    playlist_folders, smart_playlists,
+    state::filter_items_by_query,
+    ui::single_line_input::InputEffect,
+};
 use anyhow::Context;
+use crossterm::event::KeyCode;
 
 pub fn handle_key_sequence_for_popup(
     key_sequence: &KeySequence,
@@ -18,6 +25,20 @@ pub fn handle_key_sequence_for_popup(
         PopupState::PlaylistCreate { .. } => {
             return handle_key_sequence_for_create_playlist_popup(key_sequence, client_pub, ui);
         }
+        PopupState::CommandLine { .. } => {
+            return handle_key_sequence_for_command_line_popup(key_sequence, client_pub, state, ui);
+        }
+        PopupState::Confirm { .. } => {
+            return handle_key_sequence_for_confirm_popup(key_sequence, client_pub, ui);
+        }
+        PopupState::CommandPalette(..) => {
+            return handle_key_sequence_for_command_palette_popup(
+                key_sequence,
+                client_pub,
+                state,
+                ui,
+            );
+        }
         PopupState::ActionList(item, ..) => {
             return handle_key_sequence_for_action_list_popup(
                 item.n_actions(),
@@ -30,20 +51,43 @@ pub fn handle_key_sequence_for_popup(
         _ => {}
     }
 
-    let Some(command) = config::get_config()
+    match config::get_config()
         .keymap_config
-        .find_command_from_key_sequence(key_sequence)
-    else {
-        return Ok(false);
-    };
+        .find_command_from_key_sequence(key_sequence, ui.keymap_scope())
+    {
+        Some(command) => handle_command_for_popup(command, client_pub, state, ui),
+        None => Ok(false),
+    }
+}
+
+/// Handle a command for the current popup
+pub fn handle_command_for_popup(
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let popup = ui.popup.as_ref().context("empty popup")?;
 
     match popup {
-        PopupState::Search { .. } => anyhow::bail!("search popup should be handled before"),
-        PopupState::PlaylistCreate { .. } => {
-            anyhow::bail!("create playlist popup should be handled before")
+        // commands not handled by the search popup are moved to the current page's event handler
+        PopupState::Search { .. } => page::handle_command_or_action_for_page(
+            CommandOrAction::Command(command),
+            client_pub,
+            state,
+            ui,
+        ),
+        // create playlist popup only handles user's inputs
+        PopupState::PlaylistCreate { .. } => Ok(false),
+        PopupState::CommandPalette(..) => {
+            handle_command_for_command_palette_popup(command, client_pub, state, ui)
         }
-        PopupState::ActionList(..) => {
-            anyhow::bail!("action list popup should be handled before")
+        PopupState::CommandLine { .. } => {
+            handle_command_for_command_line_popup(command, client_pub, state, ui)
+        }
+        PopupState::Confirm { .. } => handle_command_for_confirm_popup(command, client_pub, ui),
+        PopupState::ActionList(item, ..) => {
+            handle_command_for_action_list_popup(item.n_actions(), command, client_pub, state, ui)
         }
         PopupState::ArtistList(_, artists, _) => {
             let n_items = artists.len();
@@ -125,11 +169,13 @@ pub fn handle_key_sequence_for_popup(
                     },
                 )
             }
-            PlaylistPopupAction::AddTrack {
+            PlaylistPopupAction::AddTracks {
                 folder_id,
-                track_id,
+                track_ids,
+                marked,
             } => {
-                let track_id = track_id.clone();
+                let track_ids = track_ids.clone();
+                let marked = *marked;
                 let data = state.data.read();
                 let items = data.user_data.modifiable_playlist_items(Some(*folder_id));
 
@@ -141,18 +187,33 @@ pub fn handle_key_sequence_for_popup(
                     |ui: &mut UIStateGuard, id: usize| -> Result<()> {
                         ui.popup = match items.get(id).expect("invalid index") {
                             PlaylistFolderItem::Folder(f) => Some(PopupState::UserPlaylistList(
-                                PlaylistPopupAction::AddTrack {
+                                PlaylistPopupAction::AddTracks {
                                     folder_id: f.target_id,
-                                    track_id,
+                                    track_ids,
+                                    marked,
                                 },
                                 ListState::default(),
                             )),
                             PlaylistFolderItem::Playlist(p) => {
-                                client_pub.send(ClientRequest::AddPlayableToPlaylist(
+                                let n_tracks = track_ids.len();
+                                let request = ClientRequest::AddPlayablesToPlaylist(
                                     p.id.clone(),
-                                    track_id.into(),
-                                ))?;
-                                None
+                                    track_ids.into_iter().map(Into::into).collect(),
+                                );
+                                // adding marked tracks requires a confirmation, after which
+                                // the tracks are unmarked
+                                if marked {
+                                    Some(PopupState::Confirm {
+                                        message: format!(
+                                            "Add {n_tracks} tracks to playlist {}?",
+                                            p.name
+                                        ),
+                                        request: Box::new(request),
+                                    })
+                                } else {
+                                    client_pub.send(request)?;
+                                    None
+                                }
                             }
                         };
                         Ok(())
@@ -185,9 +246,9 @@ pub fn handle_key_sequence_for_popup(
                                 ListState::default(),
                             )),
                             PlaylistFolderItem::Playlist(p) => {
-                                client_pub.send(ClientRequest::AddPlayableToPlaylist(
+                                client_pub.send(ClientRequest::AddPlayablesToPlaylist(
                                     p.id.clone(),
-                                    episode_id.into(),
+                                    vec![episode_id.into()],
                                 ))?;
                                 None
                             }
@@ -199,6 +260,44 @@ pub fn handle_key_sequence_for_popup(
                     },
                 )
             }
+            PlaylistPopupAction::AddArtistDiscography { folder_id, artist } => {
+                let artist = artist.clone();
+                let data = state.data.read();
+                let items = data.user_data.modifiable_playlist_items(Some(*folder_id));
+
+                handle_command_for_list_popup(
+                    command,
+                    ui,
+                    items.len(),
+                    |_, _| {},
+                    |ui: &mut UIStateGuard, id: usize| -> Result<()> {
+                        ui.popup = match items.get(id).expect("invalid index") {
+                            PlaylistFolderItem::Folder(f) => Some(PopupState::UserPlaylistList(
+                                PlaylistPopupAction::AddArtistDiscography {
+                                    folder_id: f.target_id,
+                                    artist,
+                                },
+                                ListState::default(),
+                            )),
+                            // adding an artist's discography requires a confirmation
+                            PlaylistFolderItem::Playlist(p) => Some(PopupState::Confirm {
+                                message: format!(
+                                    "Add the discography of {} to playlist {}?",
+                                    artist.name, p.name
+                                ),
+                                request: Box::new(ClientRequest::AddArtistDiscographyToPlaylist {
+                                    artist_id: artist.id,
+                                    playlist_id: p.id.clone(),
+                                }),
+                            }),
+                        };
+                        Ok(())
+                    },
+                    |ui: &mut UIStateGuard| {
+                        ui.popup = None;
+                    },
+                )
+            }
         },
         PopupState::UserFollowedArtistList(_) => {
             let artist_uris = state
@@ -234,6 +333,84 @@ pub fn handle_key_sequence_for_popup(
                 rspotify::model::Type::Album,
             )
         }
+        PopupState::UserSavedShowList(_) => {
+            let show_uris = state
+                .data
+                .read()
+                .user_data
+                .saved_shows
+                .iter()
+                .map(|s| s.id.uri())
+                .collect::<Vec<_>>();
+
+            handle_command_for_context_browsing_list_popup(
+                command,
+                ui,
+                &show_uris,
+                rspotify::model::Type::Show,
+            )
+        }
+        PopupState::SmartPlaylistList(_) => {
+            let names = config::get_config()
+                .smart_playlist_config
+                .smart_playlists
+                .iter()
+                .map(|p| p.name.clone())
+                .collect::<Vec<_>>();
+
+            handle_command_for_list_popup(
+                command,
+                ui,
+                names.len(),
+                |_, _| {},
+                |ui: &mut UIStateGuard, id: usize| -> Result<()> {
+                    ui.new_page(PageState::Context {
+                        id: None,
+                        context_page_type: ContextPageType::Browsing(ContextId::Tracks(
+                            smart_playlists::tracks_id(&names[id]),
+                        )),
+                        state: None,
+                    });
+                    client_pub.send(ClientRequest::GetSmartPlaylist(names[id].clone()))?;
+                    Ok(())
+                },
+                |ui: &mut UIStateGuard| {
+                    ui.popup = None;
+                },
+            )
+        }
+        PopupState::UserSavedAudiobookList(_) => {
+            let audiobook_ids = state
+                .data
+                .read()
+                .user_data
+                .saved_audiobooks
+                .iter()
+                .map(|a| a.id.clone())
+                .collect::<Vec<_>>();
+
+            // audiobooks are not supported by `rspotify::model::Type`,
+            // so `handle_command_for_context_browsing_list_popup` can't be used
+            handle_command_for_list_popup(
+                command,
+                ui,
+                audiobook_ids.len(),
+                |_, _| {},
+                |ui: &mut UIStateGuard, id: usize| -> Result<()> {
+                    ui.new_page(PageState::Context {
+                        id: None,
+                        context_page_type: ContextPageType::Browsing(ContextId::Audiobook(
+                            audiobook_ids[id].clone(),
+                        )),
+                        state: None,
+                    });
+                    Ok(())
+                },
+                |ui: &mut UIStateGuard| {
+                    ui.popup = None;
+                },
+            )
+        }
         PopupState::ThemeList(themes, _) => {
             let n_items = themes.len();
 
@@ -285,6 +462,47 @@ pub fn handle_key_sequence_for_popup(
     }
 }
 
+fn handle_key_sequence_for_confirm_popup(
+    key_sequence: &KeySequence,
+    client_pub: &flume::Sender<ClientRequest>,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    // handle answering the confirmation by pressing `y` or `n`
+    let command = match key_sequence.keys.first().copied().and_then(Key::plain_code) {
+        Some(crossterm::event::KeyCode::Char('y')) => Command::ChooseSelected,
+        Some(crossterm::event::KeyCode::Char('n')) => Command::ClosePopup,
+        _ => match config::get_config()
+            .keymap_config
+            .find_command_from_key_sequence(key_sequence, ui.keymap_scope())
+        {
+            Some(command) => command,
+            None => return Ok(false),
+        },
+    };
+    handle_command_for_confirm_popup(command, client_pub, ui)
+}
+
+fn handle_command_for_confirm_popup(
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    match command {
+        Command::ChooseSelected => {
+            if let Some(PopupState::Confirm { request, .. }) = ui.popup.take() {
+                client_pub.send(*request)?;
+                // marked tracks are unmarked after a bulk action is confirmed
+                ui.track_marks.clear();
+            }
+        }
+        Command::ClosePopup => {
+            ui.popup = None;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
 fn handle_key_sequence_for_create_playlist_popup(
     key_sequence: &KeySequence,
     client_pub: &flume::Sender<ClientRequest>,
@@ -299,8 +517,9 @@ fn handle_key_sequence_for_create_playlist_popup(
         return Ok(false);
     };
     if key_sequence.keys.len() == 1 {
-        match &key_sequence.keys[0] {
-            Key::None(crossterm::event::KeyCode::Enter) => {
+        let key = &key_sequence.keys[0];
+        match key.plain_code() {
+            Some(crossterm::event::KeyCode::Enter) => {
                 client_pub.send(ClientRequest::CreatePlaylist {
                     playlist_name: name.get_text(),
                     public: false,
@@ -310,19 +529,19 @@ fn handle_key_sequence_for_create_playlist_popup(
                 ui.popup = None;
                 return Ok(true);
             }
-            Key::None(crossterm::event::KeyCode::Tab | crossterm::event::KeyCode::BackTab) => {
+            Some(crossterm::event::KeyCode::Tab | crossterm::event::KeyCode::BackTab) => {
                 *current_field = match &current_field {
                     PlaylistCreateCurrentField::Name => PlaylistCreateCurrentField::Desc,
                     PlaylistCreateCurrentField::Desc => PlaylistCreateCurrentField::Name,
                 };
                 return Ok(true);
             }
-            k => {
+            _ => {
                 let line_input = match current_field {
                     PlaylistCreateCurrentField::Name => name,
                     PlaylistCreateCurrentField::Desc => desc,
                 };
-                if line_input.input(k).is_some() {
+                if line_input.input(key).is_some() {
                     return Ok(true);
                 }
             }
@@ -331,6 +550,345 @@ fn handle_key_sequence_for_create_playlist_popup(
     Ok(false)
 }
 
+fn handle_key_sequence_for_command_line_popup(
+    key_sequence: &KeySequence,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let history = ui.command_line_history.clone();
+    let Some(PopupState::CommandLine {
+        input,
+        history_index,
+        completions,
+    }) = &mut ui.popup
+    else {
+        return Ok(false);
+    };
+
+    if key_sequence.keys.len() == 1 {
+        match key_sequence.keys[0].plain_code() {
+            // complete the typed text, cycling through the candidates on repeated presses
+            Some(code @ (KeyCode::Tab | KeyCode::BackTab)) => {
+                let (candidates, id) = if let Some((candidates, id)) = completions.take() {
+                    let n = candidates.len();
+                    let id = if code == KeyCode::Tab {
+                        (id + 1) % n
+                    } else {
+                        (id + n - 1) % n
+                    };
+                    (candidates, id)
+                } else {
+                    let themes = config::get_config()
+                        .theme_config
+                        .themes
+                        .iter()
+                        .map(|t| t.name.clone())
+                        .collect::<Vec<_>>();
+                    let devices = state
+                        .player
+                        .read()
+                        .devices
+                        .iter()
+                        .map(|d| d.name.clone())
+                        .collect::<Vec<_>>();
+                    let folders = playlist_folders::local_folder_paths(
+                        &state.data.read().user_data.local_playlist_folders,
+                    );
+                    let candidates =
+                        command_line::complete(&input.get_text(), &themes, &devices, &folders);
+                    if candidates.is_empty() {
+                        return Ok(true);
+                    }
+                    (candidates, 0)
+                };
+                input.set_text(&candidates[id]);
+                *completions = Some((candidates, id));
+                return Ok(true);
+            }
+            // browse the command line history
+            Some(KeyCode::Up) => {
+                if !history.is_empty() {
+                    let id = history_index.map_or(history.len() - 1, |id| id.saturating_sub(1));
+                    input.set_text(&history[id]);
+                    *history_index = Some(id);
+                    *completions = None;
+                }
+                return Ok(true);
+            }
+            Some(KeyCode::Down) => {
+                if let Some(id) = *history_index {
+                    if id + 1 < history.len() {
+                        input.set_text(&history[id + 1]);
+                        *history_index = Some(id + 1);
+                    } else {
+                        input.set_text("");
+                        *history_index = None;
+                    }
+                    *completions = None;
+                }
+                return Ok(true);
+            }
+            _ => {
+                if let Some(effect) = input.input(&key_sequence.keys[0]) {
+                    if let InputEffect::TextChanged = effect {
+                        *history_index = None;
+                        *completions = None;
+                    }
+                    return Ok(true);
+                }
+            }
+        }
+    }
+
+    match config::get_config()
+        .keymap_config
+        .find_command_from_key_sequence(key_sequence, ui.keymap_scope())
+    {
+        Some(command) => handle_command_for_command_line_popup(command, client_pub, state, ui),
+        None => Ok(false),
+    }
+}
+
+fn handle_command_for_command_line_popup(
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let Some(PopupState::CommandLine { input, .. }) = &ui.popup else {
+        return Ok(false);
+    };
+
+    match command {
+        Command::ChooseSelected => {
+            let text = input.get_text().trim().to_string();
+            ui.popup = None;
+            if text.is_empty() {
+                return Ok(true);
+            }
+            if ui.command_line_history.last() != Some(&text) {
+                ui.command_line_history.push(text.clone());
+            }
+            let command = text.parse::<LineCommand>()?;
+            handle_line_command(command, client_pub, state, ui)?;
+        }
+        Command::ClosePopup => {
+            ui.popup = None;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Handle a command typed in the command line popup
+fn handle_line_command(
+    command: LineCommand,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<()> {
+    match command {
+        LineCommand::Volume { value, relative } => {
+            let volume = if relative {
+                let player = state.player.read();
+                let current = player
+                    .buffered_playback
+                    .as_ref()
+                    .and_then(|p| p.volume)
+                    .context("no playback volume")?;
+                (current as i32).saturating_add(value)
+            } else {
+                value
+            };
+            client_pub.send(ClientRequest::Player(PlayerRequest::Volume(
+                volume.clamp(0, 100) as u8,
+            )))?;
+        }
+        LineCommand::Seek { secs, relative } => {
+            let offset = chrono::Duration::try_seconds(secs).context("invalid seek position")?;
+            let position = if relative {
+                let progress = state
+                    .player
+                    .read()
+                    .playback_progress()
+                    .context("no playback")?;
+                progress
+                    .checked_add(&offset)
+                    .context("invalid seek position")?
+                    .max(chrono::Duration::zero())
+            } else {
+                offset
+            };
+            client_pub.send(ClientRequest::Player(PlayerRequest::SeekTrack(position)))?;
+        }
+        LineCommand::Play(uri) => {
+            let playback = playback_from_uri(&uri)?;
+            client_pub.send(ClientRequest::Player(PlayerRequest::StartPlayback(
+                playback, None,
+            )))?;
+        }
+        LineCommand::Theme(theme) => {
+            ui.theme = config::get_config()
+                .theme_config
+                .find_theme(&theme)
+                .with_context(|| format!("theme {theme} not found"))?;
+        }
+        LineCommand::Device(name) => {
+            let player = state.player.read();
+            let name = name.to_lowercase();
+            let device = player
+                .devices
+                .iter()
+                .find(|d| d.name.to_lowercase() == name)
+                .or_else(|| {
+                    player
+                        .devices
+                        .iter()
+                        .find(|d| d.name.to_lowercase().contains(&name))
+                })
+                .with_context(|| format!("device {name} not found"))?;
+            let is_playing = player.playback.as_ref().is_some_and(|p| p.is_playing);
+            client_pub.send(ClientRequest::Player(PlayerRequest::TransferPlayback(
+                device.id.clone(),
+                is_playing,
+            )))?;
+        }
+        LineCommand::Sleep(duration) => {
+            state.player.write().sleep_timer = match duration {
+                Some(d) => Some(
+                    std::time::Instant::now()
+                        .checked_add(d)
+                        .context("sleep duration is too large")?,
+                ),
+                None => None,
+            };
+        }
+        LineCommand::Sort(commands) => {
+            for command in commands {
+                handle_command_or_action(CommandOrAction::Command(command), client_pub, state, ui)?;
+            }
+        }
+        LineCommand::Disc(disc) => {
+            handle_command_or_action(
+                CommandOrAction::Command(Command::PlayDisc { disc }),
+                client_pub,
+                state,
+                ui,
+            )?;
+        }
+        LineCommand::Folder(command) => {
+            page::handle_folder_command(command, state, ui)?;
+        }
+    }
+    Ok(())
+}
+
+fn handle_key_sequence_for_command_palette_popup(
+    key_sequence: &KeySequence,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    // handle user's input that updates the search query
+    let Some(PopupState::CommandPalette(query, list_state)) = &mut ui.popup else {
+        return Ok(false);
+    };
+    if key_sequence.keys.len() == 1 {
+        if let Some(effect) = query.input(&key_sequence.keys[0]) {
+            if let InputEffect::TextChanged = effect {
+                list_state.select(Some(0));
+            }
+            return Ok(true);
+        }
+    }
+
+    match config::get_config()
+        .keymap_config
+        .find_command_from_key_sequence(key_sequence, ui.keymap_scope())
+    {
+        Some(command) => handle_command_for_command_palette_popup(command, client_pub, state, ui),
+        None => Ok(false),
+    }
+}
+
+fn handle_command_for_command_palette_popup(
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let Some(PopupState::CommandPalette(query, _)) = &ui.popup else {
+        return Ok(false);
+    };
+    let query = query.get_text();
+
+    let items = {
+        let data = state.data.read();
+        let player = state.player.read();
+        construct_command_palette_items(&data, &player.devices)
+    };
+    let items = filter_items_by_query(&items, &query);
+
+    handle_command_for_list_popup(
+        command,
+        ui,
+        items.len(),
+        |_, _| {},
+        |ui: &mut UIStateGuard, id: usize| -> Result<()> {
+            ui.popup = None;
+            handle_command_palette_item(items[id], client_pub, state, ui)
+        },
+        |ui: &mut UIStateGuard| {
+            ui.popup = None;
+        },
+    )
+}
+
+/// Handle a chosen item in the command palette popup
+fn handle_command_palette_item(
+    item: &CommandPaletteItem,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<()> {
+    match item {
+        CommandPaletteItem::Command(command, _) => {
+            handle_command_or_action(CommandOrAction::Command(*command), client_pub, state, ui)?;
+        }
+        CommandPaletteItem::Action(action, _) => {
+            handle_command_or_action(
+                CommandOrAction::Action(*action, ActionTarget::SelectedItem),
+                client_pub,
+                state,
+                ui,
+            )?;
+        }
+        CommandPaletteItem::Playlist(playlist) => {
+            ui.new_page(PageState::Context {
+                id: None,
+                context_page_type: ContextPageType::Browsing(ContextId::Playlist(
+                    playlist.id.clone(),
+                )),
+                state: None,
+            });
+        }
+        CommandPaletteItem::Device(device) => {
+            let is_playing = state
+                .player
+                .read()
+                .playback
+                .as_ref()
+                .is_some_and(|p| p.is_playing);
+            client_pub.send(ClientRequest::Player(PlayerRequest::TransferPlayback(
+                device.id.clone(),
+                is_playing,
+            )))?;
+        }
+    }
+    Ok(())
+}
+
 fn handle_key_sequence_for_search_popup(
     key_sequence: &KeySequence,
     client_pub: &flume::Sender<ClientRequest>,
@@ -342,7 +900,7 @@ fn handle_key_sequence_for_search_popup(
         return Ok(false);
     };
     if key_sequence.keys.len() == 1 {
-        if let Key::None(c) = key_sequence.keys[0] {
+        if let Some(c) = key_sequence.keys[0].plain_code() {
             match c {
                 crossterm::event::KeyCode::Char(c) => {
                     query.push(c);
@@ -398,6 +956,9 @@ fn handle_command_for_context_browsing_list_popup(
                 rspotify::model::Type::Album => {
                     ContextId::Album(AlbumId::from_uri(&uri)?.into_static())
                 }
+                rspotify::model::Type::Show => {
+                    ContextId::Show(ShowId::from_uri(&uri)?.into_static())
+                }
                 _ => {
                     return Ok(());
                 }
@@ -470,10 +1031,12 @@ fn handle_key_sequence_for_action_list_popup(
 ) -> Result<bool> {
     let Some(command) = config::get_config()
         .keymap_config
-        .find_command_from_key_sequence(key_sequence)
+        .find_command_from_key_sequence(key_sequence, ui.keymap_scope())
     else {
         // handle selecting an action by pressing a key from '0' to '9'
-        if let Some(Key::None(crossterm::event::KeyCode::Char(c))) = key_sequence.keys.first() {
+        if let Some(crossterm::event::KeyCode::Char(c)) =
+            key_sequence.keys.first().copied().and_then(Key::plain_code)
+        {
             if let Some(id) = c.to_digit(10) {
                 let id = id as usize;
                 if id < n_actions {
@@ -485,6 +1048,16 @@ fn handle_key_sequence_for_action_list_popup(
         return Ok(false);
     };
 
+    handle_command_for_action_list_popup(n_actions, command, client_pub, state, ui)
+}
+
+fn handle_command_for_action_list_popup(
+    n_actions: usize,
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
     handle_command_for_list_popup(
         command,
         ui,
@@ -518,6 +1091,13 @@ pub fn handle_item_action(
         ActionListItem::Track(track, actions) => {
             handle_action_in_context(actions[n], track.into(), client_pub, &data, ui)
         }
+        ActionListItem::Tracks(tracks, actions) => handle_action_in_context(
+            actions[n],
+            ActionContext::Tracks(tracks),
+            client_pub,
+            &data,
+            ui,
+        ),
         ActionListItem::Album(album, actions) => {
             handle_action_in_context(actions[n], album.into(), client_pub, &data, ui)
         }
@@ -533,5 +1113,8 @@ pub fn handle_item_action(
         ActionListItem::Episode(episode, actions) => {
             handle_action_in_context(actions[n], episode.into(), client_pub, &data, ui)
         }
+        ActionListItem::Audiobook(audiobook, actions) => {
+            handle_action_in_context(actions[n], audiobook.into(), client_pub, &data, ui)
+        }
     }
 }
@@ -2,10 +2,14 @@ use super::page::handle_navigation_command;
 use super::*;
 use crate::{
     command::{
-        construct_album_actions, construct_artist_actions, construct_playlist_actions,
-        construct_show_actions,
+        construct_album_actions, construct_artist_actions, construct_audiobook_actions,
+        construct_playlist_actions, construct_show_actions,
+    },
+    smart_playlists,
+    state::{
+        store_data_into_file_cache, Audiobook, AudiobookId, Chapter, Episode, FileCacheKey, Show,
+        TrackSort, UIStateGuard,
     },
-    state::{Episode, Show, UIStateGuard},
 };
 use command::Action;
 use rand::Rng;
@@ -29,7 +33,12 @@ pub fn handle_action_for_focused_context_page(
             ..
         }) => {
             let PageState::Context {
-                state: Some(ContextPageUIState::Artist { focus, .. }),
+                state:
+                    Some(ContextPageUIState::Artist {
+                        focus,
+                        album_section,
+                        ..
+                    }),
                 ..
             } = ui.current_page()
             else {
@@ -39,7 +48,10 @@ pub fn handle_action_for_focused_context_page(
             match focus {
                 ArtistFocusState::Albums => handle_action_for_selected_item(
                     action,
-                    &ui.search_filtered_items(albums),
+                    &ui.search_filtered_items(albums)
+                        .into_iter()
+                        .filter(|a| album_section.contains(a))
+                        .collect::<Vec<_>>(),
                     &data,
                     ui,
                     client_pub,
@@ -51,34 +63,55 @@ pub fn handle_action_for_focused_context_page(
                     ui,
                     client_pub,
                 ),
-                ArtistFocusState::TopTracks => handle_action_for_selected_item(
-                    action,
-                    &ui.search_filtered_items(top_tracks),
-                    &data,
-                    ui,
-                    client_pub,
-                ),
+                ArtistFocusState::TopTracks => match marked_tracks(top_tracks, &data, ui) {
+                    Some(tracks) => handle_action_in_context(
+                        action,
+                        ActionContext::Tracks(tracks),
+                        client_pub,
+                        &data,
+                        ui,
+                    ),
+                    None => handle_action_for_selected_item(
+                        action,
+                        &ui.search_filtered_tracks(top_tracks, &data.user_data),
+                        &data,
+                        ui,
+                        client_pub,
+                    ),
+                },
             }
         }
         Some(
             Context::Album { tracks, .. }
             | Context::Tracks { tracks, .. }
             | Context::Playlist { tracks, .. },
-        ) => handle_action_for_selected_item(
-            action,
-            &ui.search_filtered_items(tracks),
-            &data,
-            ui,
-            client_pub,
-        ),
-        Some(Context::Show { episodes, .. }) => handle_action_for_selected_item(
-            action,
-            &ui.search_filtered_items(episodes),
-            &data,
-            ui,
-            client_pub,
-        ),
-        None => Ok(false),
+        ) => match marked_tracks(tracks, &data, ui) {
+            Some(tracks) => handle_action_in_context(
+                action,
+                ActionContext::Tracks(tracks),
+                client_pub,
+                &data,
+                ui,
+            ),
+            None => handle_action_for_selected_item(
+                action,
+                &ui.search_filtered_tracks(tracks, &data.user_data),
+                &data,
+                ui,
+                client_pub,
+            ),
+        },
+        Some(Context::Show { episodes, .. } | Context::Episodes { episodes, .. }) => {
+            handle_action_for_selected_item(
+                action,
+                &ui.search_filtered_items(episodes),
+                &data,
+                ui,
+                client_pub,
+            )
+        }
+        // chapters of an audiobook have no actions
+        Some(Context::Audiobook { .. }) | None => Ok(false),
     }
 }
 
@@ -97,6 +130,61 @@ pub fn handle_action_for_selected_item<T: Into<ActionContext> + Clone>(
     handle_action_in_context(action, items[id].clone().into(), client_pub, data, ui)
 }
 
+/// Get the marked tracks of the current page's context (if any) in the track table's order
+fn marked_tracks(tracks: &[Track], data: &DataReadGuard, ui: &UIStateGuard) -> Option<Vec<Track>> {
+    let context_uri = ui.current_page().context_uri()?;
+    if !ui.track_marks.has_marks(&context_uri) {
+        return None;
+    }
+    Some(
+        ui.search_filtered_tracks(tracks, &data.user_data)
+            .into_iter()
+            .filter(|t| ui.track_marks.is_marked(&context_uri, t))
+            .cloned()
+            .collect(),
+    )
+}
+
+/// Handle commands that mark tracks in a track table
+fn handle_mark_command(
+    command: Command,
+    context_uri: &str,
+    tracks: &[&Track],
+    id: usize,
+    ui: &mut UIStateGuard,
+) -> bool {
+    ui.track_marks.switch_context(context_uri);
+    match command {
+        Command::ToggleMark => {
+            ui.track_marks.toggle(tracks[id]);
+            if id + 1 < tracks.len() {
+                ui.current_page_mut().select(id + 1);
+            }
+        }
+        Command::MarkRange => match ui.track_marks.range_start.take() {
+            Some(start) => {
+                let start = std::cmp::min(start, tracks.len() - 1);
+                for track in &tracks[std::cmp::min(start, id)..=std::cmp::max(start, id)] {
+                    ui.track_marks.mark(track);
+                }
+            }
+            None => {
+                ui.track_marks.range_start = Some(id);
+            }
+        },
+        Command::MarkAll => {
+            for track in tracks {
+                ui.track_marks.mark(track);
+            }
+        }
+        Command::ClearMarks => {
+            ui.track_marks.clear();
+        }
+        _ => return false,
+    }
+    true
+}
+
 /// Handle a command for the currently focused context window
 ///
 /// The function will need to determine the focused window then
@@ -116,32 +204,8 @@ pub fn handle_command_for_focused_context_window(
     };
 
     // handle commands that require access to data's mutable state
-    {
-        let order = match command {
-            Command::SortTrackByTitle => Some(TrackOrder::TrackName),
-            Command::SortTrackByAlbum => Some(TrackOrder::Album),
-            Command::SortTrackByArtists => Some(TrackOrder::Artists),
-            Command::SortTrackByAddedDate => Some(TrackOrder::AddedAt),
-            Command::SortTrackByDuration => Some(TrackOrder::Duration),
-            _ => None,
-        };
-
-        // sort ordering commands
-        if let Some(order) = order {
-            let mut data = state.data.write();
-            if let Some(tracks) = data.context_tracks_mut(context_id) {
-                tracks.sort_by(|x, y| order.compare(x, y));
-            }
-            return Ok(true);
-        }
-        // reverse ordering command
-        if command == Command::ReverseTrackOrder {
-            let mut data = state.data.write();
-            if let Some(tracks) = data.context_tracks_mut(context_id) {
-                tracks.reverse();
-            }
-            return Ok(true);
-        }
+    if handle_track_sort_command(command, context_id, client_pub, state)? {
+        return Ok(true);
     }
 
     let data = state.data.read();
@@ -155,17 +219,42 @@ pub fn handle_command_for_focused_context_window(
                 ..
             } => {
                 let PageState::Context {
-                    state: Some(ContextPageUIState::Artist { focus, .. }),
+                    state:
+                        Some(ContextPageUIState::Artist {
+                            focus,
+                            album_section,
+                            ..
+                        }),
                     ..
                 } = ui.current_page()
                 else {
                     anyhow::bail!("expect an arist context page with a state")
                 };
+                let (focus, mut album_section) = (*focus, *album_section);
+
+                match command {
+                    Command::NextArtistAlbumSection => {
+                        album_section.next();
+                        ui.current_page_mut()
+                            .select_artist_album_section(album_section);
+                        return Ok(true);
+                    }
+                    Command::PreviousArtistAlbumSection => {
+                        album_section.previous();
+                        ui.current_page_mut()
+                            .select_artist_album_section(album_section);
+                        return Ok(true);
+                    }
+                    _ => {}
+                }
 
                 match focus {
                     ArtistFocusState::Albums => handle_command_for_album_list_window(
                         command,
-                        &ui.search_filtered_items(albums),
+                        &ui.search_filtered_items(albums)
+                            .into_iter()
+                            .filter(|a| album_section.contains(a))
+                            .collect::<Vec<_>>(),
                         &data,
                         ui,
                         client_pub,
@@ -194,7 +283,25 @@ pub fn handle_command_for_focused_context_window(
             Context::Show { show, episodes } => handle_command_for_episode_table_window(
                 command,
                 client_pub,
-                &show.id,
+                &Playback::Context(ContextId::Show(show.id.clone()), None),
+                &ui.search_filtered_items(episodes),
+                &data,
+                ui,
+            ),
+            Context::Audiobook {
+                audiobook,
+                chapters,
+            } => handle_command_for_chapter_table_window(
+                command,
+                client_pub,
+                &audiobook.id,
+                &ui.search_filtered_items(chapters),
+                ui,
+            ),
+            Context::Episodes { episodes, .. } => handle_command_for_episode_table_window(
+                command,
+                client_pub,
+                &Playback::URIs(episodes.iter().map(|e| e.id.clone().into()).collect(), None),
                 &ui.search_filtered_items(episodes),
                 &data,
                 ui,
@@ -204,6 +311,122 @@ pub fn handle_command_for_focused_context_window(
     }
 }
 
+/// Handle commands that change the order of a context's track table.
+///
+/// The chosen order is remembered per context and stored in the cache folder.
+fn handle_track_sort_command(
+    command: Command,
+    context_id: &ContextId,
+    client_pub: &flume::Sender<ClientRequest>,
+    state: &SharedState,
+) -> Result<bool> {
+    let order = match command {
+        Command::SortTrackByTitle => Some(TrackOrder::TrackName),
+        Command::SortTrackByAlbum => Some(TrackOrder::Album),
+        Command::SortTrackByArtists => Some(TrackOrder::Artists),
+        Command::SortTrackByAddedDate => Some(TrackOrder::AddedAt),
+        Command::SortTrackByDuration => Some(TrackOrder::Duration),
+        Command::SortTrackByColumn { column } | Command::AddTrackSortKey { column } => {
+            match TrackOrder::from_column(column) {
+                Some(order) => Some(order),
+                None => return Ok(false),
+            }
+        }
+        Command::ReverseTrackOrder | Command::ClearTrackSort => None,
+        _ => return Ok(false),
+    };
+
+    // episodes and chapters cannot be sorted
+    let is_sortable = match context_id {
+        ContextId::Show(_) | ContextId::Audiobook(_) | ContextId::SavedEpisodes => false,
+        ContextId::Album(_)
+        | ContextId::Artist(_)
+        | ContextId::Playlist(_)
+        | ContextId::Tracks(_) => true,
+    };
+    if !is_sortable {
+        return Ok(false);
+    }
+
+    let uri = context_id.uri();
+    let mut data = state.data.write();
+    let mut sort = data.user_data.track_sorts.remove(&uri).unwrap_or_default();
+    match command {
+        Command::ReverseTrackOrder => {
+            sort.reversed = !sort.reversed;
+            if let Some(tracks) = data.context_tracks_mut(context_id) {
+                tracks.reverse();
+            }
+        }
+        Command::ClearTrackSort => {
+            sort = TrackSort::default();
+            // refetch the context's tracks to get them in their original order
+            let request = match context_id {
+                ContextId::Tracks(id) if id.uri == USER_LIKED_TRACKS_ID.uri => {
+                    Some(ClientRequest::GetUserSavedTracks)
+                }
+                ContextId::Tracks(id) if id.uri == USER_TOP_TRACKS_ID.uri => {
+                    Some(ClientRequest::GetUserTopTracks)
+                }
+                ContextId::Tracks(id) if id.uri == USER_RECENTLY_PLAYED_TRACKS_ID.uri => {
+                    Some(ClientRequest::GetUserRecentlyPlayedTracks)
+                }
+                ContextId::Tracks(id) => {
+                    if let Some(name) = smart_playlists::name_from_tracks_id(id) {
+                        Some(ClientRequest::GetSmartPlaylist(name.to_string()))
+                    } else if let Some(seed_uri) = id.uri.strip_prefix("radio:") {
+                        // a radio's description is named after the radio's seed
+                        match data.caches.context.get(&uri) {
+                            Some(Context::Tracks { desc, .. }) => {
+                                Some(ClientRequest::GetRadioTracks {
+                                    seed_uri: seed_uri.to_string(),
+                                    seed_name: desc
+                                        .strip_suffix(" Radio")
+                                        .unwrap_or(desc)
+                                        .to_string(),
+                                })
+                            }
+                            _ => None,
+                        }
+                    } else {
+                        None
+                    }
+                }
+                _ => Some(ClientRequest::GetContext(context_id.clone())),
+            };
+            if let Some(request) = request {
+                data.caches.context.remove(&uri);
+                client_pub.send(request)?;
+            }
+        }
+        _ => {
+            let order = order.expect("sort commands have an order");
+            if let Command::AddTrackSortKey { .. } = command {
+                sort.keys.retain(|key| *key != order);
+                sort.keys.push(order);
+            } else {
+                sort.keys = vec![order];
+            }
+            sort.reversed = false;
+            let play_counts = data.user_data.play_counts.clone();
+            if let Some(tracks) = data.context_tracks_mut(context_id) {
+                sort.sort(tracks, &play_counts);
+            }
+        }
+    }
+
+    if !sort.is_original() {
+        data.user_data.track_sorts.insert(uri, sort);
+    }
+    store_data_into_file_cache(
+        FileCacheKey::TrackSorts,
+        &config::get_config().cache_folder,
+        &data.user_data.track_sorts,
+    )
+    .context("store track orders into the cache folder")?;
+    Ok(true)
+}
+
 /// Handle commands that may modify a playlist
 fn handle_playlist_modify_command(
     id: usize,
@@ -217,27 +440,29 @@ fn handle_playlist_modify_command(
     match command {
         Command::MovePlaylistItemUp => {
             if id > 0 {
+                let insert_index = id.saturating_sub(count_prefix(ui));
                 client_pub.send(ClientRequest::ReorderPlaylistItems {
                     playlist_id: playlist_id.clone_static(),
-                    insert_index: id - 1,
+                    insert_index,
                     range_start: id,
                     range_length: None,
                     snapshot_id: None,
                 })?;
-                ui.current_page_mut().select(id - 1);
+                ui.current_page_mut().select(insert_index);
             }
             return Ok(true);
         }
         Command::MovePlaylistItemDown => {
             if id + 1 < tracks.len() {
+                let insert_index = std::cmp::min(id + count_prefix(ui), tracks.len() - 1);
                 client_pub.send(ClientRequest::ReorderPlaylistItems {
                     playlist_id: playlist_id.clone_static(),
-                    insert_index: id + 1,
+                    insert_index,
                     range_start: id,
                     range_length: None,
                     snapshot_id: None,
                 })?;
-                ui.current_page_mut().select(id + 1);
+                ui.current_page_mut().select(insert_index);
             };
             return Ok(true);
         }
@@ -265,16 +490,57 @@ fn handle_command_for_track_table_window(
     ui: &mut UIStateGuard,
 ) -> Result<bool> {
     let id = ui.current_page_mut().selected().unwrap_or_default();
-    let filtered_tracks = ui.search_filtered_items(tracks);
+    let filtered_tracks = ui.search_filtered_tracks(tracks, &data.user_data);
     if id >= filtered_tracks.len() {
         return Ok(false);
     }
 
+    let context_uri = ui.current_page().context_uri().unwrap_or_default();
+    if handle_mark_command(command, &context_uri, &filtered_tracks, id, ui) {
+        return Ok(true);
+    }
+
+    let modifiable = match context_id {
+        Some(ContextId::Playlist(ref playlist_id)) => data
+            .user_data
+            .modifiable_playlist_items(None)
+            .iter()
+            .any(|item| matches!(item, PlaylistFolderItem::Playlist(p) if p.id.eq(playlist_id))),
+        _ => false,
+    };
+
+    // actions on marked tracks take precedence over actions on the selected track
+    if ui.track_marks.has_marks(&context_uri) {
+        let marked = filtered_tracks
+            .iter()
+            .filter(|t| ui.track_marks.is_marked(&context_uri, t))
+            .map(|t| (*t).clone())
+            .collect::<Vec<_>>();
+        match command {
+            Command::ShowActionsOnSelectedItem => {
+                ui.popup = Some(PopupState::ActionList(
+                    Box::new(ActionListItem::Tracks(
+                        marked,
+                        command::construct_marked_tracks_actions(modifiable),
+                    )),
+                    ListState::default(),
+                ));
+                return Ok(true);
+            }
+            Command::AddSelectedItemToQueue => {
+                return handle_action_in_context(
+                    Action::AddToQueue,
+                    ActionContext::Tracks(marked),
+                    client_pub,
+                    data,
+                    ui,
+                );
+            }
+            _ => {}
+        }
+    }
+
     if let Some(ContextId::Playlist(ref playlist_id)) = context_id {
-        let modifiable =
-            data.user_data.modifiable_playlist_items(None).iter().any(
-                |item| matches!(item, PlaylistFolderItem::Playlist(p) if p.id.eq(playlist_id)),
-            );
         if modifiable
             && handle_playlist_modify_command(
                 id,
@@ -320,6 +586,26 @@ fn handle_command_for_track_table_window(
                 None,
             )))?;
         }
+        Command::PlayDisc { disc } => {
+            let Some(context_id @ ContextId::Album(_)) = context_id else {
+                return Ok(false);
+            };
+            // start the album's playback from the first track of the disc
+            let Some(track) = tracks
+                .iter()
+                .filter(|t| t.disc_number == disc)
+                .min_by_key(|t| t.number)
+            else {
+                return Ok(true);
+            };
+            client_pub.send(ClientRequest::Player(PlayerRequest::StartPlayback(
+                Playback::Context(context_id, None).uri_offset(
+                    track.id.uri(),
+                    config::get_config().app_config.tracks_playback_limit,
+                ),
+                None,
+            )))?;
+        }
         Command::ShowActionsOnSelectedItem => {
             let actions = command::construct_track_actions(filtered_tracks[id], data);
             ui.popup = Some(PopupState::ActionList(
@@ -381,6 +667,20 @@ pub fn handle_command_for_track_list_window(
     Ok(true)
 }
 
+/// Open the browsing page of a context, in a new tab if the command is `OpenInNewTab`
+fn open_context_page(command: Command, context_id: ContextId, ui: &mut UIStateGuard) {
+    let page = PageState::Context {
+        id: None,
+        context_page_type: ContextPageType::Browsing(context_id),
+        state: None,
+    };
+    if command == Command::OpenInNewTab {
+        ui.new_tab(page);
+    } else {
+        ui.new_page(page);
+    }
+}
+
 pub fn handle_command_for_artist_list_window(
     command: Command,
     artists: &[&Artist],
@@ -396,13 +696,9 @@ pub fn handle_command_for_artist_list_window(
         return true;
     }
     match command {
-        Command::ChooseSelected => {
+        Command::ChooseSelected | Command::OpenInNewTab => {
             let context_id = ContextId::Artist(artists[id].id.clone());
-            ui.new_page(PageState::Context {
-                id: None,
-                context_page_type: ContextPageType::Browsing(context_id),
-                state: None,
-            });
+            open_context_page(command, context_id, ui);
         }
         Command::ShowActionsOnSelectedItem => {
             let actions = construct_artist_actions(artists[id], data);
@@ -432,13 +728,9 @@ pub fn handle_command_for_album_list_window(
         return Ok(true);
     }
     match command {
-        Command::ChooseSelected => {
+        Command::ChooseSelected | Command::OpenInNewTab => {
             let context_id = ContextId::Album(albums[id].id.clone());
-            ui.new_page(PageState::Context {
-                id: None,
-                context_page_type: ContextPageType::Browsing(context_id),
-                state: None,
-            });
+            open_context_page(command, context_id, ui);
         }
         Command::ShowActionsOnSelectedItem => {
             let actions = construct_album_actions(albums[id], data);
@@ -470,10 +762,10 @@ pub fn handle_command_for_playlist_list_window(
         return true;
     }
     match command {
-        Command::ChooseSelected => {
+        Command::ChooseSelected | Command::OpenInNewTab => {
             let playlist = playlists[id];
             match playlist {
-                PlaylistFolderItem::Folder(f) => {
+                PlaylistFolderItem::Folder(f) if command == Command::ChooseSelected => {
                     // currently folders are only supported in the library page
                     match ui.current_page_mut() {
                         PageState::Library { state } => {
@@ -484,13 +776,11 @@ pub fn handle_command_for_playlist_list_window(
                         _ => return false,
                     };
                 }
+                // folders don't have a page to open in a new tab
+                PlaylistFolderItem::Folder(_) => return false,
                 PlaylistFolderItem::Playlist(p) => {
                     let context_id = ContextId::Playlist(p.id.clone());
-                    ui.new_page(PageState::Context {
-                        id: None,
-                        context_page_type: ContextPageType::Browsing(context_id),
-                        state: None,
-                    });
+                    open_context_page(command, context_id, ui);
                 }
             }
         }
@@ -523,13 +813,9 @@ pub fn handle_command_for_show_list_window(
         return true;
     }
     match command {
-        Command::ChooseSelected => {
+        Command::ChooseSelected | Command::OpenInNewTab => {
             let context_id = ContextId::Show(shows[id].id.clone());
-            ui.new_page(PageState::Context {
-                id: None,
-                context_page_type: ContextPageType::Browsing(context_id),
-                state: None,
-            });
+            open_context_page(command, context_id, ui);
         }
         Command::ShowActionsOnSelectedItem => {
             let actions = construct_show_actions(shows[id], data);
@@ -543,6 +829,37 @@ pub fn handle_command_for_show_list_window(
     true
 }
 
+pub fn handle_command_for_audiobook_list_window(
+    command: Command,
+    audiobooks: &[&Audiobook],
+    data: &DataReadGuard,
+    ui: &mut UIStateGuard,
+) -> bool {
+    let id = ui.current_page_mut().selected().unwrap_or_default();
+    if id >= audiobooks.len() {
+        return false;
+    }
+
+    if handle_navigation_command(command, ui.current_page_mut(), id, audiobooks.len()) {
+        return true;
+    }
+    match command {
+        Command::ChooseSelected | Command::OpenInNewTab => {
+            let context_id = ContextId::Audiobook(audiobooks[id].id.clone());
+            open_context_page(command, context_id, ui);
+        }
+        Command::ShowActionsOnSelectedItem => {
+            let actions = construct_audiobook_actions(audiobooks[id], data);
+            ui.popup = Some(PopupState::ActionList(
+                Box::new(ActionListItem::Audiobook(audiobooks[id].clone(), actions)),
+                ListState::default(),
+            ));
+        }
+        _ => return false,
+    }
+    true
+}
+
 pub fn handle_command_for_episode_list_window(
     command: Command,
     client_pub: &flume::Sender<ClientRequest>,
@@ -585,7 +902,7 @@ pub fn handle_command_for_episode_list_window(
 fn handle_command_for_episode_table_window(
     command: Command,
     client_pub: &flume::Sender<ClientRequest>,
-    show_id: &ShowId,
+    base_playback: &Playback,
     episodes: &[&Episode],
     data: &DataReadGuard,
     ui: &mut UIStateGuard,
@@ -600,14 +917,20 @@ fn handle_command_for_episode_table_window(
     }
     match command {
         Command::ChooseSelected => {
-            let uri = episodes[id].id.uri();
-            client_pub.send(ClientRequest::Player(PlayerRequest::StartPlayback(
-                Playback::Context(
-                    ContextId::Show(show_id.clone_static()),
-                    Some(rspotify::model::Offset::Uri(uri)),
-                ),
-                None,
-            )))?;
+            let playback = base_playback.uri_offset(
+                episodes[id].id.uri(),
+                config::get_config().app_config.tracks_playback_limit,
+            );
+            // a partially played episode is resumed from where it was left off
+            let request = match data
+                .user_data
+                .episode_resume_position(episodes[id])
+                .and_then(|position| chrono::Duration::from_std(position).ok())
+            {
+                Some(position) => PlayerRequest::StartPlaybackAt(playback, position),
+                None => PlayerRequest::StartPlayback(playback, None),
+            };
+            client_pub.send(ClientRequest::Player(request))?;
         }
         Command::ShowActionsOnSelectedItem => {
             let actions = command::construct_episode_actions(episodes[id], data);
@@ -625,3 +948,40 @@ fn handle_command_for_episode_table_window(
     }
     Ok(true)
 }
+
+fn handle_command_for_chapter_table_window(
+    command: Command,
+    client_pub: &flume::Sender<ClientRequest>,
+    audiobook_id: &AudiobookId,
+    chapters: &[&Chapter],
+    ui: &mut UIStateGuard,
+) -> Result<bool> {
+    let id = ui.current_page_mut().selected().unwrap_or_default();
+    if id >= chapters.len() {
+        return Ok(false);
+    }
+
+    if handle_navigation_command(command, ui.current_page_mut(), id, chapters.len()) {
+        return Ok(true);
+    }
+    match command {
+        Command::ChooseSelected => {
+            let playback = Playback::Context(
+                ContextId::Audiobook(audiobook_id.clone()),
+                Some(rspotify::model::Offset::Uri(chapters[id].id.uri())),
+            );
+            // a partially played chapter is resumed from where it was left off
+            let request = match chapters[id]
+                .resume_position
+                .filter(|_| !chapters[id].fully_played)
+                .and_then(|position| chrono::Duration::from_std(position).ok())
+            {
+                Some(position) => PlayerRequest::StartPlaybackAt(playback, position),
+                None => PlayerRequest::StartPlayback(playback, None),
+            };
+            client_pub.send(ClientRequest::Player(request))?;
+        }
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
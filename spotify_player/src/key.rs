@@ -1,12 +1,19 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
+/// supported modifiers with their string representations, in the order of displaying
+const MODIFIERS: [(KeyModifiers, char); 4] = [
+    (KeyModifiers::CONTROL, 'C'),
+    (KeyModifiers::ALT, 'M'),
+    (KeyModifiers::SHIFT, 'S'),
+    (KeyModifiers::SUPER, 's'),
+];
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
-/// Key represents a key received from user's input
-pub enum Key {
-    Unknown,
-    None(KeyCode),
-    Ctrl(KeyCode),
-    Alt(KeyCode),
+/// Key represents a key received from user's input,
+/// which consists of a key code and a set of modifiers
+pub struct Key {
+    pub code: KeyCode,
+    pub modifiers: KeyModifiers,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -16,6 +23,39 @@ pub struct KeySequence {
 }
 
 impl Key {
+    /// creates a `Key` from a key code and a set of modifiers.
+    ///
+    /// The key is normalized so that the same key has only one representation:
+    /// - `SHIFT` is removed from character keys, as the key code already represents the shifted character
+    ///   (an ASCII letter is converted to its uppercase form)
+    /// - `SHIFT` + `tab` is converted into `backtab`
+    pub fn new(code: KeyCode, modifiers: KeyModifiers) -> Self {
+        let mut key = Self { code, modifiers };
+        if key.modifiers.contains(KeyModifiers::SHIFT) {
+            match key.code {
+                KeyCode::Char(c) => {
+                    key.code = KeyCode::Char(c.to_ascii_uppercase());
+                    key.modifiers.remove(KeyModifiers::SHIFT);
+                }
+                KeyCode::Tab | KeyCode::BackTab => {
+                    key.code = KeyCode::BackTab;
+                    key.modifiers.remove(KeyModifiers::SHIFT);
+                }
+                _ => {}
+            }
+        }
+        key
+    }
+
+    /// returns the key code if the key has no modifier
+    pub fn plain_code(self) -> Option<KeyCode> {
+        if self.modifiers.is_empty() {
+            Some(self.code)
+        } else {
+            None
+        }
+    }
+
     fn parse_key_code(s: &str) -> Option<KeyCode> {
         Some(match s {
             "enter" => KeyCode::Enter,
@@ -61,23 +101,24 @@ impl Key {
         })
     }
 
-    /// creates a `Key` from its string representation
+    /// creates a `Key` from its string representation.
+    ///
+    /// A key can be prefixed by one or more modifiers:
+    /// `C-` for ctrl, `M-` for alt, `S-` for shift and `s-` for super, e.g `C-S-left`.
     pub fn from_str(s: &str) -> Option<Self> {
-        let chars = s.chars().collect::<Vec<_>>();
-        if chars.len() > 2 && chars[1] == '-' && chars[2] != ' ' {
-            // M-<c> for alt-<c> and C-<c> for ctrl-<c>
-            let mut chars = chars.into_iter();
-            let c = chars.next().unwrap();
-            chars.next();
-            let key = Self::parse_key_code(&chars.collect::<String>());
-            match c {
-                'C' => key.map(Key::Ctrl),
-                'M' => key.map(Key::Alt),
-                _ => None,
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = s;
+        loop {
+            let chars = rest.chars().collect::<Vec<_>>();
+            if chars.len() > 2 && chars[1] == '-' && chars[2] != ' ' {
+                let (modifier, _) = MODIFIERS.iter().find(|(_, c)| *c == chars[0])?;
+                modifiers |= *modifier;
+                rest = &rest[2..];
+            } else {
+                break;
             }
-        } else {
-            Self::parse_key_code(s).map(Key::None)
         }
+        Self::parse_key_code(rest).map(|code| Self::new(code, modifiers))
     }
 }
 
@@ -127,12 +168,12 @@ fn key_code_to_string(k: KeyCode) -> String {
 
 impl std::fmt::Display for Key {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match *self {
-            Key::Ctrl(k) => write!(f, "C-{}", key_code_to_string(k)),
-            Key::Alt(k) => write!(f, "M-{}", key_code_to_string(k)),
-            Key::None(k) => write!(f, "{}", key_code_to_string(k)),
-            Key::Unknown => write!(f, "unknown key"),
+        for (modifier, c) in MODIFIERS {
+            if self.modifiers.contains(modifier) {
+                write!(f, "{c}-")?;
+            }
         }
+        write!(f, "{}", key_code_to_string(self.code))
     }
 }
 
@@ -175,19 +216,7 @@ impl KeySequence {
 
 impl From<KeyEvent> for Key {
     fn from(event: KeyEvent) -> Self {
-        let mut modifiers = event.modifiers;
-        // if the key combination contains `SHIFT`, remove it
-        // because the `event.code` already represents the with-SHIFT key code
-        if modifiers & KeyModifiers::SHIFT == KeyModifiers::SHIFT {
-            modifiers ^= KeyModifiers::SHIFT;
-        }
-
-        match modifiers {
-            KeyModifiers::NONE => Key::None(event.code),
-            KeyModifiers::ALT => Key::Alt(event.code),
-            KeyModifiers::CONTROL => Key::Ctrl(event.code),
-            _ => Key::Unknown,
-        }
+        Self::new(event.code, event.modifiers)
     }
 }
 
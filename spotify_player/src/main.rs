@@ -2,12 +2,14 @@ mod auth;
 mod cli;
 mod client;
 mod command;
+mod command_line;
 mod config;
 mod event;
 mod key;
 #[cfg(feature = "media-control")]
 mod media_control;
 mod playlist_folders;
+mod smart_playlists;
 mod state;
 #[cfg(feature = "streaming")]
 mod streaming;
@@ -32,6 +34,8 @@ fn init_spotify(
     client_pub.send(client::ClientRequest::GetUserSavedAlbums)?;
     client_pub.send(client::ClientRequest::GetUserSavedTracks)?;
     client_pub.send(client::ClientRequest::GetUserSavedShows)?;
+    client_pub.send(client::ClientRequest::GetUserSavedEpisodes)?;
+    client_pub.send(client::ClientRequest::GetUserSavedAudiobooks)?;
 
     Ok(())
 }
@@ -166,6 +170,10 @@ async fn start_app(state: &state::SharedState) -> Result<()> {
     if !state.is_daemon {
         // spawn tasks needed for running the application UI
 
+        // the terminal must be queried before the terminal event handler starts reading events
+        let keyboard_enhancement =
+            crossterm::terminal::supports_keyboard_enhancement().unwrap_or(false);
+
         // terminal event handler task
         tokio::task::spawn_blocking({
             let client_pub = client_pub.clone();
@@ -178,7 +186,7 @@ async fn start_app(state: &state::SharedState) -> Result<()> {
         // application UI task
         tokio::task::spawn_blocking({
             let state = state.clone();
-            move || ui::run(&state)
+            move || ui::run(&state, keyboard_enhancement)
         });
     }
 
@@ -249,13 +257,29 @@ fn main() -> Result<()> {
         std::fs::create_dir_all(&cache_image_folder)?;
     }
 
+    let config_overrides = args
+        .get_many::<String>("set")
+        .unwrap_or_default()
+        .cloned()
+        .collect::<Vec<_>>();
+
+    // `config` subcommand inspects the configuration files, so it must be handled
+    // before initializing the application configs
+    if let Some(("config", args)) = args.subcommand() {
+        return cli::handle_config_subcommand(args, &config_folder, &config_overrides);
+    }
+
     // initialize the application configs
     {
-        let mut configs = config::Configs::new(&config_folder, &cache_folder)?;
+        let mut configs = config::Configs::new(&config_folder, &cache_folder, &config_overrides)?;
         if let Some(theme) = args.get_one::<String>("theme") {
             // override the theme config if user specifies a `theme` cli argument
             theme.clone_into(&mut configs.app_config.theme);
         }
+        if args.get_flag("mini") {
+            // start the application in the mini mode if user specifies a `mini` cli argument
+            configs.app_config.mini_mode = true;
+        }
         config::set_config(configs);
     }
 
@@ -1,9 +1,28 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
+use anyhow::{bail, Context, Result};
 use rspotify::model::Id;
 
 use crate::state::{Playlist, PlaylistFolder, PlaylistFolderItem, PlaylistFolderNode};
 
+/// The URI prefix of user-defined local playlist folders
+pub const LOCAL_FOLDER_URI_PREFIX: &str = "local:folder:";
+
+/// Structurize the user's playlists according to the imported and the local playlist folder nodes
+pub fn structurize_user_playlists(
+    playlists: Vec<Playlist>,
+    imported_nodes: &[PlaylistFolderNode],
+    local_nodes: &[PlaylistFolderNode],
+) -> Vec<PlaylistFolderItem> {
+    if imported_nodes.is_empty() && local_nodes.is_empty() {
+        return playlists
+            .into_iter()
+            .map(PlaylistFolderItem::Playlist)
+            .collect();
+    }
+    structurize(playlists, &merge_nodes(imported_nodes, local_nodes))
+}
+
 /// Structurize a flat input playlist according to the playlist folder nodes
 pub fn structurize(
     playlists: Vec<Playlist>,
@@ -11,6 +30,10 @@ pub fn structurize(
 ) -> Vec<PlaylistFolderItem> {
     let mut playlist_folders = Vec::new();
 
+    let ids = playlists
+        .iter()
+        .map(|p| p.id.id().to_string())
+        .collect::<Vec<_>>();
     let mut playlists = playlists
         .into_iter()
         .map(|p| (p.id.id().to_string(), p))
@@ -19,10 +42,13 @@ pub fn structurize(
     // Construct playlist folders with relevant playlists
     add_playlist_folders(nodes, &mut playlists, &mut 0, &mut playlist_folders);
 
-    // Remaining playlists that don't belong to any folders are added as root playlists
-    for (_, mut p) in playlists {
-        p.current_folder_id = 0;
-        playlist_folders.push(PlaylistFolderItem::Playlist(p));
+    // Remaining playlists that don't belong to any folders are added as root playlists,
+    // keeping their original order
+    for id in ids {
+        if let Some(mut p) = playlists.remove(&id) {
+            p.current_folder_id = 0;
+            playlist_folders.push(PlaylistFolderItem::Playlist(p));
+        }
     }
     playlist_folders
 }
@@ -47,12 +73,14 @@ fn add_playlist_folders(
                     name: name.clone(),
                     current_id: current_folder_id,
                     target_id: *folder_id,
+                    uri: f.uri.clone(),
                 }));
                 // Up node
                 acc.push(PlaylistFolderItem::Folder(PlaylistFolder {
                     name: format!("← {name}"),
                     current_id: *folder_id,
                     target_id: current_folder_id,
+                    uri: f.uri.clone(),
                 }));
                 add_playlist_folders(&f.children, playlists, folder_id, acc);
             } else if let Some(mut p) = playlists.remove(id) {
@@ -62,3 +90,263 @@ fn add_playlist_folders(
         }
     }
 }
+
+/// Merge the local playlist folder nodes into the imported nodes.
+///
+/// Playlists placed by the local nodes are removed from the imported nodes,
+/// and the local nodes are added after the imported root nodes.
+fn merge_nodes(
+    imported_nodes: &[PlaylistFolderNode],
+    local_nodes: &[PlaylistFolderNode],
+) -> Vec<PlaylistFolderNode> {
+    let mut local_playlist_uris = HashSet::new();
+    collect_playlist_uris(local_nodes, &mut local_playlist_uris);
+
+    let mut nodes = remove_playlists(imported_nodes, &local_playlist_uris);
+    nodes.extend(local_nodes.iter().cloned());
+    nodes
+}
+
+fn collect_playlist_uris<'a>(nodes: &'a [PlaylistFolderNode], acc: &mut HashSet<&'a str>) {
+    for node in nodes {
+        if node.node_type == "folder" {
+            collect_playlist_uris(&node.children, acc);
+        } else {
+            acc.insert(&node.uri);
+        }
+    }
+}
+
+fn remove_playlists(nodes: &[PlaylistFolderNode], uris: &HashSet<&str>) -> Vec<PlaylistFolderNode> {
+    nodes
+        .iter()
+        .filter(|node| node.node_type == "folder" || !uris.contains(node.uri.as_str()))
+        .map(|node| PlaylistFolderNode {
+            name: node.name.clone(),
+            node_type: node.node_type.clone(),
+            uri: node.uri.clone(),
+            children: remove_playlists(&node.children, uris),
+        })
+        .collect()
+}
+
+/// Get the paths of the local playlist folders, e.g. `/Rock/90s`, including `/` for the root folder
+pub fn local_folder_paths(nodes: &[PlaylistFolderNode]) -> Vec<String> {
+    fn add_paths(nodes: &[PlaylistFolderNode], parent: &str, acc: &mut Vec<String>) {
+        for node in nodes.iter().filter(|n| n.node_type == "folder") {
+            let path = format!("{parent}/{}", node.name.as_deref().unwrap_or_default());
+            acc.push(path.clone());
+            add_paths(&node.children, &path, acc);
+        }
+    }
+
+    let mut paths = vec!["/".to_string()];
+    add_paths(nodes, "", &mut paths);
+    paths
+}
+
+/// Find the URI of the local playlist folder at a path, or `None` for the root folder
+pub fn find_local_folder_by_path(
+    nodes: &[PlaylistFolderNode],
+    path: &str,
+) -> Result<Option<String>> {
+    let mut uri = None;
+    let mut nodes = nodes;
+    for name in path.split('/').filter(|name| !name.is_empty()) {
+        let node = nodes
+            .iter()
+            .find(|n| n.node_type == "folder" && n.name.as_deref() == Some(name))
+            .with_context(|| format!("local folder {path} not found"))?;
+        uri = Some(node.uri.clone());
+        nodes = &node.children;
+    }
+    Ok(uri)
+}
+
+fn find_local_folder_mut<'a>(
+    nodes: &'a mut [PlaylistFolderNode],
+    uri: &str,
+) -> Option<&'a mut PlaylistFolderNode> {
+    for node in nodes.iter_mut().filter(|n| n.node_type == "folder") {
+        if node.uri == uri {
+            return Some(node);
+        }
+        if let Some(node) = find_local_folder_mut(&mut node.children, uri) {
+            return Some(node);
+        }
+    }
+    None
+}
+
+/// Get the children nodes of a local folder, or the root nodes if `folder_uri` is `None`
+fn local_folder_children_mut<'a>(
+    nodes: &'a mut Vec<PlaylistFolderNode>,
+    folder_uri: Option<&str>,
+) -> Result<&'a mut Vec<PlaylistFolderNode>> {
+    match folder_uri {
+        None => Ok(nodes),
+        Some(uri) => match find_local_folder_mut(nodes, uri) {
+            Some(node) => Ok(&mut node.children),
+            None => bail!("only local folders can be edited"),
+        },
+    }
+}
+
+/// Remove a node from the local nodes, returning the removed node (if any)
+fn remove_node(nodes: &mut Vec<PlaylistFolderNode>, uri: &str) -> Option<PlaylistFolderNode> {
+    if let Some(i) = nodes.iter().position(|n| n.uri == uri) {
+        return Some(nodes.remove(i));
+    }
+    nodes
+        .iter_mut()
+        .find_map(|node| remove_node(&mut node.children, uri))
+}
+
+/// Get the nodes containing a node, i.e. the node and its siblings
+fn containing_nodes<'a>(
+    nodes: &'a [PlaylistFolderNode],
+    uri: &str,
+) -> Option<&'a [PlaylistFolderNode]> {
+    if nodes.iter().any(|n| n.uri == uri) {
+        return Some(nodes);
+    }
+    nodes
+        .iter()
+        .find_map(|node| containing_nodes(&node.children, uri))
+}
+
+fn validate_folder_name<'a>(
+    name: &str,
+    siblings: impl IntoIterator<Item = &'a PlaylistFolderNode>,
+) -> Result<()> {
+    if name.contains('/') {
+        bail!("folder name cannot contain `/`");
+    }
+    // folders are found by their paths, so sibling folders cannot have the same name
+    if siblings
+        .into_iter()
+        .any(|n| n.node_type == "folder" && n.name.as_deref() == Some(name))
+    {
+        bail!("folder {name} already exists");
+    }
+    Ok(())
+}
+
+/// Create a local folder inside a parent local folder, or the root folder if `parent_uri` is `None`
+pub fn create_local_folder(
+    nodes: &mut Vec<PlaylistFolderNode>,
+    parent_uri: Option<&str>,
+    name: String,
+) -> Result<()> {
+    fn max_local_id(nodes: &[PlaylistFolderNode]) -> usize {
+        nodes
+            .iter()
+            .map(|n| {
+                let id = n
+                    .uri
+                    .strip_prefix(LOCAL_FOLDER_URI_PREFIX)
+                    .and_then(|id| id.parse().ok())
+                    .unwrap_or(0);
+                id.max(max_local_id(&n.children))
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    let uri = format!("{LOCAL_FOLDER_URI_PREFIX}{}", max_local_id(nodes) + 1);
+    let children = local_folder_children_mut(nodes, parent_uri)?;
+    validate_folder_name(&name, children.iter())?;
+    children.push(PlaylistFolderNode {
+        name: Some(name),
+        node_type: "folder".to_string(),
+        uri,
+        children: vec![],
+    });
+    Ok(())
+}
+
+/// Rename a local folder
+pub fn rename_local_folder(
+    nodes: &mut [PlaylistFolderNode],
+    uri: &str,
+    name: String,
+) -> Result<()> {
+    let siblings = containing_nodes(nodes, uri).context("only local folders can be renamed")?;
+    validate_folder_name(&name, siblings.iter().filter(|n| n.uri != uri))?;
+    let node = find_local_folder_mut(nodes, uri).context("only local folders can be renamed")?;
+    node.name = Some(name);
+    Ok(())
+}
+
+/// Delete a local folder, moving the folder's items into its parent folder
+pub fn delete_local_folder(nodes: &mut Vec<PlaylistFolderNode>, uri: &str) -> Result<()> {
+    fn delete(nodes: &mut Vec<PlaylistFolderNode>, uri: &str) {
+        if let Some(i) = nodes.iter().position(|n| n.uri == uri) {
+            let node = nodes.remove(i);
+            nodes.splice(i..i, node.children);
+            return;
+        }
+        for node in nodes {
+            delete(&mut node.children, uri);
+        }
+    }
+
+    let siblings = containing_nodes(nodes, uri).context("only local folders can be deleted")?;
+    let node = siblings
+        .iter()
+        .find(|n| n.uri == uri)
+        .context("only local folders can be deleted")?;
+    for child in node.children.iter().filter(|n| n.node_type == "folder") {
+        validate_folder_name(
+            child.name.as_deref().unwrap_or_default(),
+            siblings.iter().filter(|n| n.uri != uri),
+        )
+        .context("cannot move the folder's items into its parent folder")?;
+    }
+
+    delete(nodes, uri);
+    Ok(())
+}
+
+/// Move a playlist into a local folder, or the root folder if `folder_uri` is `None`
+pub fn move_playlist(
+    nodes: &mut Vec<PlaylistFolderNode>,
+    playlist: &Playlist,
+    folder_uri: Option<&str>,
+) -> Result<()> {
+    // validate the destination before detaching the playlist from its current folder
+    local_folder_children_mut(nodes, folder_uri)?;
+
+    let uri = playlist.id.uri();
+    let node = remove_node(nodes, &uri).unwrap_or_else(|| PlaylistFolderNode {
+        name: Some(playlist.name.clone()),
+        node_type: "playlist".to_string(),
+        uri,
+        children: vec![],
+    });
+    local_folder_children_mut(nodes, folder_uri)?.push(node);
+    Ok(())
+}
+
+/// Move a local folder into another local folder, or the root folder if `folder_uri` is `None`
+pub fn move_local_folder(
+    nodes: &mut Vec<PlaylistFolderNode>,
+    uri: &str,
+    folder_uri: Option<&str>,
+) -> Result<()> {
+    let node = find_local_folder_mut(nodes, uri).context("only local folders can be moved")?;
+    if let Some(folder_uri) = folder_uri {
+        if folder_uri == uri || find_local_folder_mut(&mut node.children, folder_uri).is_some() {
+            bail!("cannot move a folder into itself");
+        }
+    }
+    let name = node.name.clone().unwrap_or_default();
+    let siblings = local_folder_children_mut(nodes, folder_uri)?;
+    if !siblings.iter().any(|n| n.uri == uri) {
+        validate_folder_name(&name, siblings.iter())?;
+    }
+
+    let node = remove_node(nodes, uri).context("only local folders can be moved")?;
+    local_folder_children_mut(nodes, folder_uri)?.push(node);
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use std::collections::{HashMap, HashSet};
+
+use rspotify::model::Id;
+
+use crate::{
+    config::SmartPlaylist,
+    state::{Artist, Track, TracksId},
+};
+
+/// The URI prefix of smart playlists' track contexts
+const SMART_PLAYLIST_URI_PREFIX: &str = "tracks:smart-playlist:";
+
+/// The context cache key of the tracks of the user's saved albums, from which
+/// smart playlists sourced from saved albums are previewed
+pub const SAVED_ALBUM_TRACKS_CACHE_KEY: &str = "tracks:user-saved-album-tracks";
+
+/// Get the ID of a smart playlist's track context, which is titled with the playlist's name
+pub fn tracks_id(name: &str) -> TracksId {
+    TracksId::new(format!("{SMART_PLAYLIST_URI_PREFIX}{name}"), name)
+}
+
+/// Get the name of a smart playlist from the ID of its track context (if any)
+pub fn name_from_tracks_id(id: &TracksId) -> Option<&str> {
+    id.uri.strip_prefix(SMART_PLAYLIST_URI_PREFIX)
+}
+
+/// Get the release year of a track's album (if any)
+fn release_year(track: &Track) -> Option<i32> {
+    track.album.as_ref()?.release_date.get(..4)?.parse().ok()
+}
+
+/// Select the tracks of a smart playlist from the playlist's source tracks.
+///
+/// Rules are evaluated against the user's followed artists and the application's play counts.
+/// The selected tracks keep their order in the source and duplicated tracks are removed.
+pub fn select_tracks(
+    playlist: &SmartPlaylist,
+    tracks: Vec<Track>,
+    followed_artists: &[Artist],
+    play_counts: &HashMap<String, u32>,
+) -> Vec<Track> {
+    let rules = &playlist.rules;
+    let followed_artist_ids = followed_artists
+        .iter()
+        .map(|a| &a.id)
+        .collect::<HashSet<_>>();
+    let artist_names = rules
+        .artists
+        .iter()
+        .map(|name| name.to_lowercase())
+        .collect::<Vec<_>>();
+    let now = chrono::Utc::now().timestamp() as u64;
+
+    let mut track_ids = HashSet::new();
+    let tracks = tracks.into_iter().filter(|t| {
+        if rules.followed_artists_only
+            && !t
+                .artists
+                .iter()
+                .any(|a| followed_artist_ids.contains(&a.id))
+        {
+            return false;
+        }
+        if !artist_names.is_empty()
+            && !t
+                .artists
+                .iter()
+                .any(|a| artist_names.contains(&a.name.to_lowercase()))
+        {
+            return false;
+        }
+        // tracks without an added date don't satisfy the rule
+        if rules.added_within_days.is_some_and(|days| {
+            t.added_at == 0 || t.added_at.saturating_add(days.saturating_mul(24 * 60 * 60)) < now
+        }) {
+            return false;
+        }
+
+        // tracks without a release date don't satisfy the rules
+        let year = release_year(t);
+        if let Some(before) = rules.released_before {
+            if year.is_none_or(|y| y >= before) {
+                return false;
+            }
+        }
+        if let Some(after) = rules.released_after {
+            if year.is_none_or(|y| y <= after) {
+                return false;
+            }
+        }
+
+        let play_count = play_counts.get(&t.id.uri()).copied().unwrap_or_default();
+        if rules.min_play_count.is_some_and(|n| play_count < n)
+            || rules.max_play_count.is_some_and(|n| play_count > n)
+        {
+            return false;
+        }
+
+        track_ids.insert(t.id.clone())
+    });
+
+    match playlist.limit {
+        Some(limit) => tracks.take(limit).collect(),
+        None => tracks.collect(),
+    }
+}
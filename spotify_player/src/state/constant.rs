@@ -13,3 +13,6 @@ pub static USER_RECENTLY_PLAYED_TRACKS_ID: Lazy<TracksId> = Lazy::new(|| {
 
 pub static USER_LIKED_TRACKS_ID: Lazy<TracksId> =
     Lazy::new(|| TracksId::new("tracks:user-liked-tracks", "Liked Tracks"));
+
+/// the URI of the user's saved episodes context
+pub const USER_SAVED_EPISODES_URI: &str = "episodes:user-saved-episodes";
@@ -2,11 +2,12 @@ use std::io::{BufReader, BufWriter};
 use std::{collections::HashMap, path::Path};
 
 use once_cell::sync::Lazy;
-use serde::{de::DeserializeOwned, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use super::model::{
-    Album, Artist, Category, Context, ContextId, Id, Playlist, PlaylistFolderItem,
-    PlaylistFolderNode, SearchResults, Show, Track,
+    Album, Artist, Audiobook, Category, Context, ContextId, Episode, Id, Playlist,
+    PlaylistFolderItem, PlaylistFolderNode, PlaylistId, SearchResults, Show, ShowSummary, Track,
+    TrackId, TrackSort,
 };
 use super::Lyrics;
 
@@ -16,12 +17,22 @@ pub type DataReadGuard<'a> = parking_lot::RwLockReadGuard<'a, AppData>;
 pub enum FileCacheKey {
     Playlists,
     PlaylistFolders,
+    LocalPlaylistFolders,
     FollowedArtists,
     SavedShows,
+    SavedEpisodes,
+    SavedAudiobooks,
     SavedAlbums,
     SavedTracks,
+    PlayCounts,
+    UndoJournal,
+    TrackSorts,
+    PlayedEpisodes,
 }
 
+/// the maximum number of edits kept in the undo journal
+pub const UNDO_JOURNAL_SIZE: usize = 32;
+
 /// default time-to-live cache duration
 pub static TTL_CACHE_DURATION: Lazy<std::time::Duration> =
     Lazy::new(|| std::time::Duration::from_secs(60 * 60));
@@ -39,10 +50,45 @@ pub struct UserData {
     pub user: Option<rspotify::model::PrivateUser>,
     pub playlists: Vec<PlaylistFolderItem>,
     pub playlist_folder_node: Option<PlaylistFolderNode>,
+    /// the root nodes of the user-defined local playlist folders
+    pub local_playlist_folders: Vec<PlaylistFolderNode>,
     pub followed_artists: Vec<Artist>,
     pub saved_shows: Vec<Show>,
+    /// the episode summaries of saved shows (by URI), loaded once per session
+    pub saved_show_summaries: HashMap<String, ShowSummary>,
+    pub saved_episodes: Vec<Episode>,
+    pub saved_audiobooks: Vec<Audiobook>,
     pub saved_albums: Vec<Album>,
     pub saved_tracks: HashMap<String, Track>,
+    /// the number of times each track (by URI) was played by the application
+    pub play_counts: HashMap<String, u32>,
+    /// recent destructive edits, the most recent one last
+    pub undo_journal: Vec<UndoEntry>,
+    /// the chosen track orders by context URI
+    pub track_sorts: HashMap<String, TrackSort>,
+    /// the played states of episodes (by URI) marked by the user, which take precedence
+    /// over the states reported by Spotify
+    pub played_episodes: HashMap<String, bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+/// A destructive library or playlist edit, storing the data needed to revert it
+pub enum UndoEntry {
+    /// tracks deleted from a playlist, together with their former positions
+    PlaylistItems {
+        playlist_id: PlaylistId<'static>,
+        playlist_name: String,
+        items: Vec<(usize, TrackId<'static>)>,
+        /// the playlist's snapshot right after the deletion
+        snapshot_id: String,
+    },
+    LikedTracks(Vec<Track>),
+    SavedAlbum(Album),
+    FollowedArtist(Artist),
+    FollowedPlaylist(Playlist),
+    SavedShow(Show),
+    SavedEpisode(Episode),
+    SavedAudiobook(Audiobook),
 }
 
 /// the application's in-memory caches
@@ -82,6 +128,37 @@ impl AppData {
         }
     }
 
+    /// Store a context in the in-memory caches, ordering its tracks by the context's chosen order (if any)
+    pub fn cache_context(&mut self, uri: String, mut context: Context) {
+        if let Some(sort) = self.user_data.track_sorts.get(&uri) {
+            let tracks = match context {
+                Context::Album { ref mut tracks, .. }
+                | Context::Playlist { ref mut tracks, .. }
+                | Context::Tracks { ref mut tracks, .. }
+                | Context::Artist {
+                    top_tracks: ref mut tracks,
+                    ..
+                } => Some(tracks),
+                Context::Show { .. } | Context::Episodes { .. } | Context::Audiobook { .. } => None,
+            };
+            if let Some(tracks) = tracks {
+                sort.apply(tracks, &self.user_data.play_counts);
+            }
+        }
+        self.caches
+            .context
+            .insert(uri, context, *TTL_CACHE_DURATION);
+    }
+
+    /// Update the cached saved episodes context (if any) after the user's saved episodes change
+    pub fn update_saved_episodes_context(&mut self) {
+        if let Some(Context::Episodes { episodes, .. }) =
+            self.caches.context.get_mut(&ContextId::SavedEpisodes.uri())
+        {
+            episodes.clone_from(&self.user_data.saved_episodes);
+        }
+    }
+
     /// Get a list of tracks inside a given context
     pub fn context_tracks_mut(&mut self, id: &ContextId) -> Option<&mut Vec<Track>> {
         let c = self.caches.context.get_mut(&id.uri())?;
@@ -93,7 +170,7 @@ impl AppData {
             | Context::Artist {
                 top_tracks: tracks, ..
             } => tracks,
-            Context::Show { .. } => {
+            Context::Show { .. } | Context::Episodes { .. } | Context::Audiobook { .. } => {
                 return None;
             }
         })
@@ -108,7 +185,7 @@ impl AppData {
             | Context::Artist {
                 top_tracks: tracks, ..
             } => tracks,
-            Context::Show { .. } => {
+            Context::Show { .. } | Context::Episodes { .. } | Context::Audiobook { .. } => {
                 return None;
             }
         })
@@ -126,6 +203,11 @@ impl UserData {
                 FileCacheKey::PlaylistFolders,
                 cache_folder,
             ),
+            local_playlist_folders: load_data_from_file_cache(
+                FileCacheKey::LocalPlaylistFolders,
+                cache_folder,
+            )
+            .unwrap_or_default(),
             followed_artists: load_data_from_file_cache(
                 FileCacheKey::FollowedArtists,
                 cache_folder,
@@ -133,10 +215,26 @@ impl UserData {
             .unwrap_or_default(),
             saved_shows: load_data_from_file_cache(FileCacheKey::SavedShows, cache_folder)
                 .unwrap_or_default(),
+            saved_episodes: load_data_from_file_cache(FileCacheKey::SavedEpisodes, cache_folder)
+                .unwrap_or_default(),
+            saved_audiobooks: load_data_from_file_cache(
+                FileCacheKey::SavedAudiobooks,
+                cache_folder,
+            )
+            .unwrap_or_default(),
             saved_albums: load_data_from_file_cache(FileCacheKey::SavedAlbums, cache_folder)
                 .unwrap_or_default(),
             saved_tracks: load_data_from_file_cache(FileCacheKey::SavedTracks, cache_folder)
                 .unwrap_or_default(),
+            play_counts: load_data_from_file_cache(FileCacheKey::PlayCounts, cache_folder)
+                .unwrap_or_default(),
+            undo_journal: load_data_from_file_cache(FileCacheKey::UndoJournal, cache_folder)
+                .unwrap_or_default(),
+            track_sorts: load_data_from_file_cache(FileCacheKey::TrackSorts, cache_folder)
+                .unwrap_or_default(),
+            played_episodes: load_data_from_file_cache(FileCacheKey::PlayedEpisodes, cache_folder)
+                .unwrap_or_default(),
+            saved_show_summaries: HashMap::new(),
         }
     }
 
@@ -170,6 +268,50 @@ impl UserData {
         }
     }
 
+    /// Structurize the user's playlists according to the imported and the local playlist folders
+    pub fn structurize_playlists(&self, playlists: Vec<Playlist>) -> Vec<PlaylistFolderItem> {
+        crate::playlist_folders::structurize_user_playlists(
+            playlists,
+            self.playlist_folder_node
+                .as_ref()
+                .map(|node| node.children.as_slice())
+                .unwrap_or_default(),
+            &self.local_playlist_folders,
+        )
+    }
+
+    /// Rebuild the hierarchy of the user's playlists, e.g. after the local playlist folders are edited
+    pub fn restructurize_playlists(&mut self) {
+        let playlists = std::mem::take(&mut self.playlists)
+            .into_iter()
+            .filter_map(|item| match item {
+                PlaylistFolderItem::Playlist(p) => Some(p),
+                PlaylistFolderItem::Folder(_) => None,
+            })
+            .collect();
+        self.playlists = self.structurize_playlists(playlists);
+    }
+
+    /// Get the URI of the folder with the given folder id, or `None` for the root folder
+    pub fn playlist_folder_uri(&self, folder_id: usize) -> Option<&str> {
+        self.playlists.iter().find_map(|item| match item {
+            PlaylistFolderItem::Folder(f) if f.target_id == folder_id && !f.is_parent_link() => {
+                Some(f.uri.as_str())
+            }
+            _ => None,
+        })
+    }
+
+    /// Get the folder id of the folder with the given URI
+    pub fn playlist_folder_id(&self, uri: &str) -> Option<usize> {
+        self.playlists.iter().find_map(|item| match item {
+            PlaylistFolderItem::Folder(f) if f.uri == uri && !f.is_parent_link() => {
+                Some(f.target_id)
+            }
+            _ => None,
+        })
+    }
+
     /// Get playlists items for the given folder id
     pub fn folder_playlists_items(&self, folder_id: usize) -> Vec<&PlaylistFolderItem> {
         self.playlists
@@ -185,6 +327,59 @@ impl UserData {
     pub fn is_liked_track(&self, track: &Track) -> bool {
         self.saved_tracks.contains_key(&track.id.uri())
     }
+
+    /// Check if an episode is saved in the user's library
+    pub fn is_saved_episode(&self, episode: &Episode) -> bool {
+        self.saved_episodes.iter().any(|e| e.id == episode.id)
+    }
+
+    /// Check if an episode was fully played
+    pub fn is_played_episode(&self, episode: &Episode) -> bool {
+        self.played_episodes
+            .get(&episode.id.uri())
+            .copied()
+            .unwrap_or(episode.fully_played)
+    }
+
+    /// Get the position to resume an episode from, if the episode is partially played
+    pub fn episode_resume_position(&self, episode: &Episode) -> Option<std::time::Duration> {
+        if self.played_episodes.contains_key(&episode.id.uri()) {
+            // a marked episode is either fully played or unplayed
+            return None;
+        }
+        episode.resume_position.filter(|_| !episode.fully_played)
+    }
+}
+
+impl std::fmt::Display for UndoEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::PlaylistItems {
+                playlist_name,
+                items,
+                ..
+            } => write!(
+                f,
+                "deleting {} item(s) from playlist {playlist_name}",
+                items.len()
+            ),
+            Self::LikedTracks(tracks) => {
+                write!(f, "deleting {} track(s) from liked tracks", tracks.len())
+            }
+            Self::SavedAlbum(album) => write!(f, "deleting album {} from library", album.name),
+            Self::FollowedArtist(artist) => write!(f, "unfollowing artist {}", artist.name),
+            Self::FollowedPlaylist(playlist) => {
+                write!(f, "deleting playlist {} from library", playlist.name)
+            }
+            Self::SavedShow(show) => write!(f, "deleting show {} from library", show.name),
+            Self::SavedEpisode(episode) => {
+                write!(f, "deleting episode {} from library", episode.name)
+            }
+            Self::SavedAudiobook(audiobook) => {
+                write!(f, "deleting audiobook {} from library", audiobook.name)
+            }
+        }
+    }
 }
 
 pub fn store_data_into_file_cache<T: Serialize>(
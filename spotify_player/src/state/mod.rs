@@ -35,6 +35,7 @@ impl State {
             // update the UI's theme based on the `theme` config option
             ui.theme = theme;
         }
+        ui.is_mini_mode = configs.app_config.mini_mode;
 
         let app_data = AppData::new(&configs.cache_folder);
 
@@ -2,10 +2,10 @@ pub use rspotify::model::{
     AlbumId, ArtistId, EpisodeId, Id, PlayableId, PlaylistId, ShowId, TrackId, UserId,
 };
 
-use crate::utils::map_join;
+use crate::{config::TrackTableColumnType, utils::map_join};
 use html_escape::decode_html_entities;
 use serde::{Deserialize, Serialize};
-use std::borrow::Cow;
+use std::{borrow::Cow, collections::HashMap};
 
 #[derive(Serialize, Clone, Debug)]
 #[serde(untagged)]
@@ -33,6 +33,14 @@ pub enum Context {
         show: Show,
         episodes: Vec<Episode>,
     },
+    Episodes {
+        episodes: Vec<Episode>,
+        desc: String,
+    },
+    Audiobook {
+        audiobook: Audiobook,
+        chapters: Vec<Chapter>,
+    },
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -49,6 +57,9 @@ pub enum ContextId {
     Artist(ArtistId<'static>),
     Tracks(TracksId),
     Show(ShowId<'static>),
+    Audiobook(AudiobookId),
+    /// the user's saved episodes
+    SavedEpisodes,
 }
 
 /// Data used to start a new playback.
@@ -72,9 +83,11 @@ pub struct SearchResults {
     pub playlists: Vec<Playlist>,
     pub shows: Vec<Show>,
     pub episodes: Vec<Episode>,
+    #[serde(default)]
+    pub audiobooks: Vec<Audiobook>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 /// A track order
 pub enum TrackOrder {
     AddedAt,
@@ -82,6 +95,21 @@ pub enum TrackOrder {
     Album,
     Artists,
     Duration,
+    TrackNumber,
+    Popularity,
+    ReleaseDate,
+    AddedBy,
+    Explicit,
+    PlayCount,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+/// An order of a track table by stacked sort keys
+pub struct TrackSort {
+    /// the sort keys, from the primary one
+    pub keys: Vec<TrackOrder>,
+    /// whether the order is reversed
+    pub reversed: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -92,6 +120,8 @@ pub enum Item {
     Artist(Artist),
     Playlist(Playlist),
     Show(Show),
+    Episode(Episode),
+    Audiobook(Audiobook),
 }
 
 #[derive(Debug, Clone)]
@@ -101,6 +131,8 @@ pub enum ItemId {
     Artist(ArtistId<'static>),
     Playlist(PlaylistId<'static>),
     Show(ShowId<'static>),
+    Episode(EpisodeId<'static>),
+    Audiobook(AudiobookId),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -135,8 +167,19 @@ pub struct Track {
     pub album: Option<Album>,
     pub duration: std::time::Duration,
     pub explicit: bool,
+    /// the track's number in its disc
+    #[serde(default)]
+    pub number: u32,
+    #[serde(default)]
+    pub disc_number: i32,
+    /// the track's popularity (from 0 to 100), which is unavailable for tracks of an album
+    #[serde(default)]
+    pub popularity: Option<u32>,
     #[serde(skip)]
     pub added_at: u64,
+    /// the name of the user who added the track to a playlist
+    #[serde(skip)]
+    pub added_by: Option<String>,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -147,6 +190,19 @@ pub struct Album {
     pub name: String,
     pub artists: Vec<Artist>,
     pub typ: Option<rspotify::model::AlbumType>,
+    /// the album's metadata, which is only available for a full album
+    #[serde(default)]
+    pub metadata: Option<Box<AlbumMetadata>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+/// Metadata of a Spotify album
+pub struct AlbumMetadata {
+    pub label: Option<String>,
+    pub copyrights: Vec<String>,
+    pub genres: Vec<String>,
+    /// the album's popularity (from 0 to 100)
+    pub popularity: u32,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -176,6 +232,14 @@ pub struct Show {
     pub name: String,
 }
 
+#[derive(Debug, Clone)]
+/// A summary of a show's latest episodes
+pub struct ShowSummary {
+    pub latest_release_date: String,
+    /// the latest episodes, whose played states are looked up when the summary is shown
+    pub latest_episodes: Vec<Episode>,
+}
+
 #[derive(Deserialize, Serialize, Debug, Clone)]
 /// A Spotify episode (podcast episode)
 pub struct Episode {
@@ -185,6 +249,42 @@ pub struct Episode {
     pub duration: std::time::Duration,
     pub show: Option<Show>,
     pub release_date: String,
+    /// the position to resume the episode from, if the episode was partially played
+    #[serde(default)]
+    pub resume_position: Option<std::time::Duration>,
+    #[serde(default)]
+    pub fully_played: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+/// A Spotify audiobook's ID. Audiobooks are not supported by `rspotify`.
+pub struct AudiobookId(String);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(transparent)]
+/// A Spotify audiobook chapter's ID
+pub struct ChapterId(String);
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+/// A Spotify audiobook
+pub struct Audiobook {
+    pub id: AudiobookId,
+    pub name: String,
+    pub authors: Vec<String>,
+    pub narrators: Vec<String>,
+    pub publisher: Option<String>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+/// A chapter of a Spotify audiobook
+pub struct Chapter {
+    pub id: ChapterId,
+    pub name: String,
+    pub duration: std::time::Duration,
+    /// the position to resume the chapter from, if the chapter was partially played
+    pub resume_position: Option<std::time::Duration>,
+    pub fully_played: bool,
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone)]
@@ -195,6 +295,9 @@ pub struct PlaylistFolder {
     pub current_id: usize,
     /// target folder id it refers to
     pub target_id: usize,
+    /// URI of the folder's node, which is prefixed with `local:folder:` for a user-defined local folder
+    #[serde(default)]
+    pub uri: String,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -204,7 +307,7 @@ pub enum PlaylistFolderItem {
     Folder(PlaylistFolder),
 }
 
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 /// A reference node retrieved by running <https://github.com/mikez/spotify-folders>
 /// Helps building a playlist folder hierarchy
 pub struct PlaylistFolderNode {
@@ -225,6 +328,18 @@ pub struct Category {
 }
 
 impl Context {
+    /// gets the context's name
+    pub fn name(&self) -> &str {
+        match self {
+            Context::Album { album, .. } => &album.name,
+            Context::Playlist { playlist, .. } => &playlist.name,
+            Context::Artist { artist, .. } => &artist.name,
+            Context::Tracks { desc, .. } | Context::Episodes { desc, .. } => desc,
+            Context::Show { show, .. } => &show.name,
+            Context::Audiobook { audiobook, .. } => &audiobook.name,
+        }
+    }
+
     /// gets the context's description
     pub fn description(&self) -> String {
         match self {
@@ -232,12 +347,33 @@ impl Context {
                 ref album,
                 ref tracks,
             } => {
-                format!(
-                    "{} | {} | {} songs",
+                let duration = tracks
+                    .iter()
+                    .map(|t| t.duration)
+                    .sum::<std::time::Duration>();
+                let mut lines = vec![format!(
+                    "{} | {} | {} songs | {}",
                     album.name,
                     album.release_date,
-                    tracks.len()
-                )
+                    tracks.len(),
+                    crate::utils::format_long_duration(duration),
+                )];
+                if let Some(ref metadata) = album.metadata {
+                    lines[0] = format!("{} | popularity {}", lines[0], metadata.popularity);
+                    let details = metadata
+                        .label
+                        .iter()
+                        .chain(metadata.genres.iter())
+                        .map(String::as_str)
+                        .collect::<Vec<_>>();
+                    if !details.is_empty() {
+                        lines.push(details.join(" | "));
+                    }
+                    if !metadata.copyrights.is_empty() {
+                        lines.push(metadata.copyrights.join(" | "));
+                    }
+                }
+                lines.join("\n")
             }
             Context::Playlist {
                 ref playlist,
@@ -256,6 +392,26 @@ impl Context {
                 ref show,
                 ref episodes,
             } => format!("{} | {} episodes", show.name, episodes.len()),
+            Context::Audiobook {
+                ref audiobook,
+                ref chapters,
+            } => {
+                let duration = chapters
+                    .iter()
+                    .map(|c| c.duration)
+                    .sum::<std::time::Duration>();
+                format!(
+                    "{} | {} | narrated by {} | {} chapters | {}",
+                    audiobook.name,
+                    audiobook.authors.join(", "),
+                    audiobook.narrators.join(", "),
+                    chapters.len(),
+                    crate::utils::format_long_duration(duration),
+                )
+            }
+            Context::Episodes { desc, episodes } => {
+                format!("{} | {} episodes", desc, episodes.len())
+            }
         }
     }
 }
@@ -268,18 +424,114 @@ impl ContextId {
             Self::Playlist(id) => id.uri(),
             Self::Tracks(id) => id.uri.clone(),
             Self::Show(id) => id.uri(),
+            Self::Audiobook(id) => id.uri(),
+            Self::SavedEpisodes => super::USER_SAVED_EPISODES_URI.to_string(),
+        }
+    }
+}
+
+impl AudiobookId {
+    pub fn from_id(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    /// parses an audiobook ID from an URI of format `spotify:audiobook:{id}`
+    pub fn from_uri(uri: &str) -> anyhow::Result<Self> {
+        match uri.strip_prefix("spotify:audiobook:") {
+            Some(id) if !id.is_empty() => Ok(Self::from_id(id)),
+            _ => anyhow::bail!("invalid audiobook URI: {uri}"),
         }
     }
+
+    pub fn id(&self) -> &str {
+        &self.0
+    }
+
+    pub fn uri(&self) -> String {
+        format!("spotify:audiobook:{}", self.0)
+    }
+}
+
+impl ChapterId {
+    pub fn from_id(id: impl Into<String>) -> Self {
+        Self(id.into())
+    }
+
+    pub fn uri(&self) -> String {
+        format!("spotify:chapter:{}", self.0)
+    }
 }
 
 impl TrackOrder {
-    pub fn compare(&self, x: &Track, y: &Track) -> std::cmp::Ordering {
-        match *self {
+    /// gets the order of a track table's column, if the column is sortable
+    pub fn from_column(column: TrackTableColumnType) -> Option<Self> {
+        Some(match column {
+            TrackTableColumnType::Liked | TrackTableColumnType::Number => return None,
+            TrackTableColumnType::Title => Self::TrackName,
+            TrackTableColumnType::Artists => Self::Artists,
+            TrackTableColumnType::Album => Self::Album,
+            TrackTableColumnType::Duration => Self::Duration,
+            TrackTableColumnType::Track | TrackTableColumnType::Disc => Self::TrackNumber,
+            TrackTableColumnType::Popularity => Self::Popularity,
+            TrackTableColumnType::ReleaseDate => Self::ReleaseDate,
+            TrackTableColumnType::AddedAt => Self::AddedAt,
+            TrackTableColumnType::AddedBy => Self::AddedBy,
+            TrackTableColumnType::Explicit => Self::Explicit,
+            TrackTableColumnType::PlayCount => Self::PlayCount,
+        })
+    }
+
+    /// compares two tracks, using the local play counts (by track's URI) for `PlayCount` order
+    pub fn compare(
+        self,
+        x: &Track,
+        y: &Track,
+        play_counts: &HashMap<String, u32>,
+    ) -> std::cmp::Ordering {
+        match self {
             Self::AddedAt => x.added_at.cmp(&y.added_at),
             Self::TrackName => x.name.cmp(&y.name),
             Self::Album => x.album_info().cmp(&y.album_info()),
             Self::Duration => x.duration.cmp(&y.duration),
             Self::Artists => x.artists_info().cmp(&y.artists_info()),
+            Self::TrackNumber => (x.disc_number, x.number).cmp(&(y.disc_number, y.number)),
+            Self::Popularity => x.popularity.cmp(&y.popularity),
+            Self::ReleaseDate => x.release_date().cmp(y.release_date()),
+            Self::AddedBy => x.added_by.cmp(&y.added_by),
+            Self::Explicit => x.explicit.cmp(&y.explicit),
+            Self::PlayCount => {
+                let count = |t: &Track| play_counts.get(&t.id.uri()).copied().unwrap_or_default();
+                count(x).cmp(&count(y))
+            }
+        }
+    }
+}
+
+impl TrackSort {
+    /// whether the sort keeps the original order of tracks
+    pub fn is_original(&self) -> bool {
+        self.keys.is_empty() && !self.reversed
+    }
+
+    /// sorts tracks by the sort keys, ignoring `reversed`
+    pub fn sort(&self, tracks: &mut [Track], play_counts: &HashMap<String, u32>) {
+        if self.keys.is_empty() {
+            return;
+        }
+        tracks.sort_by(|x, y| {
+            self.keys
+                .iter()
+                .map(|key| key.compare(x, y, play_counts))
+                .find(|ordering| ordering.is_ne())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+    }
+
+    /// applies the sort to tracks in their original order
+    pub fn apply(&self, tracks: &mut [Track], play_counts: &HashMap<String, u32>) {
+        self.sort(tracks, play_counts);
+        if self.reversed {
+            tracks.reverse();
         }
     }
 }
@@ -308,6 +560,14 @@ impl Track {
             .unwrap_or_default()
     }
 
+    /// gets the release date of the track's album
+    pub fn release_date(&self) -> &str {
+        self.album
+            .as_ref()
+            .map(|a| a.release_date.as_str())
+            .unwrap_or_default()
+    }
+
     /// gets the track's name, including an explicit label
     pub fn display_name(&self) -> Cow<'_, str> {
         if self.explicit {
@@ -331,7 +591,11 @@ impl Track {
                 album: None,
                 duration: track.duration.to_std().expect("valid chrono duration"),
                 explicit: track.explicit,
+                number: track.track_number,
+                disc_number: track.disc_number,
+                popularity: None,
                 added_at: 0,
+                added_by: None,
             })
         } else {
             None
@@ -342,6 +606,7 @@ impl Track {
     fn try_from_full_track_with_date(
         track: rspotify::model::FullTrack,
         added_at: Option<chrono::DateTime<chrono::Utc>>,
+        added_by: Option<String>,
     ) -> Option<Self> {
         if track.is_playable.unwrap_or(true) {
             let id = match track.linked_from {
@@ -355,7 +620,11 @@ impl Track {
                 album: Album::try_from_simplified_album(track.album),
                 duration: track.duration.to_std().expect("valid chrono duration"),
                 explicit: track.explicit,
+                number: track.track_number,
+                disc_number: track.disc_number,
+                popularity: Some(track.popularity),
                 added_at: added_at.map(|t| t.timestamp() as u64).unwrap_or_default(),
+                added_by,
             })
         } else {
             None
@@ -364,7 +633,12 @@ impl Track {
 
     /// tries to convert from a `rspotify::model::FullTrack` into `Track`
     pub fn try_from_full_track(track: rspotify::model::FullTrack) -> Option<Self> {
-        Track::try_from_full_track_with_date(track, None)
+        Track::try_from_full_track_with_date(track, None, None)
+    }
+
+    /// tries to convert from a `rspotify::model::SavedTrack` into `Track`
+    pub fn try_from_saved_track(track: rspotify::model::SavedTrack) -> Option<Self> {
+        Track::try_from_full_track_with_date(track.track, Some(track.added_at), None)
     }
 
     /// tries to convert from a `rspotify::model::PlaylistItem` into `Track`
@@ -373,7 +647,11 @@ impl Track {
             return None;
         };
 
-        Track::try_from_full_track_with_date(track, item.added_at)
+        let added_by = item.added_by.map(|user| {
+            user.display_name
+                .unwrap_or_else(|| user.id.id().to_string())
+        });
+        Track::try_from_full_track_with_date(track, item.added_at, added_by)
     }
 }
 
@@ -397,15 +675,18 @@ impl Album {
             name: album.name,
             release_date: album.release_date.unwrap_or_default(),
             artists: from_simplified_artists_to_artists(album.artists),
-            typ: album
-                .album_type
-                .and_then(|t| match t.to_ascii_lowercase().as_str() {
+            // an artist's album has a group (e.g. `appears_on`) representing
+            // the album's relation to the artist, which is preferred over the album's type
+            typ: album.album_group.or(album.album_type).and_then(|t| {
+                match t.to_ascii_lowercase().as_str() {
                     "album" => Some(rspotify::model::AlbumType::Album),
                     "single" => Some(rspotify::model::AlbumType::Single),
                     "appears_on" => Some(rspotify::model::AlbumType::AppearsOn),
                     "compilation" => Some(rspotify::model::AlbumType::Compilation),
                     _ => None,
-                }),
+                }
+            }),
+            metadata: None,
         })
     }
 
@@ -435,6 +716,12 @@ impl From<rspotify::model::FullAlbum> for Album {
             release_date: album.release_date,
             artists: from_simplified_artists_to_artists(album.artists),
             typ: Some(album.album_type),
+            metadata: Some(Box::new(AlbumMetadata {
+                label: album.label,
+                copyrights: album.copyrights.into_iter().map(|c| c.text).collect(),
+                genres: album.genres,
+                popularity: album.popularity,
+            })),
         }
     }
 }
@@ -563,6 +850,8 @@ impl From<rspotify::model::SimplifiedEpisode> for Episode {
             duration: episode.duration.to_std().expect("valid chrono duration"),
             show: None,
             release_date: episode.release_date,
+            resume_position: resume_position(episode.resume_point.as_ref()),
+            fully_played: episode.resume_point.is_some_and(|p| p.fully_played),
         }
     }
 }
@@ -576,10 +865,21 @@ impl From<rspotify::model::FullEpisode> for Episode {
             duration: episode.duration.to_std().expect("valid chrono duration"),
             show: Some(episode.show.into()),
             release_date: episode.release_date,
+            resume_position: resume_position(episode.resume_point.as_ref()),
+            fully_played: episode.resume_point.is_some_and(|p| p.fully_played),
         }
     }
 }
 
+/// Get the position to resume an episode from a resume point, if the episode was started
+fn resume_position(
+    resume_point: Option<&rspotify::model::ResumePoint>,
+) -> Option<std::time::Duration> {
+    resume_point
+        .and_then(|p| p.resume_position.to_std().ok())
+        .filter(|position| !position.is_zero())
+}
+
 impl std::fmt::Display for Episode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         if let Some(s) = &self.show {
@@ -590,6 +890,88 @@ impl std::fmt::Display for Episode {
     }
 }
 
+/// A person object (e.g. an author or a narrator) of Spotify API's audiobook object
+#[derive(Deserialize, Debug)]
+pub struct AudiobookPersonObject {
+    pub name: String,
+}
+
+/// Spotify API's simplified audiobook object, which is not supported by `rspotify`
+#[derive(Deserialize, Debug)]
+pub struct SimplifiedAudiobookObject {
+    pub id: String,
+    pub name: String,
+    pub authors: Vec<AudiobookPersonObject>,
+    pub narrators: Vec<AudiobookPersonObject>,
+    pub publisher: Option<String>,
+}
+
+/// Spotify API's audiobook object, which is not supported by `rspotify`
+#[derive(Deserialize, Debug)]
+pub struct FullAudiobookObject {
+    #[serde(flatten)]
+    pub audiobook: SimplifiedAudiobookObject,
+    pub chapters: rspotify::model::Page<SimplifiedChapterObject>,
+}
+
+/// Spotify API's simplified chapter object, which is not supported by `rspotify`
+#[derive(Deserialize, Debug)]
+pub struct SimplifiedChapterObject {
+    pub id: String,
+    pub name: String,
+    pub duration_ms: u64,
+    pub resume_point: Option<rspotify::model::ResumePoint>,
+}
+
+impl From<SimplifiedAudiobookObject> for Audiobook {
+    fn from(audiobook: SimplifiedAudiobookObject) -> Self {
+        Self {
+            id: AudiobookId::from_id(audiobook.id),
+            name: audiobook.name,
+            authors: audiobook.authors.into_iter().map(|a| a.name).collect(),
+            narrators: audiobook.narrators.into_iter().map(|n| n.name).collect(),
+            publisher: audiobook.publisher,
+        }
+    }
+}
+
+impl From<SimplifiedChapterObject> for Chapter {
+    fn from(chapter: SimplifiedChapterObject) -> Self {
+        Self {
+            id: ChapterId::from_id(chapter.id),
+            name: chapter.name,
+            duration: std::time::Duration::from_millis(chapter.duration_ms),
+            resume_position: resume_position(chapter.resume_point.as_ref()),
+            fully_played: chapter.resume_point.is_some_and(|p| p.fully_played),
+        }
+    }
+}
+
+impl std::fmt::Display for Audiobook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} • {}", self.name, self.authors.join(", "))
+    }
+}
+
+impl std::fmt::Display for Chapter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl PlaylistFolder {
+    /// Check if the folder item links back to the parent of the folder it refers to
+    pub fn is_parent_link(&self) -> bool {
+        self.target_id < self.current_id
+    }
+
+    /// Check if the folder is a user-defined local folder
+    pub fn is_local(&self) -> bool {
+        self.uri
+            .starts_with(crate::playlist_folders::LOCAL_FOLDER_URI_PREFIX)
+    }
+}
+
 impl std::fmt::Display for PlaylistFolder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{}/", self.name)
@@ -1,4 +1,6 @@
-use super::model::{AlbumId, ArtistId, ContextId, Device, PlaybackMetadata, PlaylistId, ShowId};
+use super::model::{
+    AlbumId, ArtistId, AudiobookId, ContextId, Device, PlaybackMetadata, PlaylistId, ShowId,
+};
 
 /// Player state
 #[derive(Default, Debug)]
@@ -12,6 +14,9 @@ pub struct PlayerState {
     pub buffered_playback: Option<PlaybackMetadata>,
 
     pub queue: Option<rspotify::model::CurrentUserQueue>,
+
+    /// The time at which the playback will be paused, set by the `sleep` command line command
+    pub sleep_timer: Option<std::time::Instant>,
 }
 
 impl PlayerState {
@@ -73,6 +78,10 @@ impl PlayerState {
             Some(ref playback) => match playback.context {
                 Some(ref context) => {
                     let uri = crate::utils::parse_uri(&context.uri);
+                    // audiobook contexts are not recognized by `rspotify`
+                    if let Ok(id) = AudiobookId::from_uri(&uri) {
+                        return Some(ContextId::Audiobook(id));
+                    }
                     match context._type {
                         rspotify::model::Type::Playlist => Some(ContextId::Playlist(
                             PlaylistId::from_uri(&uri).ok()?.into_static(),
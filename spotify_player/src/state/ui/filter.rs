@@ -0,0 +1,276 @@
+use std::ops::RangeInclusive;
+
+use crate::state::{Id, Track, UserData};
+
+/// A search query parsed into a list of filter terms.
+///
+/// A query consists of space-separated terms, each of which is either a text matched
+/// against an item's description or a field qualifier `field:value` matched against
+/// a track's field, e.g. `artist:radiohead album:"ok computer" year:1990..1999 dur:>5m`.
+/// A term prefixed by `-` is negated.
+///
+/// A query without field qualifiers and negated terms is matched as a plain text instead.
+#[derive(Debug)]
+pub struct SearchFilter {
+    terms: Vec<FilterTerm>,
+}
+
+#[derive(Debug)]
+struct FilterTerm {
+    negated: bool,
+    /// the lowercased text of the term (the value of a field qualifier)
+    text: String,
+    field: Option<FieldFilter>,
+}
+
+#[derive(Debug)]
+/// A filter on a track's field
+pub enum FieldFilter {
+    Artist(String),
+    Album(String),
+    Title(String),
+    Year(RangeInclusive<u64>),
+    /// duration in seconds
+    Duration(RangeInclusive<u64>),
+    Popularity(RangeInclusive<u64>),
+    Plays(RangeInclusive<u64>),
+    Explicit(bool),
+    Liked(bool),
+}
+
+impl SearchFilter {
+    /// Parse a search query, returning `None` if the query has neither field qualifiers nor negated terms
+    pub fn new(query: &str) -> Option<Self> {
+        let terms = split_query(query)
+            .into_iter()
+            .map(|term| {
+                let (negated, term) = match term.strip_prefix('-') {
+                    Some(t) if !t.is_empty() => (true, t),
+                    _ => (false, term.as_str()),
+                };
+                let term = term.to_lowercase();
+                // a term with an unknown field or an invalid value is treated as a text
+                match term
+                    .split_once(':')
+                    .and_then(|(field, value)| Some((value, FieldFilter::new(field, value)?)))
+                {
+                    Some((value, field)) => FilterTerm {
+                        negated,
+                        text: value.to_string(),
+                        field: Some(field),
+                    },
+                    None => FilterTerm {
+                        negated,
+                        text: term,
+                        field: None,
+                    },
+                }
+            })
+            .collect::<Vec<_>>();
+        if terms
+            .iter()
+            .all(|term| term.field.is_none() && !term.negated)
+        {
+            return None;
+        }
+        Some(Self { terms })
+    }
+
+    /// Filter a list of items.
+    ///
+    /// `match_field` checks if an item matches a field filter, returning `None` if the field
+    /// is not supported by the item, in which case the field's value is matched as a text.
+    pub fn filter<'a, T: std::fmt::Display>(
+        &self,
+        items: &'a [T],
+        match_field: impl Fn(&T, &FieldFilter) -> Option<bool>,
+    ) -> Vec<&'a T> {
+        let items = items.iter().filter(|item| {
+            let desc = item.to_string().to_lowercase();
+            self.terms.iter().all(|term| {
+                let matched = match term.field {
+                    Some(ref field) => {
+                        match_field(item, field).unwrap_or_else(|| desc.contains(&term.text))
+                    }
+                    // non-negated texts are matched using fuzzy search
+                    None if cfg!(feature = "fzf") && !term.negated => true,
+                    None => desc.contains(&term.text),
+                };
+                matched != term.negated
+            })
+        });
+
+        #[cfg(feature = "fzf")]
+        {
+            let query = self
+                .terms
+                .iter()
+                .filter(|term| term.field.is_none() && !term.negated)
+                .map(|term| term.text.as_str())
+                .collect::<Vec<_>>()
+                .join(" ");
+            fuzzy_search_items(items, &query)
+        }
+
+        #[cfg(not(feature = "fzf"))]
+        items.collect()
+    }
+}
+
+impl FieldFilter {
+    fn new(field: &str, value: &str) -> Option<Self> {
+        if value.is_empty() {
+            return None;
+        }
+        Some(match field {
+            "artist" => Self::Artist(value.to_string()),
+            "album" => Self::Album(value.to_string()),
+            "title" | "name" => Self::Title(value.to_string()),
+            "year" => Self::Year(parse_range(value, |v| v.parse().ok())?),
+            "dur" | "duration" => Self::Duration(parse_range(value, parse_duration)?),
+            "pop" | "popularity" => Self::Popularity(parse_range(value, |v| v.parse().ok())?),
+            "plays" => Self::Plays(parse_range(value, |v| v.parse().ok())?),
+            "explicit" => Self::Explicit(parse_bool(value)?),
+            "liked" => Self::Liked(parse_bool(value)?),
+            _ => return None,
+        })
+    }
+
+    /// Check if a track matches the filter
+    pub fn matches_track(&self, track: &Track, data: &UserData) -> bool {
+        let contains = |s: &str, value: &str| s.to_lowercase().contains(value);
+        match self {
+            Self::Artist(value) => track.artists.iter().any(|a| contains(&a.name, value)),
+            Self::Album(value) => track
+                .album
+                .as_ref()
+                .is_some_and(|a| contains(&a.name, value)),
+            Self::Title(value) => contains(&track.name, value),
+            Self::Year(range) => track
+                .release_date()
+                .get(..4)
+                .and_then(|year| year.parse().ok())
+                .is_some_and(|year| range.contains(&year)),
+            Self::Duration(range) => range.contains(&track.duration.as_secs()),
+            Self::Popularity(range) => track
+                .popularity
+                .is_some_and(|p| range.contains(&u64::from(p))),
+            Self::Plays(range) => {
+                let plays = data
+                    .play_counts
+                    .get(&track.id.uri())
+                    .copied()
+                    .unwrap_or_default();
+                range.contains(&u64::from(plays))
+            }
+            Self::Explicit(explicit) => track.explicit == *explicit,
+            Self::Liked(liked) => data.is_liked_track(track) == *liked,
+        }
+    }
+}
+
+/// Split a query into space-separated terms, in which a double-quoted text is kept in one term
+fn split_query(query: &str) -> Vec<String> {
+    let mut terms = vec![];
+    let mut term = String::new();
+    let mut in_quotes = false;
+    for c in query.chars() {
+        match c {
+            '"' => in_quotes = !in_quotes,
+            c if c.is_whitespace() && !in_quotes => {
+                if !term.is_empty() {
+                    terms.push(std::mem::take(&mut term));
+                }
+            }
+            c => term.push(c),
+        }
+    }
+    if !term.is_empty() {
+        terms.push(term);
+    }
+    terms
+}
+
+/// Parse a range of numbers in the form of `x`, `a..b`, `a..`, `..b`, `>x`, `>=x`, `<x` or `<=x`
+fn parse_range(value: &str, parse: impl Fn(&str) -> Option<u64>) -> Option<RangeInclusive<u64>> {
+    if let Some(v) = value.strip_prefix(">=") {
+        Some(parse(v)?..=u64::MAX)
+    } else if let Some(v) = value.strip_prefix("<=") {
+        Some(0..=parse(v)?)
+    } else if let Some(v) = value.strip_prefix('>') {
+        Some(parse(v)?.saturating_add(1)..=u64::MAX)
+    } else if let Some(v) = value.strip_prefix('<') {
+        // `<0` is invalid
+        Some(0..=parse(v)?.checked_sub(1)?)
+    } else if let Some((start, end)) = value.split_once("..") {
+        let start = if start.is_empty() { 0 } else { parse(start)? };
+        let end = if end.is_empty() {
+            u64::MAX
+        } else {
+            parse(end)?
+        };
+        Some(start..=end)
+    } else {
+        let v = parse(value)?;
+        Some(v..=v)
+    }
+}
+
+/// Parse a duration in seconds in the form of `m:ss` or a number with optional `h`/`m`/`s` units
+/// (e.g. `1h`, `5m`, `3m30s`, `90`)
+fn parse_duration(value: &str) -> Option<u64> {
+    if let Some((m, s)) = value.split_once(':') {
+        return m
+            .parse::<u64>()
+            .ok()?
+            .checked_mul(60)?
+            .checked_add(s.parse().ok()?);
+    }
+
+    let mut secs: u64 = 0;
+    let mut number = String::new();
+    for c in value.chars() {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+        let unit = match c {
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        secs = secs.checked_add(number.parse::<u64>().ok()?.checked_mul(unit)?)?;
+        number.clear();
+    }
+    if !number.is_empty() {
+        secs = secs.checked_add(number.parse().ok()?)?;
+    }
+    Some(secs)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "yes" | "y" | "true" => Some(true),
+        "no" | "n" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(feature = "fzf")]
+pub(super) fn fuzzy_search_items<'a, T: std::fmt::Display>(
+    items: impl Iterator<Item = &'a T>,
+    query: &str,
+) -> Vec<&'a T> {
+    let matcher = fuzzy_matcher::skim::SkimMatcherV2::default();
+    let mut result = items
+        .filter_map(|t| {
+            matcher
+                .fuzzy(&t.to_string(), query, false)
+                .map(|(score, _)| (t, score))
+        })
+        .collect::<Vec<_>>();
+
+    result.sort_by(|(_, a), (_, b)| b.cmp(a));
+    result.into_iter().map(|(t, _)| t).collect::<Vec<_>>()
+}
@@ -1,16 +1,19 @@
 use crate::{
-    config::{self, Theme},
+    command::CommandOrAction,
+    config::{self, KeymapScope, Theme},
     key,
     ui::{self, Orientation},
 };
 
 pub type UIStateGuard<'a> = parking_lot::MutexGuard<'a, UIState>;
 
+mod filter;
 mod page;
 mod popup;
 
-use super::TracksId;
+use super::{Track, TracksId, UserData};
 
+pub use filter::*;
 pub use page::*;
 pub use popup::*;
 
@@ -23,20 +26,56 @@ pub struct ImageRenderInfo {
     pub rendered: bool,
 }
 
+/// A target of mouse events in a clickable region of the rendered UI
+#[derive(Debug, Clone, Copy)]
+pub enum MouseTarget {
+    /// A window of the current page with `len` rows, starting at the region's top
+    Window { window: WindowType, len: usize },
+    /// A row of a window of the current page, representing the window's `id`-th item
+    Row { window: WindowType, id: usize },
+    /// The list of the current popup with `len` rows, starting at the region's top
+    PopupList { len: usize },
+    /// A button running a command or an action when clicked
+    Button(CommandOrAction),
+    /// A tab in the tab bar
+    Tab(usize),
+    /// A section tab of an artist page's album window
+    ArtistAlbumSection(ArtistAlbumSection),
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MouseRegion {
+    pub rect: tui::layout::Rect,
+    pub target: MouseTarget,
+}
+
 /// Application's UI state
 #[derive(Debug)]
 pub struct UIState {
     pub is_running: bool,
     pub theme: config::Theme,
     pub input_key_sequence: key::KeySequence,
+    /// A pending count prefix (e.g. `5` in `5j`) for the next command
+    pub count_prefix: Option<usize>,
     pub orientation: ui::Orientation,
+    /// Whether the application is toggled to render only a single-line playback status
+    pub is_mini_mode: bool,
 
-    pub history: Vec<PageState>,
+    /// Opened tabs, each of which holds its own page history
+    pub tabs: Vec<Tab>,
+    pub current_tab: usize,
     pub popup: Option<PopupState>,
+    pub track_marks: TrackMarks,
+    /// Previously executed command line inputs, from oldest to newest
+    pub command_line_history: Vec<String>,
 
     /// The rectangle representing the playback progress bar,
     /// which is mainly used to handle mouse click events (for seeking command)
     pub playback_progress_bar_rect: tui::layout::Rect,
+    /// The clickable regions of the last rendered frame
+    pub mouse_regions: Vec<MouseRegion>,
+    /// The time and position of the last left click, which is used to detect double clicks
+    pub last_mouse_click: Option<(std::time::Instant, u16, u16)>,
 
     #[cfg(feature = "image")]
     pub last_cover_image_render_info: ImageRenderInfo,
@@ -44,11 +83,41 @@ pub struct UIState {
 
 impl UIState {
     pub fn current_page(&self) -> &PageState {
-        self.history.last().expect("non-empty history")
+        self.tabs[self.current_tab].current_page()
     }
 
     pub fn current_page_mut(&mut self) -> &mut PageState {
-        self.history.last_mut().expect("non-empty history")
+        self.tabs[self.current_tab].current_page_mut()
+    }
+
+    pub fn current_tab_mut(&mut self) -> &mut Tab {
+        &mut self.tabs[self.current_tab]
+    }
+
+    /// Open a new tab with a given page right after the current tab
+    pub fn new_tab(&mut self, page: PageState) {
+        self.current_tab += 1;
+        self.tabs.insert(self.current_tab, Tab::new(page));
+        self.popup = None;
+    }
+
+    /// Close the current tab, returning `false` if it is the only opened tab
+    pub fn close_current_tab(&mut self) -> bool {
+        if self.tabs.len() <= 1 {
+            return false;
+        }
+        self.tabs.remove(self.current_tab);
+        self.current_tab = self.current_tab.min(self.tabs.len() - 1);
+        self.popup = None;
+        true
+    }
+
+    /// Switch to the tab at a given position
+    pub fn switch_tab(&mut self, id: usize) {
+        if id < self.tabs.len() {
+            self.current_tab = id;
+            self.popup = None;
+        }
     }
 
     pub fn new_search_popup(&mut self) {
@@ -59,7 +128,7 @@ impl UIState {
     }
 
     pub fn new_page(&mut self, page: PageState) {
-        self.history.push(page);
+        self.current_tab_mut().push(page);
         self.popup = None;
     }
 
@@ -84,62 +153,128 @@ impl UIState {
         }
     }
 
+    /// Register a clickable region of the currently rendered frame
+    pub fn add_mouse_region(&mut self, rect: Rect, target: MouseTarget) {
+        self.mouse_regions.push(MouseRegion { rect, target });
+    }
+
+    /// Find the clickable region at a given position of the terminal
+    pub fn find_mouse_region(&self, column: u16, row: u16) -> Option<MouseRegion> {
+        self.mouse_regions
+            .iter()
+            .rev()
+            .find(|region| region.rect.contains((column, row).into()))
+            .copied()
+    }
+
+    /// Return the current UI scope, which is used to resolve scoped keymaps.
+    ///
+    /// The page and window scopes are unset if there exists a focused popup,
+    /// so that page-scoped keymaps don't apply inside such popup.
+    pub fn keymap_scope(&self) -> KeymapScope {
+        let page = self.current_page();
+        let is_page_focused = !self.has_focused_popup();
+        KeymapScope {
+            page: is_page_focused.then(|| page.page_type()),
+            window: page.focus_window_type().filter(|_| is_page_focused),
+            popup: self.popup.as_ref().map(PopupState::popup_type),
+        }
+    }
+
+    /// Return whether a count prefix can be typed in the current UI,
+    /// i.e. digit keys are not used for user's inputs
+    pub fn accepts_count_prefix(&self) -> bool {
+        let is_typing = match self.popup {
+            Some(
+                PopupState::Search { .. }
+                | PopupState::PlaylistCreate { .. }
+                | PopupState::ActionList(..)
+                | PopupState::CommandPalette(..)
+                | PopupState::CommandLine { .. },
+            ) => true,
+            Some(_) => false,
+            None => matches!(
+                self.current_page(),
+                PageState::Search { state, .. } if state.focus == SearchFocusState::Input
+            ),
+        };
+        !is_typing
+    }
+
     /// Get a list of items possibly filtered by a search query if exists a search popup
     pub fn search_filtered_items<'a, T: std::fmt::Display>(&self, items: &'a [T]) -> Vec<&'a T> {
         match self.popup {
-            Some(PopupState::Search { ref query }) => {
-                let query = query.to_lowercase();
-
-                #[cfg(feature = "fzf")]
-                return fuzzy_search_items(items, &query);
-
-                #[cfg(not(feature = "fzf"))]
-                items
-                    .iter()
-                    .filter(|t| {
-                        if query.is_empty() {
-                            true
-                        } else {
-                            let t = t.to_string().to_lowercase();
-                            query
-                                .split(' ')
-                                .filter(|q| !q.is_empty())
-                                .all(|q| t.contains(q))
-                        }
-                    })
-                    .collect::<Vec<_>>()
-            }
+            Some(PopupState::Search { ref query }) => filter_items_by_query(items, query),
             _ => items.iter().collect::<Vec<_>>(),
         }
     }
+
+    /// Get a list of tracks possibly filtered by a search query if exists a search popup.
+    ///
+    /// Unlike `search_filtered_items`, field qualifiers of the query are matched against the tracks' fields.
+    pub fn search_filtered_tracks<'a>(
+        &self,
+        tracks: &'a [Track],
+        data: &UserData,
+    ) -> Vec<&'a Track> {
+        match self.popup {
+            Some(PopupState::Search { ref query }) => match SearchFilter::new(query) {
+                Some(filter) => {
+                    filter.filter(tracks, |t, field| Some(field.matches_track(t, data)))
+                }
+                None => filter_items_by_query(tracks, query),
+            },
+            _ => tracks.iter().collect::<Vec<_>>(),
+        }
+    }
+
+    /// Get the search query if exists a search popup
+    pub fn search_query(&self) -> Option<&str> {
+        match self.popup {
+            Some(PopupState::Search { ref query }) => Some(query),
+            _ => None,
+        }
+    }
 }
 
-#[cfg(feature = "fzf")]
-use fuzzy_matcher::skim::SkimMatcherV2;
-use tui::layout::Rect;
+/// Get a list of items filtered by a search query
+pub fn filter_items_by_query<'a, T: std::fmt::Display>(items: &'a [T], query: &str) -> Vec<&'a T> {
+    if let Some(filter) = SearchFilter::new(query) {
+        return filter.filter(items, |_, _| None);
+    }
+
+    let query = query.to_lowercase();
 
-#[cfg(feature = "fzf")]
-fn fuzzy_search_items<'a, T: std::fmt::Display>(items: &'a [T], query: &str) -> Vec<&'a T> {
-    let matcher = SkimMatcherV2::default();
-    let mut result = items
+    #[cfg(feature = "fzf")]
+    return filter::fuzzy_search_items(items.iter(), &query);
+
+    #[cfg(not(feature = "fzf"))]
+    items
         .iter()
-        .filter_map(|t| {
-            matcher
-                .fuzzy(&t.to_string(), &query, false)
-                .map(|(score, _)| (t, score))
+        .filter(|t| {
+            if query.is_empty() {
+                true
+            } else {
+                let t = t.to_string().to_lowercase();
+                query
+                    .split(' ')
+                    .filter(|q| !q.is_empty())
+                    .all(|q| t.contains(q))
+            }
         })
-        .collect::<Vec<_>>();
-
-    result.sort_by(|(_, a), (_, b)| b.cmp(a));
-    result.into_iter().map(|(t, _)| t).collect::<Vec<_>>()
+        .collect::<Vec<_>>()
 }
 
+use tui::layout::Rect;
+
 impl Default for UIState {
     fn default() -> Self {
         Self {
             is_running: true,
             theme: Theme::default(),
             input_key_sequence: key::KeySequence { keys: vec![] },
+            count_prefix: None,
+            is_mini_mode: false,
             orientation: match crossterm::terminal::size() {
                 Ok((columns, rows)) => ui::Orientation::from_size(columns, rows),
                 Err(err) => {
@@ -148,12 +283,17 @@ impl Default for UIState {
                 }
             },
 
-            history: vec![PageState::Library {
+            tabs: vec![Tab::new(PageState::Library {
                 state: LibraryPageUIState::new(),
-            }],
+            })],
+            current_tab: 0,
             popup: None,
+            track_marks: TrackMarks::default(),
+            command_line_history: vec![],
 
             playback_progress_bar_rect: Rect::default(),
+            mouse_regions: vec![],
+            last_mouse_click: None,
 
             #[cfg(feature = "image")]
             last_cover_image_render_info: ImageRenderInfo::default(),
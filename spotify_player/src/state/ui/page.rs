@@ -1,7 +1,10 @@
 use crate::{
-    state::model::{Category, ContextId},
+    state::model::{Album, Category, ContextId, Track},
     ui::single_line_input::LineInput,
 };
+use rspotify::model::Id;
+use serde::Deserialize;
+use std::collections::HashSet;
 use tui::widgets::{ListState, TableState};
 
 #[derive(Clone, Debug)]
@@ -35,7 +38,7 @@ pub enum PageState {
     },
 }
 
-#[derive(PartialEq, Eq, Clone, Copy)]
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize)]
 pub enum PageType {
     Library,
     Context,
@@ -46,11 +49,27 @@ pub enum PageType {
     CommandHelp,
 }
 
+/// The type of a page's window, categorized by the type of the window's items
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Deserialize)]
+pub enum WindowType {
+    SearchInput,
+    Tracks,
+    Albums,
+    Artists,
+    Playlists,
+    Shows,
+    Episodes,
+    Audiobooks,
+    Chapters,
+    Categories,
+}
+
 #[derive(Clone, Debug)]
 pub struct LibraryPageUIState {
     pub playlist_list: ListState,
     pub saved_album_list: ListState,
     pub followed_artist_list: ListState,
+    pub saved_show_list: ListState,
     pub focus: LibraryFocusState,
     pub playlist_folder_id: usize,
 }
@@ -63,6 +82,7 @@ pub struct SearchPageUIState {
     pub playlist_list: ListState,
     pub show_list: ListState,
     pub episode_list: ListState,
+    pub audiobook_list: ListState,
     pub focus: SearchFocusState,
 }
 
@@ -85,6 +105,7 @@ pub enum ContextPageUIState {
         album_table: TableState,
         related_artist_list: ListState,
         focus: ArtistFocusState,
+        album_section: ArtistAlbumSection,
     },
     Tracks {
         track_table: TableState,
@@ -92,6 +113,9 @@ pub enum ContextPageUIState {
     Show {
         episode_table: TableState,
     },
+    Audiobook {
+        chapter_table: TableState,
+    },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -99,6 +123,7 @@ pub enum LibraryFocusState {
     Playlists,
     SavedAlbums,
     FollowedArtists,
+    SavedShows,
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
@@ -108,6 +133,15 @@ pub enum ArtistFocusState {
     RelatedArtists,
 }
 
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// A section of an artist's albums, grouped by the albums' release types
+pub enum ArtistAlbumSection {
+    Albums,
+    Singles,
+    Compilations,
+    AppearsOn,
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum SearchFocusState {
     Input,
@@ -117,6 +151,7 @@ pub enum SearchFocusState {
     Playlists,
     Shows,
     Episodes,
+    Audiobooks,
 }
 
 #[derive(Clone, Debug)]
@@ -130,6 +165,25 @@ pub enum BrowsePageUIState {
     },
 }
 
+/// A tab holding a back/forward history of visited pages
+#[derive(Clone, Debug)]
+pub struct Tab {
+    history: Vec<PageState>,
+    /// the position of the current page in the history
+    current: usize,
+}
+
+/// Tracks marked in a context's track table, which bulk actions apply to
+#[derive(Debug, Default)]
+pub struct TrackMarks {
+    /// the URI of the context whose tracks are marked
+    context_uri: String,
+    /// the URIs of the marked tracks
+    uris: HashSet<String>,
+    /// the position in the track table where a range selection starts
+    pub range_start: Option<usize>,
+}
+
 pub enum MutableWindowState<'a> {
     Table(&'a mut TableState),
     List(&'a mut ListState),
@@ -150,6 +204,117 @@ impl PageState {
         }
     }
 
+    /// The URI of the page's context (if any).
+    pub fn context_uri(&self) -> Option<String> {
+        match self {
+            Self::Context { id: Some(id), .. } => Some(id.uri()),
+            _ => None,
+        }
+    }
+
+    /// The type of the currently focused window of the page (if any).
+    pub fn focus_window_type(&self) -> Option<WindowType> {
+        match self {
+            Self::Library { state } => Some(match state.focus {
+                LibraryFocusState::Playlists => WindowType::Playlists,
+                LibraryFocusState::SavedAlbums => WindowType::Albums,
+                LibraryFocusState::FollowedArtists => WindowType::Artists,
+                LibraryFocusState::SavedShows => WindowType::Shows,
+            }),
+            Self::Search { state, .. } => Some(match state.focus {
+                SearchFocusState::Input => WindowType::SearchInput,
+                SearchFocusState::Tracks => WindowType::Tracks,
+                SearchFocusState::Albums => WindowType::Albums,
+                SearchFocusState::Artists => WindowType::Artists,
+                SearchFocusState::Playlists => WindowType::Playlists,
+                SearchFocusState::Shows => WindowType::Shows,
+                SearchFocusState::Episodes => WindowType::Episodes,
+                SearchFocusState::Audiobooks => WindowType::Audiobooks,
+            }),
+            Self::Context { state, .. } => state.as_ref().map(|state| match state {
+                ContextPageUIState::Playlist { .. }
+                | ContextPageUIState::Album { .. }
+                | ContextPageUIState::Tracks { .. } => WindowType::Tracks,
+                ContextPageUIState::Artist { focus, .. } => match focus {
+                    ArtistFocusState::TopTracks => WindowType::Tracks,
+                    ArtistFocusState::Albums => WindowType::Albums,
+                    ArtistFocusState::RelatedArtists => WindowType::Artists,
+                },
+                ContextPageUIState::Show { .. } => WindowType::Episodes,
+                ContextPageUIState::Audiobook { .. } => WindowType::Chapters,
+            }),
+            Self::Browse { state } => Some(match state {
+                BrowsePageUIState::CategoryList { .. } => WindowType::Categories,
+                BrowsePageUIState::CategoryPlaylistList { .. } => WindowType::Playlists,
+            }),
+            Self::Lyrics { .. } | Self::Queue { .. } | Self::CommandHelp { .. } => None,
+        }
+    }
+
+    /// Switch an artist page's album window to a section, focusing the window.
+    /// Returns `false` if the page is not an artist page.
+    pub fn select_artist_album_section(&mut self, section: ArtistAlbumSection) -> bool {
+        let Self::Context {
+            state:
+                Some(ContextPageUIState::Artist {
+                    album_table,
+                    focus,
+                    album_section,
+                    ..
+                }),
+            ..
+        } = self
+        else {
+            return false;
+        };
+        *album_section = section;
+        *focus = ArtistFocusState::Albums;
+        *album_table = TableState::default().with_selected(Some(0));
+        true
+    }
+
+    /// Focus a window of the page, returning `false` if the page has no such window.
+    pub fn focus_window(&mut self, window: WindowType) -> bool {
+        match self {
+            Self::Library { state } => {
+                state.focus = match window {
+                    WindowType::Playlists => LibraryFocusState::Playlists,
+                    WindowType::Albums => LibraryFocusState::SavedAlbums,
+                    WindowType::Artists => LibraryFocusState::FollowedArtists,
+                    WindowType::Shows => LibraryFocusState::SavedShows,
+                    _ => return false,
+                };
+            }
+            Self::Search { state, .. } => {
+                state.focus = match window {
+                    WindowType::SearchInput => SearchFocusState::Input,
+                    WindowType::Tracks => SearchFocusState::Tracks,
+                    WindowType::Albums => SearchFocusState::Albums,
+                    WindowType::Artists => SearchFocusState::Artists,
+                    WindowType::Playlists => SearchFocusState::Playlists,
+                    WindowType::Shows => SearchFocusState::Shows,
+                    WindowType::Episodes => SearchFocusState::Episodes,
+                    WindowType::Audiobooks => SearchFocusState::Audiobooks,
+                    WindowType::Chapters | WindowType::Categories => return false,
+                };
+            }
+            Self::Context {
+                state: Some(ContextPageUIState::Artist { focus, .. }),
+                ..
+            } => {
+                *focus = match window {
+                    WindowType::Tracks => ArtistFocusState::TopTracks,
+                    WindowType::Albums => ArtistFocusState::Albums,
+                    WindowType::Artists => ArtistFocusState::RelatedArtists,
+                    _ => return false,
+                };
+            }
+            // other pages have at most one window
+            _ => return self.focus_window_type() == Some(window),
+        }
+        true
+    }
+
     /// Select a `id`-th item in the currently focused window of the page.
     pub fn select(&mut self, id: usize) {
         if let Some(mut state) = self.focus_window_state_mut() {
@@ -172,6 +337,7 @@ impl PageState {
                         playlist_list,
                         saved_album_list,
                         followed_artist_list,
+                        saved_show_list,
                         focus,
                         ..
                     },
@@ -181,6 +347,7 @@ impl PageState {
                 LibraryFocusState::FollowedArtists => {
                     MutableWindowState::List(followed_artist_list)
                 }
+                LibraryFocusState::SavedShows => MutableWindowState::List(saved_show_list),
             }),
             Self::Search {
                 state:
@@ -191,6 +358,7 @@ impl PageState {
                         playlist_list,
                         show_list,
                         episode_list,
+                        audiobook_list,
                         focus,
                     },
                 ..
@@ -202,6 +370,7 @@ impl PageState {
                 SearchFocusState::Playlists => Some(MutableWindowState::List(playlist_list)),
                 SearchFocusState::Shows => Some(MutableWindowState::List(show_list)),
                 SearchFocusState::Episodes => Some(MutableWindowState::List(episode_list)),
+                SearchFocusState::Audiobooks => Some(MutableWindowState::List(audiobook_list)),
             },
             Self::Context { state, .. } => state.as_mut().map(|state| match state {
                 ContextPageUIState::Tracks { track_table }
@@ -214,6 +383,7 @@ impl PageState {
                     album_table,
                     related_artist_list,
                     focus,
+                    ..
                 } => match focus {
                     ArtistFocusState::TopTracks => MutableWindowState::Table(top_track_table),
                     ArtistFocusState::Albums => MutableWindowState::Table(album_table),
@@ -224,6 +394,9 @@ impl PageState {
                 ContextPageUIState::Show { episode_table } => {
                     MutableWindowState::Table(episode_table)
                 }
+                ContextPageUIState::Audiobook { chapter_table } => {
+                    MutableWindowState::Table(chapter_table)
+                }
             }),
             Self::Browse { state } => match state {
                 BrowsePageUIState::CategoryList { state } => Some(MutableWindowState::List(state)),
@@ -239,12 +412,103 @@ impl PageState {
     }
 }
 
+impl Tab {
+    pub fn new(page: PageState) -> Self {
+        Self {
+            history: vec![page],
+            current: 0,
+        }
+    }
+
+    pub fn current_page(&self) -> &PageState {
+        &self.history[self.current]
+    }
+
+    pub fn current_page_mut(&mut self) -> &mut PageState {
+        &mut self.history[self.current]
+    }
+
+    /// Visit a new page, discarding the pages visited after the current page
+    pub fn push(&mut self, page: PageState) {
+        self.history.truncate(self.current + 1);
+        self.history.push(page);
+        self.current += 1;
+    }
+
+    /// Whether there is a previous page to go back to
+    pub fn has_previous_page(&self) -> bool {
+        self.current > 0
+    }
+
+    /// Go back to the previous page, returning `false` if there is no such page
+    pub fn back(&mut self) -> bool {
+        if !self.has_previous_page() {
+            return false;
+        }
+        self.current -= 1;
+        true
+    }
+
+    /// Go forward to the next page after going back, returning `false` if there is no such page
+    pub fn forward(&mut self) -> bool {
+        if self.current + 1 >= self.history.len() {
+            return false;
+        }
+        self.current += 1;
+        true
+    }
+}
+
+impl TrackMarks {
+    /// Discard the marks if they don't belong to a given context
+    pub fn switch_context(&mut self, context_uri: &str) {
+        if self.context_uri != context_uri {
+            *self = Self {
+                context_uri: context_uri.to_string(),
+                ..Self::default()
+            };
+        }
+    }
+
+    /// Whether the marks belong to a given context
+    pub fn is_in_context(&self, context_uri: &str) -> bool {
+        self.context_uri == context_uri
+    }
+
+    /// Whether a track of a given context is marked
+    pub fn is_marked(&self, context_uri: &str, track: &Track) -> bool {
+        self.is_in_context(context_uri) && self.uris.contains(&track.id.uri())
+    }
+
+    /// Whether there are marked tracks in a given context
+    pub fn has_marks(&self, context_uri: &str) -> bool {
+        self.is_in_context(context_uri) && !self.uris.is_empty()
+    }
+
+    pub fn mark(&mut self, track: &Track) {
+        self.uris.insert(track.id.uri());
+    }
+
+    pub fn toggle(&mut self, track: &Track) {
+        let uri = track.id.uri();
+        if !self.uris.remove(&uri) {
+            self.uris.insert(uri);
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.uris.clear();
+        self.range_start = None;
+    }
+}
+
 impl LibraryPageUIState {
     pub fn new() -> Self {
         Self {
             playlist_list: ListState::default(),
             saved_album_list: ListState::default(),
             followed_artist_list: ListState::default(),
+            saved_show_list: ListState::default(),
             focus: LibraryFocusState::Playlists,
             playlist_folder_id: 0,
         }
@@ -260,6 +524,7 @@ impl SearchPageUIState {
             playlist_list: ListState::default(),
             show_list: ListState::default(),
             episode_list: ListState::default(),
+            audiobook_list: ListState::default(),
             focus: SearchFocusState::Input,
         }
     }
@@ -275,6 +540,8 @@ impl ContextPageType {
                 ContextId::Artist(_) => String::from("Artist"),
                 ContextId::Tracks(id) => id.kind.clone(),
                 ContextId::Show(_) => String::from("Show"),
+                ContextId::Audiobook(_) => String::from("Audiobook"),
+                ContextId::SavedEpisodes => String::from("Saved Episodes"),
             },
         }
     }
@@ -299,6 +566,7 @@ impl ContextPageUIState {
             album_table: TableState::default(),
             related_artist_list: ListState::default(),
             focus: ArtistFocusState::TopTracks,
+            album_section: ArtistAlbumSection::Albums,
         }
     }
 
@@ -313,9 +581,55 @@ impl ContextPageUIState {
             episode_table: TableState::default(),
         }
     }
+
+    pub fn new_audiobook() -> Self {
+        Self::Audiobook {
+            chapter_table: TableState::default(),
+        }
+    }
+}
+
+impl ArtistAlbumSection {
+    pub const ALL: [Self; 4] = [
+        Self::Albums,
+        Self::Singles,
+        Self::Compilations,
+        Self::AppearsOn,
+    ];
+
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Albums => "Albums",
+            Self::Singles => "Singles & EPs",
+            Self::Compilations => "Compilations",
+            Self::AppearsOn => "Appears On",
+        }
+    }
+
+    /// Check if an artist's album belongs to the section
+    pub fn contains(self, album: &Album) -> bool {
+        use rspotify::model::AlbumType;
+
+        let section = match album.typ {
+            Some(AlbumType::Single) => Self::Singles,
+            Some(AlbumType::Compilation) => Self::Compilations,
+            Some(AlbumType::AppearsOn) => Self::AppearsOn,
+            Some(AlbumType::Album) | None => Self::Albums,
+        };
+        section == self
+    }
 }
 
 impl MutableWindowState<'_> {
+    /// The position of the first item rendered in the window
+    pub fn offset(&self) -> usize {
+        match self {
+            Self::List(state) => state.offset(),
+            Self::Table(state) => state.offset(),
+            Self::Scroll(scroll_offset) => **scroll_offset,
+        }
+    }
+
     pub fn select(&mut self, id: usize) {
         match self {
             Self::List(state) => state.select(Some(id)),
@@ -414,7 +728,8 @@ impl_focusable!(
     LibraryFocusState,
     [Playlists, SavedAlbums],
     [SavedAlbums, FollowedArtists],
-    [FollowedArtists, Playlists]
+    [FollowedArtists, SavedShows],
+    [SavedShows, Playlists]
 );
 
 impl_focusable!(
@@ -424,6 +739,14 @@ impl_focusable!(
     [RelatedArtists, TopTracks]
 );
 
+impl_focusable!(
+    ArtistAlbumSection,
+    [Albums, Singles],
+    [Singles, Compilations],
+    [Compilations, AppearsOn],
+    [AppearsOn, Albums]
+);
+
 impl_focusable!(
     SearchFocusState,
     [Input, Tracks],
@@ -432,5 +755,6 @@ impl_focusable!(
     [Artists, Playlists],
     [Playlists, Shows],
     [Shows, Episodes],
-    [Episodes, Input]
+    [Episodes, Audiobooks],
+    [Audiobooks, Input]
 );
@@ -1,8 +1,11 @@
 use crate::{
+    client::ClientRequest,
     command,
-    state::model::{Album, Artist, Episode, EpisodeId, Playlist, Show, Track, TrackId},
+    state::model::{Album, Artist, Audiobook, Episode, EpisodeId, Playlist, Show, Track, TrackId},
     ui::single_line_input::LineInput,
 };
+use serde::Deserialize;
+use std::borrow::Cow;
 use tui::widgets::ListState;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -19,25 +22,64 @@ pub enum PopupState {
     UserPlaylistList(PlaylistPopupAction, ListState),
     UserFollowedArtistList(ListState),
     UserSavedAlbumList(ListState),
+    UserSavedShowList(ListState),
+    SmartPlaylistList(ListState),
+    UserSavedAudiobookList(ListState),
     DeviceList(ListState),
     ArtistList(ArtistPopupAction, Vec<Artist>, ListState),
     ThemeList(Vec<crate::config::Theme>, ListState),
     ActionList(Box<ActionListItem>, ListState),
+    CommandPalette(LineInput, ListState),
+    CommandLine {
+        input: LineInput,
+        /// the position of the currently browsed entry in the command line history
+        history_index: Option<usize>,
+        /// completion candidates of the last completed text and the chosen candidate's position
+        completions: Option<(Vec<String>, usize)>,
+    },
     PlaylistCreate {
         name: LineInput,
         desc: LineInput,
         current_field: PlaylistCreateCurrentField,
     },
+    /// A popup asking for a confirmation before sending a request
+    Confirm {
+        message: String,
+        request: Box<ClientRequest>,
+    },
+}
+
+/// The type of a popup, which is the name of its `PopupState` variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum PopupType {
+    Search,
+    UserPlaylistList,
+    UserFollowedArtistList,
+    UserSavedAlbumList,
+    UserSavedShowList,
+    SmartPlaylistList,
+    UserSavedAudiobookList,
+    DeviceList,
+    ArtistList,
+    ThemeList,
+    ActionList,
+    CommandPalette,
+    CommandLine,
+    PlaylistCreate,
+    Confirm,
 }
 
 #[derive(Debug, Clone)]
 pub enum ActionListItem {
     Track(Track, Vec<command::Action>),
+    /// marked tracks in a track table
+    Tracks(Vec<Track>, Vec<command::Action>),
     Artist(Artist, Vec<command::Action>),
     Album(Album, Vec<command::Action>),
     Playlist(Playlist, Vec<command::Action>),
     Show(Show, Vec<command::Action>),
     Episode(Episode, Vec<command::Action>),
+    Audiobook(Audiobook, Vec<command::Action>),
 }
 
 /// An action on an item in a playlist popup list
@@ -46,14 +88,21 @@ pub enum PlaylistPopupAction {
     Browse {
         folder_id: usize,
     },
-    AddTrack {
+    AddTracks {
         folder_id: usize,
-        track_id: TrackId<'static>,
+        track_ids: Vec<TrackId<'static>>,
+        /// whether the tracks are the marked tracks of a track table
+        marked: bool,
     },
     AddEpisode {
         folder_id: usize,
         episode_id: EpisodeId<'static>,
     },
+    /// add all tracks of an artist's discography
+    AddArtistDiscography {
+        folder_id: usize,
+        artist: Artist,
+    },
 }
 
 /// An action on an item in an artist popup list
@@ -64,6 +113,27 @@ pub enum ArtistPopupAction {
 }
 
 impl PopupState {
+    /// gets the type of the popup
+    pub fn popup_type(&self) -> PopupType {
+        match self {
+            Self::Search { .. } => PopupType::Search,
+            Self::UserPlaylistList(..) => PopupType::UserPlaylistList,
+            Self::UserFollowedArtistList(..) => PopupType::UserFollowedArtistList,
+            Self::UserSavedAlbumList(..) => PopupType::UserSavedAlbumList,
+            Self::UserSavedShowList(..) => PopupType::UserSavedShowList,
+            Self::SmartPlaylistList(..) => PopupType::SmartPlaylistList,
+            Self::UserSavedAudiobookList(..) => PopupType::UserSavedAudiobookList,
+            Self::DeviceList(..) => PopupType::DeviceList,
+            Self::ArtistList(..) => PopupType::ArtistList,
+            Self::ThemeList(..) => PopupType::ThemeList,
+            Self::ActionList(..) => PopupType::ActionList,
+            Self::CommandPalette(..) => PopupType::CommandPalette,
+            Self::CommandLine { .. } => PopupType::CommandLine,
+            Self::PlaylistCreate { .. } => PopupType::PlaylistCreate,
+            Self::Confirm { .. } => PopupType::Confirm,
+        }
+    }
+
     /// gets the (immutable) list state of a (list) popup
     pub fn list_state(&self) -> Option<&ListState> {
         match self {
@@ -71,10 +141,17 @@ impl PopupState {
             | Self::UserPlaylistList(.., list_state)
             | Self::UserFollowedArtistList(list_state)
             | Self::UserSavedAlbumList(list_state)
+            | Self::UserSavedShowList(list_state)
+            | Self::SmartPlaylistList(list_state)
+            | Self::UserSavedAudiobookList(list_state)
             | Self::ArtistList(.., list_state)
             | Self::ThemeList(.., list_state)
-            | Self::ActionList(.., list_state) => Some(list_state),
-            Self::Search { .. } | Self::PlaylistCreate { .. } => None,
+            | Self::ActionList(.., list_state)
+            | Self::CommandPalette(.., list_state) => Some(list_state),
+            Self::Search { .. }
+            | Self::CommandLine { .. }
+            | Self::PlaylistCreate { .. }
+            | Self::Confirm { .. } => None,
         }
     }
 
@@ -85,10 +162,17 @@ impl PopupState {
             | Self::UserPlaylistList(.., list_state)
             | Self::UserFollowedArtistList(list_state)
             | Self::UserSavedAlbumList(list_state)
+            | Self::UserSavedShowList(list_state)
+            | Self::SmartPlaylistList(list_state)
+            | Self::UserSavedAudiobookList(list_state)
             | Self::ArtistList(.., list_state)
             | Self::ThemeList(.., list_state)
-            | Self::ActionList(.., list_state) => Some(list_state),
-            Self::Search { .. } | Self::PlaylistCreate { .. } => None,
+            | Self::ActionList(.., list_state)
+            | Self::CommandPalette(.., list_state) => Some(list_state),
+            Self::Search { .. }
+            | Self::CommandLine { .. }
+            | Self::PlaylistCreate { .. }
+            | Self::Confirm { .. } => None,
         }
     }
 
@@ -113,33 +197,41 @@ impl ActionListItem {
     pub fn n_actions(&self) -> usize {
         match self {
             ActionListItem::Track(.., actions)
+            | ActionListItem::Tracks(.., actions)
             | ActionListItem::Artist(.., actions)
             | ActionListItem::Album(.., actions)
             | ActionListItem::Playlist(.., actions)
             | ActionListItem::Show(.., actions)
-            | ActionListItem::Episode(.., actions) => actions.len(),
+            | ActionListItem::Episode(.., actions)
+            | ActionListItem::Audiobook(.., actions) => actions.len(),
         }
     }
 
-    pub fn name(&self) -> &str {
-        match self {
+    pub fn name(&self) -> Cow<'_, str> {
+        Cow::Borrowed(match self {
             ActionListItem::Track(track, ..) => &track.name,
+            ActionListItem::Tracks(tracks, ..) => {
+                return Cow::Owned(format!("{} marked tracks", tracks.len()))
+            }
             ActionListItem::Artist(artist, ..) => &artist.name,
             ActionListItem::Album(album, ..) => &album.name,
             ActionListItem::Playlist(playlist, ..) => &playlist.name,
             ActionListItem::Show(show, ..) => &show.name,
             ActionListItem::Episode(episode, ..) => &episode.name,
-        }
+            ActionListItem::Audiobook(audiobook, ..) => &audiobook.name,
+        })
     }
 
     pub fn actions_desc(&self) -> Vec<String> {
         match self {
             ActionListItem::Track(.., actions)
+            | ActionListItem::Tracks(.., actions)
             | ActionListItem::Artist(.., actions)
             | ActionListItem::Album(.., actions)
             | ActionListItem::Playlist(.., actions)
             | ActionListItem::Show(.., actions)
-            | ActionListItem::Episode(.., actions) => {
+            | ActionListItem::Episode(.., actions)
+            | ActionListItem::Audiobook(.., actions) => {
                 actions.iter().map(|a| format!("{a:?}")).collect::<Vec<_>>()
             }
         }
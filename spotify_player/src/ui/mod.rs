@@ -1,10 +1,11 @@
 use crate::{
     config,
     state::{
-        Album, Artist, ArtistFocusState, BrowsePageUIState, Context, ContextPageUIState,
-        DataReadGuard, Id, LibraryFocusState, MutableWindowState, PageState, PageType,
-        PlaybackMetadata, PlaylistCreateCurrentField, PlaylistFolderItem, PlaylistPopupAction,
-        PopupState, SearchFocusState, SharedState, Track, UIStateGuard,
+        Album, Artist, ArtistAlbumSection, ArtistFocusState, BrowsePageUIState, Context,
+        ContextPageType, ContextPageUIState, DataReadGuard, Id, LibraryFocusState, MouseTarget,
+        MutableWindowState, PageState, PageType, PlaybackMetadata, PlaylistCreateCurrentField,
+        PlaylistFolderItem, PlaylistPopupAction, PopupState, SearchFocusState, SharedState, Track,
+        UIStateGuard, WindowType,
     },
 };
 use anyhow::{Context as AnyhowContext, Result};
@@ -31,8 +32,12 @@ pub mod single_line_input;
 mod utils;
 
 /// Run the application UI
-pub fn run(state: &SharedState) -> Result<()> {
-    let mut terminal = init_ui().context("failed to initialize the application's UI")?;
+///
+/// `keyboard_enhancement` specifies whether the terminal supports the keyboard enhancement protocol,
+/// which is used to disambiguate key events with modifiers (e.g `C-i` and `tab`, `S-up`, `s-x`)
+pub fn run(state: &SharedState, keyboard_enhancement: bool) -> Result<()> {
+    let mut terminal =
+        init_ui(keyboard_enhancement).context("failed to initialize the application's UI")?;
 
     let ui_refresh_duration = std::time::Duration::from_millis(
         config::get_config().app_config.app_refresh_duration_in_ms,
@@ -43,7 +48,7 @@ pub fn run(state: &SharedState) -> Result<()> {
         {
             let mut ui = state.ui.lock();
             if !ui.is_running {
-                clean_up(terminal).context("clean up UI resources")?;
+                clean_up(terminal, keyboard_enhancement).context("clean up UI resources")?;
                 std::process::exit(0);
             }
 
@@ -74,7 +79,7 @@ pub fn run(state: &SharedState) -> Result<()> {
 }
 
 // initialize the application's UI
-fn init_ui() -> Result<Terminal> {
+fn init_ui(keyboard_enhancement: bool) -> Result<Terminal> {
     let mut stdout = std::io::stdout();
     crossterm::terminal::enable_raw_mode()?;
     crossterm::execute!(
@@ -82,6 +87,15 @@ fn init_ui() -> Result<Terminal> {
         crossterm::terminal::EnterAlternateScreen,
         crossterm::event::EnableMouseCapture
     )?;
+    if keyboard_enhancement {
+        crossterm::execute!(
+            stdout,
+            crossterm::event::PushKeyboardEnhancementFlags(
+                crossterm::event::KeyboardEnhancementFlags::DISAMBIGUATE_ESCAPE_CODES
+                    | crossterm::event::KeyboardEnhancementFlags::REPORT_ALTERNATE_KEYS
+            )
+        )?;
+    }
     let backend = tui::backend::CrosstermBackend::new(stdout);
     let mut terminal = tui::Terminal::new(backend)?;
     terminal.clear()?;
@@ -89,7 +103,13 @@ fn init_ui() -> Result<Terminal> {
 }
 
 /// Clean up UI resources before quitting the application
-fn clean_up(mut terminal: Terminal) -> Result<()> {
+fn clean_up(mut terminal: Terminal, keyboard_enhancement: bool) -> Result<()> {
+    if keyboard_enhancement {
+        crossterm::execute!(
+            terminal.backend_mut(),
+            crossterm::event::PopKeyboardEnhancementFlags
+        )?;
+    }
     crossterm::terminal::disable_raw_mode()?;
     crossterm::execute!(
         terminal.backend_mut(),
@@ -104,6 +124,25 @@ fn clean_up(mut terminal: Terminal) -> Result<()> {
 fn render_application(frame: &mut Frame, state: &SharedState, ui: &mut UIStateGuard, rect: Rect) {
     // rendering order: playback window -> shortcut help popup -> other popups -> main layout
 
+    // clickable regions are registered again when rendering the new frame
+    ui.mouse_regions.clear();
+
+    // render only a single-line playback status in the mini mode, which is also used
+    // if the terminal is too short to render the application's full layout
+    if ui.is_mini_mode || rect.height < config::get_config().app_config.mini_mode_height_threshold {
+        playback::render_mini_playback_window(frame, state, ui, rect);
+
+        // popups are rendered below the playback status, so that popups opened in the mini mode are visible
+        let rect = Rect {
+            y: rect.y + 1,
+            height: rect.height.saturating_sub(1),
+            ..rect
+        };
+        let rect = popup::render_shortcut_help_popup(frame, ui, rect);
+        popup::render_popup(frame, state, ui, rect);
+        return;
+    }
+
     // render playback window before other popups and windows to ensure nothing is rendered on top
     // of the playback window, which is to avoid "duplicated images" issue
     // See: https://github.com/aome510/spotify-player/issues/498
@@ -116,7 +155,17 @@ fn render_application(frame: &mut Frame, state: &SharedState, ui: &mut UIStateGu
     render_main_layout(is_active, frame, state, ui, rect);
 }
 
-/// Render the application's main layout
+/// Get the layout of the application's main area in a given orientation
+fn pane_layout(orientation: Orientation) -> &'static config::PaneLayout {
+    let layout = &config::get_config().app_config.layout;
+    match orientation {
+        Orientation::Horizontal => &layout.horizontal,
+        Orientation::Vertical => &layout.vertical,
+    }
+}
+
+/// Render the application's main layout, which consists of the current page
+/// and other panes specified in the layout configurations
 fn render_main_layout(
     is_active: bool,
     frame: &mut Frame,
@@ -124,6 +173,43 @@ fn render_main_layout(
     ui: &mut UIStateGuard,
     rect: Rect,
 ) {
+    let layout = pane_layout(ui.orientation);
+    let chunks = ui
+        .orientation
+        .layout(layout.0.iter().map(|pane| {
+            pane.percent
+                .map_or(Constraint::Fill(1), Constraint::Percentage)
+        }))
+        .split(rect);
+
+    for (pane, rect) in layout.0.iter().zip(chunks.iter()) {
+        match pane.pane_type {
+            config::PaneType::Page => render_page(is_active, frame, state, ui, *rect),
+            config::PaneType::Queue => page::render_queue_window(frame, state, ui, *rect, false),
+            config::PaneType::Lyrics => page::render_playing_lyrics_window(frame, state, ui, *rect),
+            config::PaneType::Cover => {
+                playback::render_cover_image_window(frame, state, ui, *rect);
+            }
+        }
+    }
+}
+
+/// Render the current page, together with a tab bar if there are multiple opened tabs
+fn render_page(
+    is_active: bool,
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+) {
+    let rect = if ui.tabs.len() > 1 {
+        let chunks = Layout::vertical([Constraint::Length(1), Constraint::Fill(0)]).split(rect);
+        render_tab_bar(frame, state, ui, chunks[0]);
+        chunks[1]
+    } else {
+        rect
+    };
+
     let page_type = ui.current_page().page_type();
     match page_type {
         PageType::Library => page::render_library_page(is_active, frame, state, ui, rect),
@@ -136,6 +222,55 @@ fn render_main_layout(
     }
 }
 
+/// Render a clickable bar listing the opened tabs
+fn render_tab_bar(frame: &mut Frame, state: &SharedState, ui: &mut UIStateGuard, rect: Rect) {
+    let titles = {
+        let data = state.data.read();
+        ui.tabs
+            .iter()
+            .enumerate()
+            .map(|(i, tab)| {
+                let title = match tab.current_page() {
+                    PageState::Context {
+                        context_page_type: ContextPageType::Browsing(id),
+                        ..
+                    } => data.caches.context.get(&id.uri()).map_or_else(
+                        || ContextPageType::Browsing(id.clone()).title(),
+                        |context| context.name().to_string(),
+                    ),
+                    PageState::Context {
+                        context_page_type, ..
+                    } => context_page_type.title(),
+                    PageState::Search { current_query, .. } if !current_query.is_empty() => {
+                        format!("Search: {current_query}")
+                    }
+                    PageState::Lyrics { track, .. } => format!("Lyrics: {track}"),
+                    page => format!("{:?}", page.page_type()),
+                };
+                format!(" {}: {title} ", i + 1)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut spans = vec![];
+    let mut x = rect.x;
+    for (i, title) in titles.into_iter().enumerate() {
+        let style = if i == ui.current_tab {
+            ui.theme.selection(true)
+        } else {
+            ui.theme.block_title()
+        };
+        let span = Span::styled(title, style);
+        let width = (span.width() as u16).min(rect.right().saturating_sub(x));
+        ui.add_mouse_region(Rect::new(x, rect.y, width, 1), MouseTarget::Tab(i));
+        x = x.saturating_add(width + 1);
+        spans.push(span);
+        spans.push(Span::raw("|"));
+    }
+    spans.pop();
+    frame.render_widget(Paragraph::new(Line::from(spans)), rect);
+}
+
 #[derive(Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Orientation {
     Vertical,
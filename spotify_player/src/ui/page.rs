@@ -3,18 +3,25 @@ use std::{
     fmt::Display,
 };
 
-use tui::text::Line;
+use tui::text::{Line, Span};
 
-use crate::{state::Episode, utils::format_duration};
+use crate::{
+    command::{Command, CommandOrAction},
+    config::TrackTableColumnType,
+    state::{Chapter, Episode, Show, TrackOrder},
+    utils::format_duration,
+};
 
 use super::{
-    config, utils, utils::construct_and_render_block, Album, Artist, ArtistFocusState, Borders,
-    BrowsePageUIState, Cell, Constraint, Context, ContextPageUIState, DataReadGuard, Frame, Id,
-    Layout, LibraryFocusState, MutableWindowState, Orientation, PageState, Paragraph,
-    PlaylistFolderItem, Rect, Row, SearchFocusState, SharedState, Style, Table, Track,
-    UIStateGuard,
+    config, utils, utils::construct_and_render_block, Album, Artist, ArtistAlbumSection,
+    ArtistFocusState, Borders, BrowsePageUIState, Cell, Constraint, Context, ContextPageUIState,
+    DataReadGuard, Frame, Id, Layout, LibraryFocusState, MouseTarget, MutableWindowState,
+    Orientation, PageState, Paragraph, PlaylistFolderItem, Rect, Row, SearchFocusState,
+    SharedState, Style, Table, TableState, Track, UIStateGuard, WindowType,
 };
 
+const TRACK_TABLE_COLUMN_SPACING: u16 = 2;
+
 const COMMAND_TABLE_CONSTRAINTS: [Constraint; 3] = [
     Constraint::Percentage(25),
     Constraint::Percentage(25),
@@ -61,30 +68,33 @@ pub fn render_search_page(
     let search_input_rect = chunks[0];
     let rect = chunks[1];
 
-    // track/album/artist/playlist/show/episode search results layout
+    // track/album/artist/playlist/show/episode/audiobook search results layout
     let chunks = match ui.orientation {
-        // 1x6
+        // 1x7
         Orientation::Vertical => {
             let constraints = if focus_state == SearchFocusState::Input {
-                [Constraint::Ratio(1, 6); 6]
+                [Constraint::Ratio(1, 7); 7]
             } else {
-                let mut constraints = [Constraint::Percentage(15); 6];
-                constraints[focus_state as usize - 1] = Constraint::Percentage(25);
+                let mut constraints = [Constraint::Percentage(12); 7];
+                constraints[focus_state as usize - 1] = Constraint::Percentage(28);
                 constraints
             };
 
             Layout::vertical(constraints).split(rect)
         }
-        // 2x3
-        Orientation::Horizontal => Layout::vertical([Constraint::Ratio(1, 3); 3])
-            .split(rect)
-            .iter()
-            .flat_map(|rect| {
-                Layout::horizontal([Constraint::Ratio(1, 2); 2])
-                    .split(*rect)
-                    .to_vec()
-            })
-            .collect(),
+        // 2x3, followed by a full-width audiobook window
+        Orientation::Horizontal => {
+            let rows = Layout::vertical([Constraint::Ratio(1, 4); 4]).split(rect);
+            rows[..3]
+                .iter()
+                .flat_map(|rect| {
+                    Layout::horizontal([Constraint::Ratio(1, 2); 2])
+                        .split(*rect)
+                        .to_vec()
+                })
+                .chain([rows[3]])
+                .collect()
+        }
     };
 
     let track_rect = construct_and_render_block(
@@ -126,6 +136,8 @@ pub fn render_search_page(
     );
     let episode_rect =
         construct_and_render_block("Episodes", &ui.theme, Borders::TOP, frame, chunks[5]);
+    let audiobook_rect =
+        construct_and_render_block("Audiobooks", &ui.theme, Borders::TOP, frame, chunks[6]);
 
     // 3. Construct the page's widgets
     let (track_list, n_tracks) = {
@@ -187,6 +199,16 @@ pub fn render_search_page(
         utils::construct_list_widget(&ui.theme, episode_items, is_active)
     };
 
+    let (audiobook_list, n_audiobooks) = {
+        let audiobook_items = search_results
+            .map(|s| search_items(&s.audiobooks))
+            .unwrap_or_default();
+
+        let is_active = is_active && focus_state == SearchFocusState::Audiobooks;
+
+        utils::construct_list_widget(&ui.theme, audiobook_items, is_active)
+    };
+
     // 4. Render the page's widgets
     // Render the query input box
     frame.render_widget(
@@ -194,6 +216,19 @@ pub fn render_search_page(
         search_input_rect,
     );
 
+    for (rect, window, len) in [
+        (search_input_rect, WindowType::SearchInput, 0),
+        (track_rect, WindowType::Tracks, n_tracks),
+        (album_rect, WindowType::Albums, n_albums),
+        (artist_rect, WindowType::Artists, n_artists),
+        (playlist_rect, WindowType::Playlists, n_playlists),
+        (show_rect, WindowType::Shows, n_shows),
+        (episode_rect, WindowType::Episodes, n_episodes),
+        (audiobook_rect, WindowType::Audiobooks, n_audiobooks),
+    ] {
+        ui.add_mouse_region(rect, MouseTarget::Window { window, len });
+    }
+
     // Render the search result windows.
     // Need mutable access to the list/table states stored inside the page state for rendering.
     let PageState::Search {
@@ -244,6 +279,13 @@ pub fn render_search_page(
         n_episodes,
         &mut page_state.episode_list,
     );
+    utils::render_list_window(
+        frame,
+        audiobook_list,
+        audiobook_rect,
+        n_audiobooks,
+        &mut page_state.audiobook_list,
+    );
 }
 
 pub fn render_context_page(
@@ -263,36 +305,63 @@ pub fn render_context_page(
         return;
     };
 
+    let data = state.data.read();
+    let context = id
+        .as_ref()
+        .and_then(|id| data.caches.context.get(&id.uri()));
+
     // 2. Construct the page's layout
-    let rect = construct_and_render_block(
-        &context_page_type.title(),
-        &ui.theme,
-        Borders::ALL,
-        frame,
-        rect,
-    );
+    let mut title = context_page_type.title();
+    // show the number of filtered tracks (or episodes) when searching in the page
+    if ui.search_query().is_some() {
+        let counts = match context {
+            Some(Context::Show { episodes, .. } | Context::Episodes { episodes, .. }) => Some((
+                ui.search_filtered_items(episodes).len(),
+                episodes.len(),
+                "episodes",
+            )),
+            Some(Context::Audiobook { chapters, .. }) => Some((
+                ui.search_filtered_items(chapters).len(),
+                chapters.len(),
+                "chapters",
+            )),
+            Some(
+                Context::Album { tracks, .. }
+                | Context::Playlist { tracks, .. }
+                | Context::Tracks { tracks, .. }
+                | Context::Artist {
+                    top_tracks: tracks, ..
+                },
+            ) => Some((
+                ui.search_filtered_tracks(tracks, &data.user_data).len(),
+                tracks.len(),
+                "tracks",
+            )),
+            None => None,
+        };
+        if let Some((n_filtered, n_items, typ)) = counts {
+            title = format!("{title} ({n_filtered}/{n_items} {typ})");
+        }
+    }
+    let rect = construct_and_render_block(&title, &ui.theme, Borders::ALL, frame, rect);
 
     // 3+4. Construct and render the page's widgets
-    let id = match id {
-        None => {
-            frame.render_widget(
-                Paragraph::new("Cannot determine the current page's context"),
-                rect,
-            );
-            return;
-        }
-        Some(id) => id,
-    };
+    if id.is_none() {
+        frame.render_widget(
+            Paragraph::new("Cannot determine the current page's context"),
+            rect,
+        );
+        return;
+    }
 
-    let data = state.data.read();
-    match data.caches.context.get(&id.uri()) {
+    match context {
         Some(context) => {
             // render context description
-            let chunks = Layout::vertical([Constraint::Length(1), Constraint::Fill(0)]).split(rect);
-            frame.render_widget(
-                Paragraph::new(context.description()).style(ui.theme.page_desc()),
-                chunks[0],
-            );
+            let desc = context.description();
+            let desc_height = u16::try_from(desc.lines().count()).unwrap_or(1);
+            let chunks = Layout::vertical([Constraint::Length(desc_height), Constraint::Fill(0)])
+                .split(rect);
+            frame.render_widget(Paragraph::new(desc).style(ui.theme.page_desc()), chunks[0]);
             let rect = chunks[1];
 
             match context {
@@ -331,7 +400,7 @@ pub fn render_context_page(
                         rect,
                         is_active,
                         state,
-                        ui.search_filtered_items(tracks),
+                        ui.search_filtered_tracks(tracks, &data.user_data),
                         ui,
                         &data,
                     );
@@ -342,12 +411,12 @@ pub fn render_context_page(
                         rect,
                         is_active,
                         state,
-                        ui.search_filtered_items(tracks),
+                        ui.search_filtered_tracks(tracks, &data.user_data),
                         ui,
                         &data,
                     );
                 }
-                Context::Show { episodes, .. } => {
+                Context::Show { episodes, .. } | Context::Episodes { episodes, .. } => {
                     render_episode_table(
                         frame,
                         rect,
@@ -355,6 +424,16 @@ pub fn render_context_page(
                         state,
                         ui.search_filtered_items(episodes),
                         ui,
+                        &data,
+                    );
+                }
+                Context::Audiobook { chapters, .. } => {
+                    render_chapter_table(
+                        frame,
+                        rect,
+                        is_active,
+                        ui.search_filtered_items(chapters),
+                        ui,
                     );
                 }
             }
@@ -383,20 +462,22 @@ pub fn render_library_page(
     };
 
     // 2. Construct the page's layout
-    // Split the library page into 3 windows:
+    // Split the library page into 4 windows:
     // - a playlists window
     // - a saved albums window
     // - a followed artists window
+    // - a saved shows window
 
+    let library_layout = &configs.app_config.layout.library;
+    let remaining_percent = 100 - (library_layout.album_percent + library_layout.playlist_percent);
+    let show_percent = library_layout.show_percent.unwrap_or(remaining_percent / 2);
     let chunks = ui
         .orientation
         .layout([
-            Constraint::Percentage(configs.app_config.layout.library.playlist_percent),
-            Constraint::Percentage(configs.app_config.layout.library.album_percent),
-            Constraint::Percentage(
-                100 - (configs.app_config.layout.library.album_percent
-                    + configs.app_config.layout.library.playlist_percent),
-            ),
+            Constraint::Percentage(library_layout.playlist_percent),
+            Constraint::Percentage(library_layout.album_percent),
+            Constraint::Percentage(remaining_percent - show_percent),
+            Constraint::Percentage(show_percent),
         ])
         .split(rect);
 
@@ -420,8 +501,17 @@ pub fn render_library_page(
         frame,
         chunks[1],
     );
-    let artist_rect =
-        construct_and_render_block("Artists", &ui.theme, Borders::ALL, frame, chunks[2]);
+    let artist_rect = construct_and_render_block(
+        "Artists",
+        &ui.theme,
+        match ui.orientation {
+            Orientation::Horizontal => Borders::TOP | Borders::LEFT | Borders::BOTTOM,
+            Orientation::Vertical => Borders::ALL,
+        },
+        frame,
+        chunks[2],
+    );
+    let show_rect = construct_and_render_block("Shows", &ui.theme, Borders::ALL, frame, chunks[3]);
 
     // 3. Construct the page's widgets
     // Construct the playlist window
@@ -439,9 +529,7 @@ pub fn render_library_page(
     let (playlist_list, n_playlists) = utils::construct_list_widget(
         &ui.theme,
         items,
-        is_active
-            && focus_state != LibraryFocusState::SavedAlbums
-            && focus_state != LibraryFocusState::FollowedArtists,
+        is_active && focus_state == LibraryFocusState::Playlists,
     );
     // Construct the saved album window
     let (album_list, n_albums) = utils::construct_list_widget(
@@ -461,8 +549,31 @@ pub fn render_library_page(
             .collect(),
         is_active && focus_state == LibraryFocusState::FollowedArtists,
     );
+    // Construct the saved show window
+    let (show_list, n_shows) = utils::construct_list_widget(
+        &ui.theme,
+        ui.search_filtered_items(&data.user_data.saved_shows)
+            .into_iter()
+            .map(|s| {
+                (
+                    saved_show_desc(s, &data),
+                    curr_context_uri == Some(s.id.uri()),
+                )
+            })
+            .collect(),
+        is_active && focus_state == LibraryFocusState::SavedShows,
+    );
 
     // 4. Render the page's widgets
+    for (rect, window, len) in [
+        (playlist_rect, WindowType::Playlists, n_playlists),
+        (album_rect, WindowType::Albums, n_albums),
+        (artist_rect, WindowType::Artists, n_artists),
+        (show_rect, WindowType::Shows, n_shows),
+    ] {
+        ui.add_mouse_region(rect, MouseTarget::Window { window, len });
+    }
+
     // Render the library page's windows.
     // Will need mutable access to the list/table states stored inside the page state for rendering.
     let PageState::Library { state: page_state } = ui.current_page_mut() else {
@@ -490,6 +601,34 @@ pub fn render_library_page(
         n_artists,
         &mut page_state.followed_artist_list,
     );
+    utils::render_list_window(
+        frame,
+        show_list,
+        show_rect,
+        n_shows,
+        &mut page_state.saved_show_list,
+    );
+}
+
+/// The description of a saved show in the library window.
+///
+/// Once the show's episodes are summarized, the description also includes the release date
+/// of the show's latest episode and the number of unplayed episodes among its latest episodes.
+fn saved_show_desc(show: &Show, data: &DataReadGuard) -> String {
+    match data.user_data.saved_show_summaries.get(&show.id.uri()) {
+        Some(summary) => {
+            let n_unplayed_episodes = summary
+                .latest_episodes
+                .iter()
+                .filter(|e| !data.user_data.is_played_episode(e))
+                .count();
+            format!(
+                "{show} • {} • {n_unplayed_episodes} unplayed",
+                summary.latest_release_date
+            )
+        }
+        None => show.to_string(),
+    }
 }
 
 pub fn render_browse_page(
@@ -541,6 +680,9 @@ pub fn render_browse_page(
     };
 
     // 4. Render the page's widget
+    if let Some(window) = ui.current_page().focus_window_type() {
+        ui.add_mouse_region(rect, MouseTarget::Window { window, len });
+    }
     let Some(MutableWindowState::List(list_state)) = ui.current_page_mut().focus_window_state_mut()
     else {
         return;
@@ -554,6 +696,55 @@ pub fn render_lyrics_page(
     state: &SharedState,
     ui: &mut UIStateGuard,
     rect: Rect,
+) {
+    let PageState::Lyrics {
+        track_uri,
+        track,
+        artists,
+    } = ui.current_page()
+    else {
+        return;
+    };
+    let (track_uri, desc) = (track_uri.clone(), format!("{track} by {artists}"));
+    render_lyrics_window(frame, state, ui, rect, &track_uri, &desc);
+}
+
+/// Render a lyrics window (pane) for the currently playing track
+pub fn render_playing_lyrics_window(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+) {
+    let track = match state.player.read().currently_playing() {
+        Some(rspotify::model::PlayableItem::Track(track)) => track.id.as_ref().map(|id| {
+            (
+                id.uri(),
+                format!(
+                    "{} by {}",
+                    track.name,
+                    crate::utils::map_join(&track.artists, |a| &a.name, ", ")
+                ),
+            )
+        }),
+        _ => None,
+    };
+    if let Some((track_uri, desc)) = track {
+        render_lyrics_window(frame, state, ui, rect, &track_uri, &desc);
+    } else {
+        let rect = construct_and_render_block("Lyrics", &ui.theme, Borders::ALL, frame, rect);
+        frame.render_widget(Paragraph::new("No track playing"), rect);
+    }
+}
+
+/// Render a lyrics window of a track with a given description
+fn render_lyrics_window(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+    track_uri: &str,
+    desc: &str,
 ) {
     // 1. Get data
     let data = state.data.read();
@@ -568,15 +759,6 @@ pub fn render_lyrics_page(
         return;
     };
 
-    let PageState::Lyrics {
-        track_uri,
-        track,
-        artists,
-    } = ui.current_page_mut()
-    else {
-        return;
-    };
-
     let lyrics = match data.caches.lyrics.get(track_uri) {
         None => {
             frame.render_widget(Paragraph::new("Loading..."), rect);
@@ -592,7 +774,7 @@ pub fn render_lyrics_page(
     // 4. Render the page's widgets
     // render lyric page description text
     frame.render_widget(
-        Paragraph::new(format!("{track} by {artists}")).style(ui.theme.page_desc()),
+        Paragraph::new(desc.to_string()).style(ui.theme.page_desc()),
         chunks[0],
     );
 
@@ -638,13 +820,19 @@ pub fn render_commands_help_page(frame: &mut Frame, ui: &mut UIStateGuard, rect:
         .into_iter()
         .filter(|km| km.include_in_help_screen())
         .for_each(|km| {
+            // scoped keymaps are shown with their scopes
+            let key = if km.scope.is_global() {
+                format!("\"{}\"", km.key_sequence)
+            } else {
+                format!("\"{}\" ({})", km.key_sequence, km.scope)
+            };
             let v = map.entry(km.command);
             match v {
                 Entry::Vacant(v) => {
-                    v.insert(format!("\"{}\"", km.key_sequence));
+                    v.insert(key);
                 }
                 Entry::Occupied(mut v) => {
-                    let keys = format!("{}, \"{}\"", v.get(), km.key_sequence);
+                    let keys = format!("{}, {key}", v.get());
                     *v.get_mut() = keys;
                 }
             }
@@ -704,6 +892,18 @@ pub fn render_queue_page(
     state: &SharedState,
     ui: &mut UIStateGuard,
     rect: Rect,
+) {
+    render_queue_window(frame, state, ui, rect, true);
+}
+
+/// Render a queue window, which is scrollable if it's rendered as the queue page
+/// instead of a pane next to the current page
+pub fn render_queue_window(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+    is_page: bool,
 ) {
     use rspotify::model::{FullEpisode, FullTrack, PlayableItem};
     fn get_playable_name(item: &PlayableItem) -> String {
@@ -739,13 +939,14 @@ pub fn render_queue_page(
     let scroll_offset = match ui.current_page_mut() {
         PageState::Queue {
             ref mut scroll_offset,
-        } => {
+        } if is_page => {
             if !queue.is_empty() && *scroll_offset >= queue.len() {
                 *scroll_offset = queue.len() - 1;
             }
             *scroll_offset
         }
-        _ => return,
+        _ if is_page => return,
+        _ => 0,
     };
 
     // 2. Construct the page's layout
@@ -800,21 +1001,26 @@ fn render_artist_context_page_windows(
     rect: Rect,
     artist_data: (&[Track], &[Album], &[Artist]),
 ) {
-    let configs = config::get_config();
     // 1. Get data
     let (tracks, mut albums, artists) = (
-        ui.search_filtered_items(artist_data.0),
+        ui.search_filtered_tracks(artist_data.0, &data.user_data),
         ui.search_filtered_items(artist_data.1),
         ui.search_filtered_items(artist_data.2),
     );
 
-    let focus_state = match ui.current_page() {
+    let (focus_state, album_section) = match ui.current_page() {
         PageState::Context {
-            state: Some(ContextPageUIState::Artist { focus, .. }),
+            state:
+                Some(ContextPageUIState::Artist {
+                    focus,
+                    album_section,
+                    ..
+                }),
             ..
-        } => *focus,
+        } => (*focus, *album_section),
         _ => return,
     };
+    albums.retain(|a| album_section.contains(a));
 
     // 2. Construct the page's layout
     // top tracks window
@@ -824,7 +1030,7 @@ fn render_artist_context_page_windows(
     // albums and related artitsts windows
     let chunks = Layout::horizontal([Constraint::Ratio(1, 2); 2]).split(chunks[1]);
     let albums_rect = construct_and_render_block(
-        "Albums",
+        "",
         &ui.theme,
         Borders::TOP | Borders::RIGHT,
         frame,
@@ -833,23 +1039,39 @@ fn render_artist_context_page_windows(
     let related_artists_rect =
         construct_and_render_block("Related Artists", &ui.theme, Borders::TOP, frame, chunks[1]);
 
-    // 3. Construct the page's widgets
-    // album table
-    if configs.app_config.sort_artist_albums_by_type {
-        fn get_priority(album_type: &str) -> usize {
-            match album_type {
-                "album" => 0,
-                "single" => 1,
-                "appears_on" => 2,
-                "compilation" => 3,
-                _ => 4,
-            }
-        }
-        albums.sort_by_key(|a| get_priority(&a.album_type()));
+    // the album window's title consists of tabs of the album sections
+    let mut spans = vec![];
+    let mut x = chunks[0].x;
+    for section in ArtistAlbumSection::ALL {
+        let style = if section == album_section {
+            ui.theme.selection(true)
+        } else {
+            ui.theme.block_title()
+        };
+        let span = Span::styled(section.title(), style);
+        let width = (span.width() as u16).min(chunks[0].right().saturating_sub(x));
+        ui.add_mouse_region(
+            Rect::new(x, chunks[0].y, width, 1),
+            MouseTarget::ArtistAlbumSection(section),
+        );
+        x = x.saturating_add(width + 1);
+        spans.push(span);
+        spans.push(Span::styled("|", ui.theme.block_title()));
     }
+    spans.pop();
+    frame.render_widget(
+        Paragraph::new(Line::from(spans)),
+        Rect {
+            height: 1,
+            ..chunks[0]
+        },
+    );
 
+    // 3. Construct the page's widgets
+    // album table, in which albums are grouped by their release years
     let is_albums_active = is_active && focus_state == ArtistFocusState::Albums;
     let n_albums = albums.len();
+    let album_table_rows = group_table_rows(&albums, |a| a.year());
     let album_rows = albums
         .into_iter()
         .map(|a| {
@@ -861,6 +1083,16 @@ fn render_artist_context_page_windows(
             .style(Style::default())
         })
         .collect::<Vec<_>>();
+    let album_rows = match album_table_rows {
+        Some(ref table_rows) => insert_separator_rows(
+            album_rows,
+            table_rows,
+            (0, 3),
+            Clone::clone,
+            ui.theme.table_header(),
+        ),
+        None => album_rows,
+    };
 
     let albums_table = Table::new(
         album_rows,
@@ -906,6 +1138,14 @@ fn render_artist_context_page_windows(
         data,
     );
 
+    ui.add_mouse_region(
+        related_artists_rect,
+        MouseTarget::Window {
+            window: WindowType::Artists,
+            len: n_artists,
+        },
+    );
+
     let PageState::Context {
         state:
             Some(ContextPageUIState::Artist {
@@ -919,7 +1159,6 @@ fn render_artist_context_page_windows(
         return;
     };
 
-    utils::render_table_window(frame, albums_table, albums_rect, n_albums, album_table);
     utils::render_list_window(
         frame,
         artist_list,
@@ -927,6 +1166,33 @@ fn render_artist_context_page_windows(
         n_artists,
         related_artist_list,
     );
+    if let Some(table_rows) = album_table_rows {
+        let offset = render_grouped_table_window(
+            frame,
+            albums_table,
+            albums_rect,
+            &table_rows,
+            n_albums,
+            album_table,
+        );
+        add_grouped_table_mouse_regions(
+            ui,
+            table_rows_rect(albums_rect),
+            WindowType::Albums,
+            &table_rows,
+            offset,
+            |_| None,
+        );
+    } else {
+        utils::render_table_window(frame, albums_table, albums_rect, n_albums, album_table);
+        ui.add_mouse_region(
+            table_rows_rect(albums_rect),
+            MouseTarget::Window {
+                window: WindowType::Albums,
+                len: n_albums,
+            },
+        );
+    }
 }
 
 fn render_track_table(
@@ -958,76 +1224,337 @@ fn render_track_table(
         }
     }
 
+    let track_table_config = &configs.app_config.track_table;
+    let columns = match ui.current_page() {
+        PageState::Context {
+            state: Some(state), ..
+        } => match state {
+            ContextPageUIState::Playlist { .. } => track_table_config.playlist.as_ref(),
+            ContextPageUIState::Album { .. } => track_table_config.album.as_ref(),
+            ContextPageUIState::Artist { .. } => track_table_config.artist.as_ref(),
+            ContextPageUIState::Tracks { .. } => track_table_config.tracks.as_ref(),
+            ContextPageUIState::Show { .. } | ContextPageUIState::Audiobook { .. } => None,
+        },
+        _ => None,
+    }
+    .unwrap_or(&track_table_config.default)
+    .0
+    .as_slice();
+    // the explicit label is not needed in track's title if there is an explicit column
+    let has_explicit_column = columns
+        .iter()
+        .any(|c| c.column_type == TrackTableColumnType::Explicit);
+
+    // marked tracks and tracks in the range being marked are highlighted
+    let context_uri = ui.current_page().context_uri().unwrap_or_default();
+    let selected = ui.current_page_mut().selected().unwrap_or_default();
+    let mark_range = ui
+        .track_marks
+        .range_start
+        .filter(|_| ui.track_marks.is_in_context(&context_uri))
+        .map(|start| std::cmp::min(start, selected)..=std::cmp::max(start, selected));
+
+    // tracks of an album with multiple discs are separated by their discs
+    let is_album_page = matches!(
+        ui.current_page(),
+        PageState::Context {
+            state: Some(ContextPageUIState::Album { .. }),
+            ..
+        }
+    );
+    let table_rows = if is_album_page && tracks.iter().any(|t| t.disc_number > 1) {
+        group_table_rows(&tracks, |t| t.disc_number)
+    } else {
+        None
+    };
+
     let n_tracks = tracks.len();
     let rows = tracks
         .into_iter()
         .enumerate()
         .map(|(id, t)| {
+            let is_marked = ui.track_marks.is_marked(&context_uri, t)
+                || mark_range.as_ref().is_some_and(|r| r.contains(&id));
             let (id, style) = if playing_track_uri == t.id.uri() {
                 (playing_id.to_string(), ui.theme.current_playing())
             } else {
                 ((id + 1).to_string(), Style::default())
             };
-            Row::new(vec![
-                if data.user_data.is_liked_track(t) {
-                    Cell::from(&configs.app_config.liked_icon as &str).style(ui.theme.like())
+            let style = if is_marked {
+                style.patch(ui.theme.marked())
+            } else {
+                style
+            };
+            let cells = columns.iter().map(|column| match column.column_type {
+                TrackTableColumnType::Liked => {
+                    if data.user_data.is_liked_track(t) {
+                        Cell::from(&configs.app_config.liked_icon as &str).style(ui.theme.like())
+                    } else {
+                        Cell::from("")
+                    }
+                }
+                TrackTableColumnType::Number => Cell::from(id.clone()),
+                TrackTableColumnType::Title => Cell::from(if has_explicit_column {
+                    t.name.clone()
                 } else {
-                    Cell::from("")
-                },
-                Cell::from(id),
-                Cell::from(t.display_name()),
-                Cell::from(t.artists_info()),
-                Cell::from(t.album_info()),
-                Cell::from(format!(
+                    t.display_name().into_owned()
+                }),
+                TrackTableColumnType::Artists => Cell::from(t.artists_info()),
+                TrackTableColumnType::Album => Cell::from(t.album_info()),
+                TrackTableColumnType::Duration => Cell::from(format!(
                     "{}:{:02}",
                     t.duration.as_secs() / 60,
                     t.duration.as_secs() % 60,
                 )),
-            ])
-            .style(style)
+                TrackTableColumnType::Track => Cell::from(t.number.to_string()),
+                TrackTableColumnType::Disc => Cell::from(t.disc_number.to_string()),
+                TrackTableColumnType::Popularity => {
+                    Cell::from(t.popularity.map(|p| p.to_string()).unwrap_or_default())
+                }
+                TrackTableColumnType::ReleaseDate => Cell::from(t.release_date().to_string()),
+                TrackTableColumnType::AddedAt => Cell::from(
+                    chrono::DateTime::from_timestamp(t.added_at as i64, 0)
+                        .filter(|_| t.added_at > 0)
+                        .map(|time| time.format("%Y-%m-%d").to_string())
+                        .unwrap_or_default(),
+                ),
+                TrackTableColumnType::AddedBy => Cell::from(t.added_by.clone().unwrap_or_default()),
+                TrackTableColumnType::Explicit => Cell::from(if t.explicit { "E" } else { "" }),
+                TrackTableColumnType::PlayCount => Cell::from(
+                    data.user_data
+                        .play_counts
+                        .get(&t.id.uri())
+                        .map(ToString::to_string)
+                        .unwrap_or_default(),
+                ),
+            });
+            Row::new(cells.collect::<Vec<_>>()).style(style)
+        })
+        .collect::<Vec<_>>();
+    let rows = match table_rows {
+        Some(ref table_rows) => {
+            // a disc separator is labeled in the title column (or the first column if not exists)
+            let label_column = columns
+                .iter()
+                .position(|c| c.column_type == TrackTableColumnType::Title)
+                .unwrap_or_default();
+            insert_separator_rows(
+                rows,
+                table_rows,
+                (label_column, columns.len()),
+                |disc| format!("Disc {disc}"),
+                ui.theme.table_header(),
+            )
+        }
+        None => rows,
+    };
+    let widths = columns
+        .iter()
+        .map(|column| {
+            column.width.map_or_else(
+                || track_table_column_width(column.column_type),
+                Constraint::Length,
+            )
         })
         .collect::<Vec<_>>();
-    let track_table = Table::new(
-        rows,
-        [
-            Constraint::Length(configs.app_config.liked_icon.chars().count() as u16),
-            Constraint::Length(4),
-            Constraint::Fill(4),
-            Constraint::Fill(3),
-            Constraint::Fill(5),
-            Constraint::Fill(1),
-        ],
-    )
-    .header(
-        Row::new(vec![
-            Cell::from(""),
-            Cell::from("#"),
-            Cell::from("Title"),
-            Cell::from("Artists"),
-            Cell::from("Album"),
-            Cell::from("Duration"),
-        ])
-        .style(ui.theme.table_header()),
-    )
-    .column_spacing(2)
-    .row_highlight_style(ui.theme.selection(is_active));
 
-    if let PageState::Context {
+    // clicking a column's header sorts the table by the column
+    let header_rect = Rect { height: 1, ..rect };
+    let column_rects = Layout::horizontal(widths.clone())
+        .spacing(TRACK_TABLE_COLUMN_SPACING)
+        .split(header_rect);
+    for (column, column_rect) in columns.iter().zip(column_rects.iter()) {
+        if TrackOrder::from_column(column.column_type).is_some() {
+            ui.add_mouse_region(
+                *column_rect,
+                MouseTarget::Button(CommandOrAction::Command(Command::SortTrackByColumn {
+                    column: column.column_type,
+                })),
+            );
+        }
+    }
+
+    let track_table = Table::new(rows, widths)
+        .header(
+            Row::new(
+                columns
+                    .iter()
+                    .map(|column| Cell::from(track_table_column_header(column.column_type))),
+            )
+            .style(ui.theme.table_header()),
+        )
+        .column_spacing(TRACK_TABLE_COLUMN_SPACING)
+        .row_highlight_style(ui.theme.selection(is_active));
+
+    let PageState::Context {
         state: Some(state), ..
     } = ui.current_page_mut()
-    {
-        let playable_table_state = match state {
-            ContextPageUIState::Artist {
-                top_track_table, ..
-            } => top_track_table,
-            ContextPageUIState::Playlist { track_table }
-            | ContextPageUIState::Album { track_table }
-            | ContextPageUIState::Tracks { track_table } => track_table,
-            ContextPageUIState::Show { .. } => {
-                unreachable!("show's episode table should be handled by render_episode_table")
+    else {
+        return;
+    };
+    let playable_table_state = match state {
+        ContextPageUIState::Artist {
+            top_track_table, ..
+        } => top_track_table,
+        ContextPageUIState::Playlist { track_table }
+        | ContextPageUIState::Album { track_table }
+        | ContextPageUIState::Tracks { track_table } => track_table,
+        ContextPageUIState::Show { .. } | ContextPageUIState::Audiobook { .. } => {
+            unreachable!(
+                "show's episode table and audiobook's chapter table should be handled separately"
+            )
+        }
+    };
+
+    let Some(table_rows) = table_rows else {
+        utils::render_table_window(frame, track_table, rect, n_tracks, playable_table_state);
+        ui.add_mouse_region(
+            table_rows_rect(rect),
+            MouseTarget::Window {
+                window: WindowType::Tracks,
+                len: n_tracks,
+            },
+        );
+        return;
+    };
+
+    let offset = render_grouped_table_window(
+        frame,
+        track_table,
+        rect,
+        &table_rows,
+        n_tracks,
+        playable_table_state,
+    );
+    // clicking a disc separator plays the album from the disc
+    add_grouped_table_mouse_regions(
+        ui,
+        table_rows_rect(rect),
+        WindowType::Tracks,
+        &table_rows,
+        offset,
+        |disc| {
+            Some(MouseTarget::Button(CommandOrAction::Command(
+                Command::PlayDisc { disc: *disc },
+            )))
+        },
+    );
+}
+
+#[derive(Debug, PartialEq, Eq)]
+/// A row of a table whose items are separated into groups
+enum TableRow<G> {
+    /// an item given its index in the table's items
+    Item(usize),
+    /// a separator preceding a group of items
+    Separator(G),
+}
+
+/// Construct the rows of a table whose items are separated into groups by separator rows,
+/// returning `None` if items of a group are not consecutive (e.g. after sorting the table)
+fn group_table_rows<T, G: PartialEq>(
+    items: &[T],
+    group: impl Fn(&T) -> G,
+) -> Option<Vec<TableRow<G>>> {
+    let mut table_rows: Vec<TableRow<G>> = vec![];
+    for (id, item) in items.iter().enumerate() {
+        let item_group = group(item);
+        if id == 0 || group(&items[id - 1]) != item_group {
+            if table_rows
+                .iter()
+                .any(|row| matches!(row, TableRow::Separator(g) if *g == item_group))
+            {
+                return None;
             }
+            table_rows.push(TableRow::Separator(item_group));
+        }
+        table_rows.push(TableRow::Item(id));
+    }
+    Some(table_rows)
+}
+
+/// Insert separator rows, each of which is labeled in a given column, into the rows of a table's items
+fn insert_separator_rows<'a, G>(
+    rows: Vec<Row<'a>>,
+    table_rows: &[TableRow<G>],
+    (label_column, n_columns): (usize, usize),
+    label: impl Fn(&G) -> String,
+    style: Style,
+) -> Vec<Row<'a>> {
+    let mut rows = rows.into_iter();
+    table_rows
+        .iter()
+        .map(|row| match row {
+            TableRow::Item(_) => rows.next().expect("each item has a row"),
+            TableRow::Separator(group) => Row::new((0..n_columns).map(|i| {
+                if i == label_column {
+                    Cell::from(label(group))
+                } else {
+                    Cell::from("")
+                }
+            }))
+            .style(style),
+        })
+        .collect()
+}
+
+/// Render a table window whose items are separated into groups by separator rows,
+/// returning the table's offset.
+///
+/// The window's state stores the selected item's index and the table's offset in terms of the table's rows.
+fn render_grouped_table_window<G: PartialEq>(
+    frame: &mut Frame,
+    table: Table,
+    rect: Rect,
+    table_rows: &[TableRow<G>],
+    n_items: usize,
+    state: &mut TableState,
+) -> usize {
+    let selected = state
+        .selected()
+        .unwrap_or_default()
+        .min(n_items.saturating_sub(1));
+    state.select(Some(selected));
+    let mut rows_state = TableState::default()
+        .with_offset(state.offset())
+        .with_selected(
+            table_rows
+                .iter()
+                .position(|row| *row == TableRow::Item(selected)),
+        );
+    utils::render_table_window(frame, table, rect, table_rows.len(), &mut rows_state);
+    *state.offset_mut() = rows_state.offset();
+    rows_state.offset()
+}
+
+/// Register a clickable region for each visible row of a table whose items are separated into groups
+fn add_grouped_table_mouse_regions<G>(
+    ui: &mut UIStateGuard,
+    rows_rect: Rect,
+    window: WindowType,
+    table_rows: &[TableRow<G>],
+    offset: usize,
+    separator_target: impl Fn(&G) -> Option<MouseTarget>,
+) {
+    for (row, y) in table_rows
+        .iter()
+        .skip(offset)
+        .zip(rows_rect.top()..rows_rect.bottom())
+    {
+        let target = match row {
+            TableRow::Item(id) => Some(MouseTarget::Row { window, id: *id }),
+            TableRow::Separator(group) => separator_target(group),
         };
-        utils::render_table_window(frame, track_table, rect, n_tracks, playable_table_state);
+        if let Some(target) = target {
+            ui.add_mouse_region(
+                Rect {
+                    y,
+                    height: 1,
+                    ..rows_rect
+                },
+                target,
+            );
+        }
     }
 }
 
@@ -1038,6 +1565,7 @@ fn render_episode_table(
     state: &SharedState,
     episodes: Vec<&Episode>,
     ui: &mut UIStateGuard,
+    data: &DataReadGuard,
 ) {
     let configs = config::get_config();
     // get the current playing episode's URI to decorate such episode (if exists) in the episode table
@@ -1065,6 +1593,18 @@ fn render_episode_table(
             } else {
                 ((id + 1).to_string(), Style::default())
             };
+            // a played episode is marked as played, and a partially played episode
+            // shows its remaining time
+            let progress = if data.user_data.is_played_episode(e) {
+                "played".to_string()
+            } else if let Some(position) = data.user_data.episode_resume_position(e) {
+                format!(
+                    "{} left",
+                    crate::utils::format_long_duration(e.duration.saturating_sub(position))
+                )
+            } else {
+                String::new()
+            };
             Row::new(vec![
                 Cell::from(id),
                 Cell::from(e.name.clone()),
@@ -1074,6 +1614,7 @@ fn render_episode_table(
                     e.duration.as_secs() / 60,
                     e.duration.as_secs() % 60,
                 )),
+                Cell::from(progress),
             ])
             .style(style)
         })
@@ -1085,6 +1626,7 @@ fn render_episode_table(
             Constraint::Fill(6),
             Constraint::Fill(2),
             Constraint::Fill(1),
+            Constraint::Fill(2),
         ],
     )
     .header(
@@ -1093,12 +1635,21 @@ fn render_episode_table(
             Cell::from("Title"),
             Cell::from("Date"),
             Cell::from("Duration"),
+            Cell::from("Progress"),
         ])
         .style(ui.theme.table_header()),
     )
     .column_spacing(2)
     .row_highlight_style(ui.theme.selection(is_active));
 
+    ui.add_mouse_region(
+        table_rows_rect(rect),
+        MouseTarget::Window {
+            window: WindowType::Episodes,
+            len: n_episodes,
+        },
+    );
+
     if let PageState::Context {
         state: Some(state), ..
     } = ui.current_page_mut()
@@ -1110,3 +1661,126 @@ fn render_episode_table(
         utils::render_table_window(frame, episode_table, rect, n_episodes, playable_table_state);
     }
 }
+
+fn render_chapter_table(
+    frame: &mut Frame,
+    rect: Rect,
+    is_active: bool,
+    chapters: Vec<&Chapter>,
+    ui: &mut UIStateGuard,
+) {
+    let n_chapters = chapters.len();
+    let rows = chapters
+        .into_iter()
+        .enumerate()
+        .map(|(id, c)| {
+            // a played chapter is marked as played, and a partially played chapter
+            // shows its remaining time
+            let progress = if c.fully_played {
+                "played".to_string()
+            } else if let Some(position) = c.resume_position {
+                format!(
+                    "{} left",
+                    crate::utils::format_long_duration(c.duration.saturating_sub(position))
+                )
+            } else {
+                String::new()
+            };
+            Row::new(vec![
+                Cell::from((id + 1).to_string()),
+                Cell::from(c.name.clone()),
+                Cell::from(format!(
+                    "{}:{:02}",
+                    c.duration.as_secs() / 60,
+                    c.duration.as_secs() % 60,
+                )),
+                Cell::from(progress),
+            ])
+        })
+        .collect::<Vec<_>>();
+    let chapter_table = Table::new(
+        rows,
+        [
+            Constraint::Length(4),
+            Constraint::Fill(6),
+            Constraint::Fill(1),
+            Constraint::Fill(2),
+        ],
+    )
+    .header(
+        Row::new(vec![
+            Cell::from("#"),
+            Cell::from("Title"),
+            Cell::from("Duration"),
+            Cell::from("Progress"),
+        ])
+        .style(ui.theme.table_header()),
+    )
+    .column_spacing(2)
+    .row_highlight_style(ui.theme.selection(is_active));
+
+    ui.add_mouse_region(
+        table_rows_rect(rect),
+        MouseTarget::Window {
+            window: WindowType::Chapters,
+            len: n_chapters,
+        },
+    );
+
+    if let PageState::Context {
+        state: Some(ContextPageUIState::Audiobook {
+            chapter_table: state,
+        }),
+        ..
+    } = ui.current_page_mut()
+    {
+        utils::render_table_window(frame, chapter_table, rect, n_chapters, state);
+    }
+}
+
+/// Get the header of a track table's column
+fn track_table_column_header(column: TrackTableColumnType) -> &'static str {
+    match column {
+        TrackTableColumnType::Liked | TrackTableColumnType::Explicit => "",
+        TrackTableColumnType::Number => "#",
+        TrackTableColumnType::Title => "Title",
+        TrackTableColumnType::Artists => "Artists",
+        TrackTableColumnType::Album => "Album",
+        TrackTableColumnType::Duration => "Duration",
+        TrackTableColumnType::Track => "Track",
+        TrackTableColumnType::Disc => "Disc",
+        TrackTableColumnType::Popularity => "Popularity",
+        TrackTableColumnType::ReleaseDate => "Release Date",
+        TrackTableColumnType::AddedAt => "Added At",
+        TrackTableColumnType::AddedBy => "Added By",
+        TrackTableColumnType::PlayCount => "Plays",
+    }
+}
+
+/// Get the default width of a track table's column
+fn track_table_column_width(column: TrackTableColumnType) -> Constraint {
+    match column {
+        TrackTableColumnType::Liked => {
+            Constraint::Length(config::get_config().app_config.liked_icon.chars().count() as u16)
+        }
+        TrackTableColumnType::Explicit => Constraint::Length(1),
+        TrackTableColumnType::Number | TrackTableColumnType::Disc => Constraint::Length(4),
+        TrackTableColumnType::Track | TrackTableColumnType::PlayCount => Constraint::Length(5),
+        TrackTableColumnType::Popularity | TrackTableColumnType::AddedAt => Constraint::Length(10),
+        TrackTableColumnType::ReleaseDate => Constraint::Length(12),
+        TrackTableColumnType::Title => Constraint::Fill(4),
+        TrackTableColumnType::Artists => Constraint::Fill(3),
+        TrackTableColumnType::Album => Constraint::Fill(5),
+        TrackTableColumnType::AddedBy => Constraint::Fill(2),
+        TrackTableColumnType::Duration => Constraint::Fill(1),
+    }
+}
+
+/// The rectangle of a table's rows, excluding the table's header
+fn table_rows_rect(rect: Rect) -> Rect {
+    Rect {
+        y: rect.y + 1,
+        height: rect.height.saturating_sub(1),
+        ..rect
+    }
+}
@@ -5,9 +5,10 @@ use anyhow::{Context, Result};
 
 use super::{
     config, utils::construct_and_render_block, Borders, Constraint, Frame, Gauge, Layout, Line,
-    LineGauge, Modifier, Paragraph, PlaybackMetadata, Rect, SharedState, Span, Style, Text,
-    UIStateGuard, Wrap,
+    LineGauge, Modifier, MouseTarget, Paragraph, PlaybackMetadata, Rect, SharedState, Span, Style,
+    Text, UIStateGuard, Wrap,
 };
+use crate::command::{Action, ActionTarget, Command, CommandOrAction};
 
 /// Render a playback window showing information about the current playback, which includes
 /// - track title, artists, album
@@ -36,84 +37,34 @@ pub fn render_playback_window(
                 };
 
                 let metadata_rect = {
-                    // Render the track's cover image if `image` feature is enabled
+                    // Render the track's cover image if `image` feature is enabled,
+                    // unless the cover image is rendered in a separate pane
                     #[cfg(feature = "image")]
                     {
-                        let configs = config::get_config();
-                        // Split the allocated rectangle into `metadata_rect` and `cover_img_rect`
-                        let (metadata_rect, cover_img_rect) = {
-                            let hor_chunks = Layout::horizontal([
-                                Constraint::Length(configs.app_config.cover_img_length as u16),
-                                Constraint::Fill(0), // metadata_rect
-                            ])
-                            .spacing(1)
-                            .split(rect);
-                            let ver_chunks = Layout::vertical([
-                                Constraint::Length(configs.app_config.cover_img_width as u16), // cover_img_rect
-                                Constraint::Fill(0), // empty space
-                            ])
-                            .split(hor_chunks[0]);
-
-                            (hor_chunks[1], ver_chunks[0])
-                        };
-
-                        let url = match item {
-                            rspotify::model::PlayableItem::Track(track) => {
-                                crate::utils::get_track_album_image_url(track).map(String::from)
-                            }
-                            rspotify::model::PlayableItem::Episode(episode) => {
-                                crate::utils::get_episode_show_image_url(episode).map(String::from)
-                            }
-                        };
-                        if let Some(url) = url {
-                            let needs_clear = if ui.last_cover_image_render_info.url != url
-                                || ui.last_cover_image_render_info.render_area != cover_img_rect
-                            {
-                                ui.last_cover_image_render_info = ImageRenderInfo {
-                                    url,
-                                    render_area: cover_img_rect,
-                                    rendered: false,
-                                };
-                                true
-                            } else {
-                                false
+                        if super::pane_layout(ui.orientation).has_pane(config::PaneType::Cover) {
+                            rect
+                        } else {
+                            let configs = config::get_config();
+                            // Split the allocated rectangle into `metadata_rect` and `cover_img_rect`
+                            let (metadata_rect, cover_img_rect) = {
+                                let hor_chunks = Layout::horizontal([
+                                    Constraint::Length(configs.app_config.cover_img_length as u16),
+                                    Constraint::Fill(0), // metadata_rect
+                                ])
+                                .spacing(1)
+                                .split(rect);
+                                let ver_chunks = Layout::vertical([
+                                    Constraint::Length(configs.app_config.cover_img_width as u16), // cover_img_rect
+                                    Constraint::Fill(0), // empty space
+                                ])
+                                .split(hor_chunks[0]);
+
+                                (hor_chunks[1], ver_chunks[0])
                             };
 
-                            if needs_clear {
-                                // clear the image's both new and old areas to ensure no remaining artifacts before rendering the image
-                                // See: https://github.com/aome510/spotify-player/issues/389
-                                clear_area(
-                                    frame,
-                                    ui.last_cover_image_render_info.render_area,
-                                    &ui.theme,
-                                );
-                                clear_area(frame, cover_img_rect, &ui.theme);
-                            } else {
-                                if !ui.last_cover_image_render_info.rendered {
-                                    if let Err(err) = render_playback_cover_image(state, ui) {
-                                        tracing::error!(
-                                            "Failed to render playback's cover image: {err:#}"
-                                        );
-                                    }
-                                }
-
-                                // set the `skip` state of cells in the cover image area
-                                // to prevent buffer from overwriting the image's rendered area
-                                // NOTE: `skip` should not be set when clearing the render area.
-                                // Otherwise, nothing will be clear as the buffer doesn't handle cells with `skip=true`.
-                                for x in cover_img_rect.left()..cover_img_rect.right() {
-                                    for y in cover_img_rect.top()..cover_img_rect.bottom() {
-                                        frame
-                                            .buffer_mut()
-                                            .cell_mut((x, y))
-                                            .expect("invalid cell")
-                                            .set_skip(true);
-                                    }
-                                }
-                            }
+                            render_cover_image(frame, state, ui, item, cover_img_rect);
+                            metadata_rect
                         }
-
-                        metadata_rect
                     }
 
                     #[cfg(not(feature = "image"))]
@@ -126,9 +77,13 @@ pub fn render_playback_window(
             };
 
             if let Some(ref playback) = player.buffered_playback {
-                let playback_text = construct_playback_text(ui, item, playback);
-                let playback_desc = Paragraph::new(playback_text);
-                frame.render_widget(playback_desc, metadata_rect);
+                let (playback_text, buttons) = construct_playback_text(
+                    ui,
+                    item,
+                    playback,
+                    &config::get_config().app_config.playback_format,
+                );
+                render_playback_text(frame, ui, playback_text, &buttons, metadata_rect);
             }
 
             let duration = match item {
@@ -148,16 +103,7 @@ pub fn render_playback_window(
     // Previously rendered image can result in a weird rendering text,
     // clear the previous widget's area before rendering the text.
     #[cfg(feature = "image")]
-    {
-        if ui.last_cover_image_render_info.rendered {
-            clear_area(
-                frame,
-                ui.last_cover_image_render_info.render_area,
-                &ui.theme,
-            );
-            ui.last_cover_image_render_info = ImageRenderInfo::default();
-        }
-    }
+    clear_cover_image(frame, ui);
 
     frame.render_widget(
             Paragraph::new(
@@ -172,6 +118,111 @@ pub fn render_playback_window(
     other_rect
 }
 
+/// Render the cover image of a playable item in a given rectangle
+#[cfg(feature = "image")]
+fn render_cover_image(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    item: &rspotify::model::PlayableItem,
+    cover_img_rect: Rect,
+) {
+    let url = match item {
+        rspotify::model::PlayableItem::Track(track) => {
+            crate::utils::get_track_album_image_url(track).map(String::from)
+        }
+        rspotify::model::PlayableItem::Episode(episode) => {
+            crate::utils::get_episode_show_image_url(episode).map(String::from)
+        }
+    };
+    if let Some(url) = url {
+        let needs_clear = if ui.last_cover_image_render_info.url != url
+            || ui.last_cover_image_render_info.render_area != cover_img_rect
+        {
+            ui.last_cover_image_render_info = ImageRenderInfo {
+                url,
+                render_area: cover_img_rect,
+                rendered: false,
+            };
+            true
+        } else {
+            false
+        };
+
+        if needs_clear {
+            // clear the image's both new and old areas to ensure no remaining artifacts before rendering the image
+            // See: https://github.com/aome510/spotify-player/issues/389
+            clear_area(
+                frame,
+                ui.last_cover_image_render_info.render_area,
+                &ui.theme,
+            );
+            clear_area(frame, cover_img_rect, &ui.theme);
+        } else {
+            if !ui.last_cover_image_render_info.rendered {
+                if let Err(err) = render_playback_cover_image(state, ui) {
+                    tracing::error!("Failed to render playback's cover image: {err:#}");
+                }
+            }
+
+            // set the `skip` state of cells in the cover image area
+            // to prevent buffer from overwriting the image's rendered area
+            // NOTE: `skip` should not be set when clearing the render area.
+            // Otherwise, nothing will be clear as the buffer doesn't handle cells with `skip=true`.
+            for x in cover_img_rect.left()..cover_img_rect.right() {
+                for y in cover_img_rect.top()..cover_img_rect.bottom() {
+                    frame
+                        .buffer_mut()
+                        .cell_mut((x, y))
+                        .expect("invalid cell")
+                        .set_skip(true);
+                }
+            }
+        }
+    }
+}
+
+/// Render a cover image window (pane) for the currently playing item
+pub fn render_cover_image_window(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+) {
+    let rect = construct_and_render_block("Cover", &ui.theme, Borders::ALL, frame, rect);
+
+    #[cfg(feature = "image")]
+    {
+        if let Some(item) = state.player.read().currently_playing() {
+            render_cover_image(frame, state, ui, item, rect);
+            return;
+        }
+        frame.render_widget(Paragraph::new("No playback found"), rect);
+    }
+
+    #[cfg(not(feature = "image"))]
+    {
+        let _ = state;
+        frame.render_widget(
+            Paragraph::new("Rendering cover images requires the `image` feature"),
+            rect,
+        );
+    }
+}
+
+/// Clear the previously rendered cover image (if any)
+#[cfg(feature = "image")]
+fn clear_cover_image(frame: &mut Frame, ui: &mut UIStateGuard) {
+    if ui.last_cover_image_render_info.rendered {
+        clear_area(
+            frame,
+            ui.last_cover_image_render_info.render_area,
+            &ui.theme,
+        );
+        ui.last_cover_image_render_info = ImageRenderInfo::default();
+    }
+}
+
 #[cfg(feature = "image")]
 fn clear_area(frame: &mut Frame, rect: Rect, theme: &config::Theme) {
     for x in rect.left()..rect.right() {
@@ -186,18 +237,132 @@ fn clear_area(frame: &mut Frame, rect: Rect, theme: &config::Theme) {
     }
 }
 
+/// Render a single-line playback status for the mini mode, which includes
+/// the playback text, the playback progress bar and the volume
+pub fn render_mini_playback_window(
+    frame: &mut Frame,
+    state: &SharedState,
+    ui: &mut UIStateGuard,
+    rect: Rect,
+) {
+    let rect = Rect {
+        height: rect.height.min(1),
+        ..rect
+    };
+
+    // the cover image is not rendered in the mini mode
+    #[cfg(feature = "image")]
+    clear_cover_image(frame, ui);
+
+    let player = state.player.read();
+    let (Some(playback), Some(item)) = (
+        player.buffered_playback.as_ref(),
+        player.currently_playing(),
+    ) else {
+        // the progress bar from the previously rendered frame is no longer visible
+        ui.playback_progress_bar_rect = Rect::default();
+        frame.render_widget(Paragraph::new("No playback found"), rect);
+        return;
+    };
+
+    let (playback_text, buttons) = construct_playback_text(
+        ui,
+        item,
+        playback,
+        &config::get_config().app_config.mini_playback_format,
+    );
+    // flatten the playback text into a single line
+    let mut spans = vec![];
+    let mut line_offsets = vec![];
+    for (i, line) in playback_text.lines.into_iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(" "));
+        }
+        line_offsets.push(spans.len());
+        spans.extend(line.spans);
+    }
+    let buttons = buttons
+        .into_iter()
+        .map(|(line_id, span_id, target)| (0, line_offsets[line_id] + span_id, target))
+        .collect::<Vec<_>>();
+
+    let volume = match playback.mute_state {
+        Some(volume) => format!(" vol: {volume}% (muted)"),
+        None => format!(" vol: {}%", playback.volume.unwrap_or_default()),
+    };
+    let chunks = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Length(20),
+        Constraint::Length(volume.chars().count() as u16),
+    ])
+    .spacing(1)
+    .split(rect);
+
+    render_playback_text(
+        frame,
+        ui,
+        Text::from(Line::from(spans)),
+        &buttons,
+        chunks[0],
+    );
+
+    let duration = match item {
+        rspotify::model::PlayableItem::Track(track) => track.duration,
+        rspotify::model::PlayableItem::Episode(episode) => episode.duration,
+    };
+    let progress = std::cmp::min(
+        player.playback_progress().expect("non-empty playback"),
+        duration,
+    );
+    render_playback_progress_bar(frame, ui, progress, duration, chunks[1]);
+
+    frame.render_widget(
+        Paragraph::new(volume).style(ui.theme.playback_metadata()),
+        chunks[2],
+    );
+}
+
+/// Render the playback text and register the clickable regions of its buttons
+fn render_playback_text(
+    frame: &mut Frame,
+    ui: &mut UIStateGuard,
+    playback_text: Text<'static>,
+    buttons: &[(usize, usize, CommandOrAction)],
+    rect: Rect,
+) {
+    for &(line_id, span_id, target) in buttons {
+        let line = &playback_text.lines[line_id];
+        let x = line.spans[..span_id].iter().map(Span::width).sum::<usize>();
+        let button_rect = Rect::new(
+            rect.x.saturating_add(x as u16),
+            rect.y.saturating_add(line_id as u16),
+            line.spans[span_id].width() as u16,
+            1,
+        )
+        .intersection(rect);
+        if !button_rect.is_empty() {
+            ui.add_mouse_region(button_rect, MouseTarget::Button(target));
+        }
+    }
+
+    frame.render_widget(Paragraph::new(playback_text), rect);
+}
+
+/// Construct the playback text from a user-configurable format string together with its buttons,
+/// each of which is represented by the positions of its line and span in the text.
 fn construct_playback_text(
     ui: &UIStateGuard,
     playable: &rspotify::model::PlayableItem,
     playback: &PlaybackMetadata,
-) -> Text<'static> {
+    format_str: &str,
+) -> (Text<'static>, Vec<(usize, usize, CommandOrAction)>) {
     // Construct a "styled" text (`playback_text`) from playback's data
-    // based on a user-configurable format string (app_config.playback_format)
+    // based on a user-configurable format string (e.g. app_config.playback_format)
     let configs = config::get_config();
-    let format_str = &configs.app_config.playback_format;
 
     let mut playback_text = Text::default();
     let mut spans = vec![];
+    let mut buttons = vec![];
 
     // this regex is to handle a format argument or a newline
     let re = regex::Regex::new(r"\{.*?\}|\n").unwrap();
@@ -263,27 +428,67 @@ fn construct_playback_text(
                     (episode.show.name.clone(), ui.theme.playback_album())
                 }
             },
-            "{metadata}" => (
-                format!(
-                    "repeat: {} | shuffle: {} | volume: {} | device: {}",
-                    if playback.fake_track_repeat_state {
-                        "track (fake)"
-                    } else {
-                        <&'static str>::from(playback.repeat_state)
-                    },
-                    playback.shuffle_state,
-                    if let Some(volume) = playback.mute_state {
-                        format!("{volume}% (muted)")
-                    } else {
-                        format!("{}%", playback.volume.unwrap_or_default())
-                    },
-                    playback.device_name,
-                ),
-                ui.theme.playback_metadata(),
-            ),
+            "{metadata}" => {
+                let metadata = [
+                    (
+                        format!(
+                            "repeat: {}",
+                            if playback.fake_track_repeat_state {
+                                "track (fake)"
+                            } else {
+                                <&'static str>::from(playback.repeat_state)
+                            }
+                        ),
+                        Command::Repeat,
+                    ),
+                    (
+                        format!("shuffle: {}", playback.shuffle_state),
+                        Command::Shuffle,
+                    ),
+                    (
+                        format!(
+                            "volume: {}",
+                            if let Some(volume) = playback.mute_state {
+                                format!("{volume}% (muted)")
+                            } else {
+                                format!("{}%", playback.volume.unwrap_or_default())
+                            }
+                        ),
+                        Command::Mute,
+                    ),
+                    (
+                        format!("device: {}", playback.device_name),
+                        Command::SwitchDevice,
+                    ),
+                ];
+                for (i, (text, command)) in metadata.into_iter().enumerate() {
+                    if i > 0 {
+                        spans.push(Span::styled(" | ", ui.theme.playback_metadata()));
+                    }
+                    buttons.push((
+                        playback_text.lines.len(),
+                        spans.len(),
+                        CommandOrAction::Command(command),
+                    ));
+                    spans.push(Span::styled(text, ui.theme.playback_metadata()));
+                }
+                continue;
+            }
             _ => continue,
         };
 
+        // clicking the playback status toggles the playback, clicking the track toggles its liked state
+        let button = match (m.as_str(), playable) {
+            ("{status}", _) => Some(CommandOrAction::Command(Command::ResumePause)),
+            ("{track}", rspotify::model::PlayableItem::Track(_)) => Some(CommandOrAction::Action(
+                Action::ToggleLiked,
+                ActionTarget::PlayingTrack,
+            )),
+            _ => None,
+        };
+        if let Some(button) = button {
+            buttons.push((playback_text.lines.len(), spans.len(), button));
+        }
         spans.push(Span::styled(text, style));
     }
     if ptr < format_str.len() {
@@ -293,7 +498,7 @@ fn construct_playback_text(
         playback_text.lines.push(Line::from(spans));
     }
 
-    playback_text
+    (playback_text, buttons)
 }
 
 fn render_playback_progress_bar(
@@ -3,6 +3,10 @@ use super::{
     Paragraph, PlaylistCreateCurrentField, PlaylistPopupAction, PopupState, Rect, Row, SharedState,
     Table, UIStateGuard,
 };
+use crate::{
+    command,
+    state::{filter_items_by_query, MouseTarget},
+};
 
 const SHORTCUT_TABLE_N_COLUMNS: usize = 3;
 const SHORTCUT_TABLE_CONSTRAINS: [Constraint; SHORTCUT_TABLE_N_COLUMNS] =
@@ -85,6 +89,65 @@ pub fn render_popup(
                 );
                 (rect, false)
             }
+            PopupState::CommandPalette(query, _) => {
+                let items = {
+                    let data = state.data.read();
+                    let player = state.player.read();
+                    let items = command::construct_command_palette_items(&data, &player.devices);
+                    filter_items_by_query(&items, &query.get_text())
+                        .into_iter()
+                        .map(|item| (item.to_string(), false))
+                        .collect::<Vec<_>>()
+                };
+
+                let chunks =
+                    Layout::vertical([Constraint::Fill(0), Constraint::Length(12)]).split(rect);
+                let rect = construct_and_render_block(
+                    "Command Palette",
+                    &ui.theme,
+                    Borders::ALL,
+                    frame,
+                    chunks[1],
+                );
+                let popup_chunks =
+                    Layout::vertical([Constraint::Length(1), Constraint::Fill(0)]).split(rect);
+
+                frame.render_widget(query.widget(true), popup_chunks[0]);
+
+                let (list, len) = utils::construct_list_widget(&ui.theme, items, true);
+                ui.add_mouse_region(popup_chunks[1], MouseTarget::PopupList { len });
+                utils::render_list_window(
+                    frame,
+                    list,
+                    popup_chunks[1],
+                    len,
+                    ui.popup.as_mut().unwrap().list_state_mut().unwrap(),
+                );
+                (chunks[0], false)
+            }
+            PopupState::Confirm { message, .. } => {
+                let chunks =
+                    Layout::vertical([Constraint::Fill(0), Constraint::Length(3)]).split(rect);
+                let rect = construct_and_render_block(
+                    "Confirm",
+                    &ui.theme,
+                    Borders::ALL,
+                    frame,
+                    chunks[1],
+                );
+                frame.render_widget(Paragraph::new(format!("{message} [y/n]")), rect);
+                (chunks[0], false)
+            }
+            PopupState::CommandLine { input, .. } => {
+                let chunks =
+                    Layout::vertical([Constraint::Fill(0), Constraint::Length(1)]).split(rect);
+                let line_chunks = Layout::horizontal([Constraint::Length(1), Constraint::Fill(0)])
+                    .split(chunks[1]);
+
+                frame.render_widget(Paragraph::new(":"), line_chunks[0]);
+                frame.render_widget(input.widget(true), line_chunks[1]);
+                (chunks[0], false)
+            }
             PopupState::DeviceList { .. } => {
                 let player = state.player.read();
 
@@ -113,8 +176,9 @@ pub fn render_popup(
                     PlaylistPopupAction::Browse { folder_id } => {
                         data.user_data.folder_playlists_items(*folder_id)
                     }
-                    PlaylistPopupAction::AddTrack { folder_id, .. }
-                    | PlaylistPopupAction::AddEpisode { folder_id, .. } => {
+                    PlaylistPopupAction::AddTracks { folder_id, .. }
+                    | PlaylistPopupAction::AddEpisode { folder_id, .. }
+                    | PlaylistPopupAction::AddArtistDiscography { folder_id, .. } => {
                         data.user_data.modifiable_playlist_items(Some(*folder_id))
                     }
                 };
@@ -149,6 +213,43 @@ pub fn render_popup(
                 let rect = render_list_popup(frame, rect, "User Saved Albums", items, 7, ui);
                 (rect, false)
             }
+            PopupState::UserSavedShowList { .. } => {
+                let items = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_shows
+                    .iter()
+                    .map(|s| (s.to_string(), false))
+                    .collect();
+
+                let rect = render_list_popup(frame, rect, "User Saved Shows", items, 7, ui);
+                (rect, false)
+            }
+            PopupState::SmartPlaylistList { .. } => {
+                let items = config::get_config()
+                    .smart_playlist_config
+                    .smart_playlists
+                    .iter()
+                    .map(|p| (p.name.clone(), false))
+                    .collect();
+
+                let rect = render_list_popup(frame, rect, "Smart Playlists", items, 7, ui);
+                (rect, false)
+            }
+            PopupState::UserSavedAudiobookList { .. } => {
+                let items = state
+                    .data
+                    .read()
+                    .user_data
+                    .saved_audiobooks
+                    .iter()
+                    .map(|a| (a.to_string(), false))
+                    .collect();
+
+                let rect = render_list_popup(frame, rect, "User Saved Audiobooks", items, 7, ui);
+                (rect, false)
+            }
             PopupState::ArtistList(_, artists, ..) => {
                 let items = artists.iter().map(|a| (a.to_string(), false)).collect();
 
@@ -172,6 +273,7 @@ fn render_list_popup(
 
     let rect = construct_and_render_block(title, &ui.theme, Borders::ALL, frame, chunks[1]);
     let (list, len) = utils::construct_list_widget(&ui.theme, items, true);
+    ui.add_mouse_region(rect, MouseTarget::PopupList { len });
 
     utils::render_list_window(
         frame,
@@ -187,17 +289,20 @@ fn render_list_popup(
 /// Render a shortcut help popup to show the available shortcuts based on user's inputs
 pub fn render_shortcut_help_popup(frame: &mut Frame, ui: &mut UIStateGuard, rect: Rect) -> Rect {
     let input = &ui.input_key_sequence;
+    let count_prefix = ui.count_prefix;
 
     // get the matches (keymaps) from the current key sequence input,
-    // if there is at lease one match, render the shortcut help popup
+    // if there is at lease one match, render the shortcut help popup.
+    // If there is a pending count prefix, only keymaps of commands supporting the count are matched.
     let matches = {
-        if input.keys.is_empty() {
+        if input.keys.is_empty() && count_prefix.is_none() {
             vec![]
         } else {
             config::get_config()
                 .keymap_config
-                .find_matched_prefix_keymaps(input)
+                .find_matched_prefix_keymaps(input, ui.keymap_scope())
                 .into_iter()
+                .filter(|keymap| count_prefix.is_none() || keymap.command.accepts_count())
                 .map(|keymap| {
                     let mut keymap = keymap.clone();
                     keymap.key_sequence.keys.drain(0..input.keys.len());
@@ -213,8 +318,11 @@ pub fn render_shortcut_help_popup(frame: &mut Frame, ui: &mut UIStateGuard, rect
     } else {
         let chunks = Layout::vertical([Constraint::Fill(0), Constraint::Length(7)]).split(rect);
 
-        let rect =
-            construct_and_render_block("Shortcuts", &ui.theme, Borders::ALL, frame, chunks[1]);
+        let title = match count_prefix {
+            Some(count) => format!("Shortcuts (count: {count})"),
+            None => "Shortcuts".to_string(),
+        };
+        let rect = construct_and_render_block(&title, &ui.theme, Borders::ALL, frame, chunks[1]);
 
         let help_table = Table::new(
             matches
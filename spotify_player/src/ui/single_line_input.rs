@@ -35,13 +35,13 @@ impl LineInput {
     }
 
     pub fn input(&mut self, key: &Key) -> Option<InputEffect> {
-        match key {
-            Key::None(c) => match c {
+        match key.plain_code() {
+            Some(c) => match c {
                 KeyCode::Char(c) => {
                     if self.cursor == self.line.len() {
-                        self.line.push(*c);
+                        self.line.push(c);
                     } else {
-                        self.line.insert(self.cursor, *c);
+                        self.line.insert(self.cursor, c);
                     }
                     self.cursor += 1;
                     Some(InputEffect::TextChanged)
@@ -74,7 +74,7 @@ impl LineInput {
                 }
                 _ => None,
             },
-            _ => None,
+            None => None,
         }
     }
 
@@ -111,6 +111,12 @@ impl LineInput {
         self.line.is_empty()
     }
 
+    /// Replace the text with a new one and move the cursor to its end
+    pub fn set_text(&mut self, text: &str) {
+        self.line = text.chars().collect();
+        self.cursor = self.line.len();
+    }
+
     pub fn get_text(&self) -> String {
         self.line.iter().collect()
     }
@@ -6,6 +6,16 @@ pub fn format_duration(duration: &chrono::Duration) -> String {
     format!("{}:{:02}", secs / 60, secs % 60)
 }
 
+/// formats a possibly long time duration into a "{hours} hr {minutes} min" format
+pub fn format_long_duration(duration: std::time::Duration) -> String {
+    let mins = duration.as_secs() / 60;
+    if mins >= 60 {
+        format!("{} hr {} min", mins / 60, mins % 60)
+    } else {
+        format!("{mins} min")
+    }
+}
+
 pub fn map_join<T, F>(v: &[T], f: F, sep: &str) -> String
 where
     F: Fn(&T) -> &str,